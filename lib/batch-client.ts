@@ -0,0 +1,29 @@
+import type {
+  BatchProgress,
+  BatchRequest,
+  BatchSummary,
+} from "./schemas/batch.schema";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { clientError } from "./errors";
+
+// Runs every row of a dataset through one model; results are appended to a JSONL file. Running
+// the same input and output again resumes where the last run stopped.
+export async function runBatch(
+  request: BatchRequest,
+  onProgress?: (progress: BatchProgress) => void,
+): Promise<BatchSummary> {
+  const runId = `batch_${Date.now()}_${Math.random().toString(36).slice(2, 8)}`;
+  const unlisten = await listen<BatchProgress>("batch-progress", (event) => {
+    if (event.payload.run_id === runId) onProgress?.(event.payload);
+  });
+  try {
+    return await invoke<BatchSummary>("run_batch", {
+      request: { ...request, run_id: runId },
+    });
+  } catch (error) {
+    throw clientError("Batch run failed", error);
+  } finally {
+    unlisten();
+  }
+}
@@ -1,6 +1,11 @@
 import type {
+  AppInfo,
   ChatRequest,
   ChatResponse,
+  CompareChunk,
+  CompareResult,
+  ContextReport,
+  ContextSettings,
   PullModelRequest,
   DeleteModelRequest,
   ShowModelRequest,
@@ -8,9 +13,22 @@ import type {
   ListModelsResponse,
   ConfigResponse,
   GeminiContent,
+  GgufInspection,
+  LogEntry,
+  LogSettings,
+  NetworkSettings,
+  Provider,
+  ProviderStatus,
+  ServerStatus,
+  ThinkingChunk,
+  ImportGgufRequest,
+  ImportGgufResult,
+  MemoryEstimate,
+  MemoryEstimateRequest,
 } from "./schemas/client.schema";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { clientError } from "./errors";
 
 // Input validation helpers
 function validateModelName(name: string): void {
@@ -61,7 +79,7 @@ export class OllamaClientClass {
       const response = await invoke<ListModelsResponse>("list_ollama_models");
       return response;
     } catch (error) {
-      throw new Error(`Failed to list models: ${error}`);
+      throw clientError("Failed to list models", error);
     }
   }
 
@@ -71,7 +89,168 @@ export class OllamaClientClass {
       const response = await invoke<string>("pull_model", { modelName: name });
       return response;
     } catch (error) {
-      throw new Error(`Failed to pull model: ${error}`);
+      throw clientError("Failed to pull model", error);
+    }
+  }
+
+  async importGguf({
+    path,
+    name,
+    template,
+  }: ImportGgufRequest): Promise<ImportGgufResult> {
+    validateModelName(name);
+    try {
+      return await invoke<ImportGgufResult>("import_gguf", {
+        path,
+        name,
+        template,
+      });
+    } catch (error) {
+      throw clientError("Failed to import GGUF file", error);
+    }
+  }
+
+  async inspectGguf(
+    source: { path: string } | { model: string },
+  ): Promise<GgufInspection> {
+    try {
+      return await invoke<GgufInspection>("inspect_gguf", source);
+    } catch (error) {
+      throw clientError("Failed to inspect GGUF file", error);
+    }
+  }
+
+  async appInfo(): Promise<AppInfo> {
+    try {
+      return await invoke<AppInfo>("app_info");
+    } catch (error) {
+      throw clientError("Failed to get app info", error);
+    }
+  }
+
+  async getNetworkSettings(): Promise<NetworkSettings> {
+    return await invoke<NetworkSettings>("get_network_settings");
+  }
+
+  async setNetworkSettings(
+    settings: NetworkSettings,
+  ): Promise<NetworkSettings> {
+    try {
+      return await invoke<NetworkSettings>("set_network_settings", {
+        settings,
+      });
+    } catch (error) {
+      throw clientError("Failed to update network settings", error);
+    }
+  }
+
+  async setProviderKey(
+    provider: Provider,
+    apiKey: string | null,
+  ): Promise<void> {
+    try {
+      await invoke("set_provider_key", { provider, apiKey });
+    } catch (error) {
+      throw clientError("Failed to store provider key", error);
+    }
+  }
+
+  async getProviderStatus(): Promise<ProviderStatus[]> {
+    return await invoke<ProviderStatus[]>("get_provider_status");
+  }
+
+  async getServerStatus(): Promise<ServerStatus> {
+    return await invoke<ServerStatus>("get_server_status");
+  }
+
+  async getContextSettings(): Promise<ContextSettings> {
+    return await invoke<ContextSettings>("get_context_settings");
+  }
+
+  async setContextSettings(
+    settings: ContextSettings,
+  ): Promise<ContextSettings> {
+    try {
+      return await invoke<ContextSettings>("set_context_settings", {
+        settings,
+      });
+    } catch (error) {
+      throw clientError("Failed to update context settings", error);
+    }
+  }
+
+  // What would be dropped or summarized if `messages` were sent to `model` now
+  async planContext(
+    model: string,
+    messages: ChatRequest["messages"],
+    options?: ChatRequest["options"],
+  ): Promise<ContextReport> {
+    try {
+      return await invoke<ContextReport>("plan_context", {
+        model,
+        messages,
+        options,
+      });
+    } catch (error) {
+      throw clientError("Failed to plan context", error);
+    }
+  }
+
+  async setServerSettings(settings: {
+    enabled?: boolean;
+    port?: number;
+  }): Promise<ServerStatus> {
+    try {
+      return await invoke<ServerStatus>("set_server_settings", settings);
+    } catch (error) {
+      throw clientError("Failed to update local API server", error);
+    }
+  }
+
+  async regenerateServerToken(): Promise<ServerStatus> {
+    try {
+      return await invoke<ServerStatus>("regenerate_server_token");
+    } catch (error) {
+      throw clientError("Failed to regenerate server token", error);
+    }
+  }
+
+  async getLogSettings(): Promise<LogSettings> {
+    return await invoke<LogSettings>("get_log_settings");
+  }
+
+  async setLogSettings(settings: LogSettings): Promise<LogSettings> {
+    try {
+      return await invoke<LogSettings>("set_log_settings", { settings });
+    } catch (error) {
+      throw clientError("Failed to update log settings", error);
+    }
+  }
+
+  async getRecentLogs(limit?: number, minLevel?: string): Promise<LogEntry[]> {
+    try {
+      return await invoke<LogEntry[]>("get_recent_logs", { limit, minLevel });
+    } catch (error) {
+      throw clientError("Failed to read logs", error);
+    }
+  }
+
+  // Returns the path of the written zip
+  async exportDiagnostics(): Promise<string> {
+    try {
+      return await invoke<string>("export_diagnostics");
+    } catch (error) {
+      throw clientError("Failed to export diagnostics", error);
+    }
+  }
+
+  async estimateMemory(
+    request: MemoryEstimateRequest,
+  ): Promise<MemoryEstimate> {
+    try {
+      return await invoke<MemoryEstimate>("estimate_memory", { request });
+    } catch (error) {
+      throw clientError("Failed to estimate memory", error);
     }
   }
 
@@ -85,7 +264,7 @@ export class OllamaClientClass {
       });
       return { success: true }; // Rust command returns string on success
     } catch (error) {
-      throw new Error(`Failed to delete model: ${error}`);
+      throw clientError("Failed to delete model", error);
     }
   }
 
@@ -97,7 +276,7 @@ export class OllamaClientClass {
       });
       return response;
     } catch (error) {
-      throw new Error(`Failed to show model: ${error}`);
+      throw clientError("Failed to show model", error);
     }
   }
 
@@ -106,7 +285,7 @@ export class OllamaClientClass {
       const response = await invoke<ConfigResponse>("get_ollama_config");
       return response;
     } catch (error) {
-      throw new Error(`Failed to get config: ${error}`);
+      throw clientError("Failed to get config", error);
     }
   }
 
@@ -127,7 +306,7 @@ export class OllamaClientClass {
           return invoke<void>("chat_ollama", { request });
         })
         .catch((error) => {
-          reject(new Error(`Failed to invoke chat_ollama: ${error}`));
+          reject(clientError("Failed to invoke chat_ollama", error));
         });
     });
 
@@ -136,9 +315,23 @@ export class OllamaClientClass {
     return messages;
   }
 
-  async *chatStream(request: ChatRequest): AsyncGenerator<ChatResponse> {
+  // Yields answer chunks; reasoning goes to `onThinking` as it streams and `onContext` learns
+  // what had to be left out to fit the model's context window
+  async *chatStream(
+    request: ChatRequest,
+    onThinking?: (content: string) => void,
+    onContext?: (report: ContextReport) => void,
+  ): AsyncGenerator<ChatResponse> {
     validateChatRequest(request);
     let unsubscribe: () => void;
+    const unsubscribeThinking = await listen<ThinkingChunk>(
+      "ollama-chat-thinking",
+      (event) => onThinking?.(event.payload.content),
+    );
+    const unsubscribeContext = await listen<ContextReport>(
+      "ollama-chat-context",
+      (event) => onContext?.(event.payload),
+    );
 
     const eventQueue: ChatResponse[] = [];
     let resolvePromise: ((value?: unknown) => void) | null = null;
@@ -175,13 +368,49 @@ export class OllamaClientClass {
         }
       }
     } catch (error) {
-      throw new Error(`Failed to invoke chat_ollama: ${error}`);
+      throw clientError("Failed to invoke chat_ollama", error);
     } finally {
       unsubscribe();
+      unsubscribeThinking();
+      unsubscribeContext();
     }
   }
 }
 
+// Runs the same conversation against several models at once; `onChunk` receives each model's
+// tokens as they stream and `onResult` its stats when it finishes
+export async function compareChat(
+  models: string[],
+  messages: ChatRequest["messages"],
+  options: Record<string, unknown> | undefined,
+  onChunk: (chunk: CompareChunk) => void,
+  onResult?: (result: CompareResult) => void,
+): Promise<CompareResult[]> {
+  const runId = `compare_${Date.now()}_${Math.random().toString(36).slice(2, 8)}`;
+  const unlistenChunk = await listen<CompareChunk>("compare-chunk", (event) => {
+    if (event.payload.run_id === runId) onChunk(event.payload);
+  });
+  const unlistenResult = await listen<CompareResult>(
+    "compare-result",
+    (event) => {
+      if (event.payload.run_id === runId) onResult?.(event.payload);
+    },
+  );
+  try {
+    return await invoke<CompareResult[]>("compare_chat", {
+      models,
+      messages,
+      options,
+      runId,
+    });
+  } catch (error) {
+    throw clientError("Failed to compare models", error);
+  } finally {
+    unlistenChunk();
+    unlistenResult();
+  }
+}
+
 export class GeminiClientClass {
   async chat(
     apiKey: string,
@@ -196,7 +425,7 @@ export class GeminiClientClass {
       });
       return response;
     } catch (error) {
-      throw new Error(`Failed to send chat to Gemini: ${error}`);
+      throw clientError("Failed to send chat to Gemini", error);
     }
   }
 }
@@ -0,0 +1,62 @@
+// Mirrors `ZamaError` in src-tauri/src/error.rs
+export type ZamaErrorCode =
+  | "connection_refused"
+  | "timeout"
+  | "network"
+  | "http"
+  | "model_not_found"
+  | "invalid_input"
+  | "provider_auth"
+  | "rate_limited"
+  | "parse"
+  | "cancelled"
+  | "io"
+  | "integrity"
+  | "internal";
+
+export interface ZamaError {
+  code: ZamaErrorCode;
+  message: string;
+  retryable: boolean;
+  service?: string;
+  status?: number;
+  body?: string;
+  model?: string;
+  provider?: string;
+  retry_after?: number | null;
+}
+
+export function isZamaError(error: unknown): error is ZamaError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    "message" in error &&
+    "retryable" in error
+  );
+}
+
+export function errorMessage(error: unknown): string {
+  if (isZamaError(error)) return error.message;
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
+
+// Error thrown by the client wrappers; keeps the backend's code so callers can branch on it
+export class ZamaClientError extends Error {
+  readonly code: ZamaErrorCode;
+  readonly retryable: boolean;
+  readonly details?: ZamaError;
+
+  constructor(context: string, error: unknown) {
+    super(`${context}: ${errorMessage(error)}`);
+    this.name = "ZamaClientError";
+    this.code = isZamaError(error) ? error.code : "internal";
+    this.retryable = isZamaError(error) ? error.retryable : false;
+    this.details = isZamaError(error) ? error : undefined;
+  }
+}
+
+export function clientError(context: string, error: unknown): ZamaClientError {
+  return new ZamaClientError(context, error);
+}
@@ -0,0 +1,64 @@
+import type {
+  EvalDiff,
+  EvalProgress,
+  EvalReport,
+  EvalReportSummary,
+} from "./schemas/evals.schema";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { clientError } from "./errors";
+
+// Runs YAML test suites against local models and keeps every scored report for comparison
+export class EvalsClientClass {
+  // `models` defaults to the suite's own list
+  async run(
+    suite: string,
+    models?: string[],
+    onProgress?: (progress: EvalProgress) => void,
+  ): Promise<EvalReport> {
+    const runId = `eval_${Date.now()}_${Math.random().toString(36).slice(2, 8)}`;
+    const unlisten = await listen<EvalProgress>("eval-progress", (event) => {
+      if (event.payload.run_id === runId) onProgress?.(event.payload);
+    });
+    try {
+      return await invoke<EvalReport>("run_eval", { suite, models, runId });
+    } catch (error) {
+      throw clientError("Eval run failed", error);
+    } finally {
+      unlisten();
+    }
+  }
+
+  async list(suite?: string): Promise<EvalReportSummary[]> {
+    try {
+      return await invoke<EvalReportSummary[]>("list_eval_reports", { suite });
+    } catch (error) {
+      throw clientError("Failed to load eval reports", error);
+    }
+  }
+
+  async get(id: string): Promise<EvalReport> {
+    try {
+      return await invoke<EvalReport>("get_eval_report", { id });
+    } catch (error) {
+      throw clientError("Failed to load eval report", error);
+    }
+  }
+
+  async delete(id: string): Promise<void> {
+    try {
+      await invoke("delete_eval_report", { id });
+    } catch (error) {
+      throw clientError("Failed to delete eval report", error);
+    }
+  }
+
+  // `baseId` defaults to the previous run of the same suite
+  async diff(headId: string, baseId?: string): Promise<EvalDiff> {
+    try {
+      return await invoke<EvalDiff>("diff_eval_reports", { headId, baseId });
+    } catch (error) {
+      throw clientError("Failed to compare eval reports", error);
+    }
+  }
+}
@@ -1,13 +1,31 @@
-import type { HfModel } from "./schemas/hf.schema";
+import type {
+  HfGgufListing,
+  HfModel,
+  HfSearchParams,
+} from "./schemas/hf.schema";
+import type { CatalogPage } from "./schemas/ollamadb.schema";
 import { invoke } from "@tauri-apps/api/core";
+import { clientError } from "./errors";
 
 export class HfClientClass {
   async listModels(search: string = "GGUF"): Promise<HfModel[]> {
+    const page = await this.searchModels({ search });
+    return page.models;
+  }
+
+  async searchModels(params: HfSearchParams): Promise<CatalogPage<HfModel>> {
+    try {
+      return await invoke<CatalogPage<HfModel>>("list_hf_models", { params });
+    } catch (error) {
+      throw clientError("Failed to list Hugging Face models", error);
+    }
+  }
+
+  async listGgufFiles(repo: string): Promise<HfGgufListing> {
     try {
-      const response = await invoke<HfModel[]>("list_hf_models", { search });
-      return response;
+      return await invoke<HfGgufListing>("list_hf_gguf_files", { repo });
     } catch (error) {
-      throw new Error(`Failed to list Hugging Face models: ${error}`);
+      throw clientError(`Failed to list GGUF files for ${repo}`, error);
     }
   }
 }
@@ -0,0 +1,217 @@
+import type { GenerationStats, ModelStats } from "./schemas/client.schema";
+import type {
+  Branch,
+  Conversation,
+  LegacyConversation,
+  MessageRole,
+  TitleSettings,
+} from "./schemas/history.schema";
+import { invoke } from "@tauri-apps/api/core";
+import { clientError } from "./errors";
+
+// Conversation history lives in the backend as a tree; every call returns the active path
+export class HistoryClientClass {
+  async list(): Promise<Conversation[]> {
+    try {
+      return await invoke<Conversation[]>("list_conversations");
+    } catch (error) {
+      throw clientError("Failed to load conversations", error);
+    }
+  }
+
+  async get(conversationId: string): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("get_conversation", { conversationId });
+    } catch (error) {
+      throw clientError("Failed to load conversation", error);
+    }
+  }
+
+  async create(model: string, title?: string): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("create_conversation", {
+        model,
+        title,
+      });
+    } catch (error) {
+      throw clientError("Failed to create conversation", error);
+    }
+  }
+
+  async appendMessage(
+    conversationId: string,
+    role: MessageRole,
+    content: string,
+    model?: string,
+  ): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("append_message", {
+        conversationId,
+        role,
+        content,
+        model,
+      });
+    } catch (error) {
+      throw clientError("Failed to save message", error);
+    }
+  }
+
+  async updateMessage(
+    conversationId: string,
+    messageId: string,
+    content: string,
+    stats?: GenerationStats,
+    thinking?: string,
+  ): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("update_message", {
+        conversationId,
+        messageId,
+        content,
+        stats,
+        thinking,
+      });
+    } catch (error) {
+      throw clientError("Failed to save message", error);
+    }
+  }
+
+  async editMessage(
+    conversationId: string,
+    messageId: string,
+    content: string,
+  ): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("edit_message", {
+        conversationId,
+        messageId,
+        content,
+      });
+    } catch (error) {
+      throw clientError("Failed to edit message", error);
+    }
+  }
+
+  async regenerateMessage(
+    conversationId: string,
+    messageId: string,
+    model?: string,
+  ): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("regenerate_message", {
+        conversationId,
+        messageId,
+        model,
+      });
+    } catch (error) {
+      throw clientError("Failed to regenerate message", error);
+    }
+  }
+
+  async listBranches(
+    conversationId: string,
+    messageId: string,
+  ): Promise<Branch[]> {
+    try {
+      return await invoke<Branch[]>("list_branches", {
+        conversationId,
+        messageId,
+      });
+    } catch (error) {
+      throw clientError("Failed to list branches", error);
+    }
+  }
+
+  async switchBranch(
+    conversationId: string,
+    messageId: string,
+  ): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("switch_branch", {
+        conversationId,
+        messageId,
+      });
+    } catch (error) {
+      throw clientError("Failed to switch branch", error);
+    }
+  }
+
+  async fork(
+    conversationId: string,
+    messageId?: string,
+  ): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("fork_conversation", {
+        conversationId,
+        messageId,
+      });
+    } catch (error) {
+      throw clientError("Failed to fork conversation", error);
+    }
+  }
+
+  async rename(conversationId: string, title: string): Promise<Conversation> {
+    try {
+      return await invoke<Conversation>("rename_conversation", {
+        conversationId,
+        title,
+      });
+    } catch (error) {
+      throw clientError("Failed to rename conversation", error);
+    }
+  }
+
+  // Runs in the background; the result arrives as a `conversation-titled` event
+  async generateTitle(conversationId: string): Promise<void> {
+    try {
+      await invoke("generate_title", { conversationId });
+    } catch (error) {
+      throw clientError("Failed to generate title", error);
+    }
+  }
+
+  async getTitleSettings(): Promise<TitleSettings> {
+    return await invoke<TitleSettings>("get_title_settings");
+  }
+
+  async setTitleSettings(settings: TitleSettings): Promise<TitleSettings> {
+    try {
+      return await invoke<TitleSettings>("set_title_settings", { settings });
+    } catch (error) {
+      throw clientError("Failed to update title settings", error);
+    }
+  }
+
+  async delete(conversationId: string): Promise<void> {
+    try {
+      await invoke("delete_conversation", { conversationId });
+    } catch (error) {
+      throw clientError("Failed to delete conversation", error);
+    }
+  }
+
+  async clear(): Promise<void> {
+    try {
+      await invoke("clear_conversations");
+    } catch (error) {
+      throw clientError("Failed to clear conversations", error);
+    }
+  }
+
+  // Generation metrics aggregated per model, optionally since a Unix millis timestamp
+  async modelStats(model?: string, since?: number): Promise<ModelStats[]> {
+    try {
+      return await invoke<ModelStats[]>("model_stats", { model, since });
+    } catch (error) {
+      throw clientError("Failed to load model stats", error);
+    }
+  }
+
+  async importLegacy(conversations: LegacyConversation[]): Promise<number> {
+    try {
+      return await invoke<number>("import_conversations", { conversations });
+    } catch (error) {
+      throw clientError("Failed to import conversations", error);
+    }
+  }
+}
@@ -0,0 +1,68 @@
+import type {
+  ParameterSet,
+  Presets,
+  ResolvedOptions,
+} from "./schemas/presets.schema";
+import { invoke } from "@tauri-apps/api/core";
+import { clientError } from "./errors";
+
+// Saved chat parameters. A chat resolves them conversation first, then model preset, then
+// global, then the Modelfile.
+export class PresetsClientClass {
+  async get(): Promise<Presets> {
+    try {
+      return await invoke<Presets>("get_presets");
+    } catch (error) {
+      throw clientError("Failed to load presets", error);
+    }
+  }
+
+  async setGlobal(preset: ParameterSet): Promise<Presets> {
+    try {
+      return await invoke<Presets>("set_global_preset", { preset });
+    } catch (error) {
+      throw clientError("Failed to save global preset", error);
+    }
+  }
+
+  // Omitting the preset removes it
+  async setModel(model: string, preset?: ParameterSet): Promise<Presets> {
+    try {
+      return await invoke<Presets>("set_model_preset", { model, preset });
+    } catch (error) {
+      throw clientError("Failed to save model preset", error);
+    }
+  }
+
+  // Omitting the parameters clears the conversation's overrides
+  async setConversation(
+    conversationId: string,
+    parameters?: ParameterSet,
+  ): Promise<ParameterSet | null> {
+    try {
+      return await invoke<ParameterSet | null>("set_conversation_parameters", {
+        conversationId,
+        parameters,
+      });
+    } catch (error) {
+      throw clientError("Failed to save conversation parameters", error);
+    }
+  }
+
+  // What the next chat would run with, and where each value came from
+  async resolve(
+    model: string,
+    conversationId?: string,
+    options?: Record<string, unknown>,
+  ): Promise<ResolvedOptions> {
+    try {
+      return await invoke<ResolvedOptions>("resolve_options", {
+        model,
+        conversationId,
+        options,
+      });
+    } catch (error) {
+      throw clientError("Failed to resolve chat options", error);
+    }
+  }
+}
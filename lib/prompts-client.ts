@@ -0,0 +1,79 @@
+import type {
+  Prompt,
+  PromptImportResult,
+  PromptInput,
+  PromptKind,
+  RenderedPrompt,
+} from "./schemas/prompts.schema";
+import { invoke } from "@tauri-apps/api/core";
+import { clientError } from "./errors";
+
+// Named system prompts and user templates with `{{variable}}` placeholders, kept by the backend
+export class PromptsClientClass {
+  async list(filter?: { tag?: string; kind?: PromptKind }): Promise<Prompt[]> {
+    try {
+      return await invoke<Prompt[]>("list_prompts", { ...filter });
+    } catch (error) {
+      throw clientError("Failed to load prompts", error);
+    }
+  }
+
+  async get(id: string): Promise<Prompt> {
+    try {
+      return await invoke<Prompt>("get_prompt", { id });
+    } catch (error) {
+      throw clientError("Failed to load prompt", error);
+    }
+  }
+
+  // Saving changed content of an existing prompt records a new version
+  async save(prompt: PromptInput): Promise<Prompt> {
+    try {
+      return await invoke<Prompt>("save_prompt", { prompt });
+    } catch (error) {
+      throw clientError("Failed to save prompt", error);
+    }
+  }
+
+  async delete(id: string): Promise<void> {
+    try {
+      await invoke("delete_prompt", { id });
+    } catch (error) {
+      throw clientError("Failed to delete prompt", error);
+    }
+  }
+
+  // Fails unless every variable has a value
+  async render(
+    id: string,
+    vars: Record<string, string>,
+    version?: number,
+  ): Promise<RenderedPrompt> {
+    try {
+      return await invoke<RenderedPrompt>("render_prompt", {
+        id,
+        vars,
+        version,
+      });
+    } catch (error) {
+      throw clientError("Failed to render prompt", error);
+    }
+  }
+
+  async export(format: "json" | "yaml", ids?: string[]): Promise<string> {
+    try {
+      return await invoke<string>("export_prompts", { format, ids });
+    } catch (error) {
+      throw clientError("Failed to export prompts", error);
+    }
+  }
+
+  // Accepts JSON or YAML exports as well as hand-written lists of `name`/`content` entries
+  async import(content: string): Promise<PromptImportResult> {
+    try {
+      return await invoke<PromptImportResult>("import_prompts", { content });
+    } catch (error) {
+      throw clientError("Failed to import prompts", error);
+    }
+  }
+}
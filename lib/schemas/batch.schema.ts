@@ -0,0 +1,50 @@
+import type { ZamaError } from "../errors";
+import type { GenerationStats } from "./client.schema";
+
+export interface BatchRequest {
+  model: string;
+  input_path: string; // `.csv` with a header row, or `.jsonl`
+  output_path?: string; // Default: `<input>.results.jsonl`
+  template?: string; // `{{column}}` placeholders; or `prompt_id`
+  prompt_id?: string;
+  prompt_version?: number;
+  system?: string;
+  options?: Record<string, unknown>;
+  concurrency?: number;
+}
+
+// One line of the output JSONL
+export interface BatchResult {
+  index: number;
+  input_hash: string;
+  model: string;
+  input: Record<string, string>;
+  prompt: string | null;
+  output: string | null;
+  thinking?: string;
+  error: ZamaError | null;
+  started_at: number;
+  duration_ms: number;
+  first_token_ms: number | null;
+  stats: GenerationStats | null;
+}
+
+export interface BatchProgress {
+  run_id: string;
+  total: number;
+  completed: number; // Includes failed and skipped rows
+  failed: number;
+  skipped: number; // Already done by an earlier run
+  index: number | null;
+  error: string | null;
+}
+
+export interface BatchSummary {
+  run_id: string;
+  output_path: string;
+  total: number;
+  succeeded: number;
+  failed: number;
+  skipped: number;
+  duration_ms: number;
+}
@@ -1,3 +1,5 @@
+import type { ZamaError } from "../errors";
+
 export interface OllamaModel {
   name: string;
   modified_at: string;
@@ -17,7 +19,9 @@ export interface ChatRequest {
     content: string;
   }[];
   stream?: boolean;
+  think?: boolean; // Unset: enabled automatically for models that support it
   options?: Record<string, unknown>;
+  conversation_id?: string; // Applies the conversation's saved parameters and presets
 }
 
 export interface ChatResponse {
@@ -25,9 +29,105 @@ export interface ChatResponse {
   created_at: string;
   message: {
     role: "assistant";
-    content: string;
+    content: string; // Answer only; reasoning arrives as `ThinkingChunk`s
   };
   done: boolean;
+  // Timings (nanoseconds) and token counts, only on the final chunk
+  total_duration?: number;
+  load_duration?: number;
+  prompt_eval_count?: number;
+  prompt_eval_duration?: number;
+  eval_count?: number;
+  eval_duration?: number;
+  done_reason?: string;
+  stats?: GenerationStats; // Derived by the backend, only on the final chunk
+}
+
+// Mirrors `GenerationStats` in src-tauri/src/stats.rs
+export interface GenerationStats {
+  total_ms: number | null;
+  load_ms: number | null;
+  prompt_tokens: number | null;
+  prompt_eval_ms: number | null;
+  completion_tokens: number | null;
+  eval_ms: number | null;
+  tokens_per_second: number | null;
+  prompt_tokens_per_second: number | null;
+  time_to_first_token_ms: number | null;
+  done_reason: string | null;
+}
+
+export interface DailyModelStats {
+  day: number;
+  messages: number;
+  avg_tokens_per_second: number | null;
+}
+
+export interface ModelStats {
+  model: string;
+  messages: number;
+  prompt_tokens: number;
+  completion_tokens: number;
+  avg_tokens_per_second: number | null;
+  avg_time_to_first_token_ms: number | null;
+  avg_total_ms: number | null;
+  avg_load_ms: number | null;
+  first_used: number;
+  last_used: number;
+  daily: DailyModelStats[];
+}
+
+export interface ThinkingChunk {
+  model: string;
+  content: string;
+}
+
+export type ContextStrategy = "sliding_window" | "pin_system" | "summarize";
+
+export interface ContextSettings {
+  enabled: boolean;
+  strategy: ContextStrategy;
+  reserve_tokens: number; // Room left for the reply
+}
+
+export interface DroppedMessage {
+  index: number; // Position in the request's messages
+  role: string;
+  preview: string;
+  tokens: number;
+}
+
+// Mirrors `ContextReport` in src-tauri/src/context.rs; token counts are estimates
+export interface ContextReport {
+  model: string;
+  strategy: ContextStrategy;
+  context_length: number;
+  model_context_length: number | null;
+  budget: number;
+  tokens_before: number;
+  tokens_after: number;
+  dropped: DroppedMessage[];
+  summary: string | null;
+  overflow: boolean;
+}
+
+export interface CompareChunk {
+  run_id: string;
+  model: string;
+  content: string;
+}
+
+export interface CompareResult {
+  run_id: string;
+  model: string;
+  provider: Provider;
+  content: string;
+  error: ZamaError | null;
+  first_token_ms: number | null;
+  total_ms: number;
+  prompt_tokens: number | null;
+  completion_tokens: number | null;
+  tokens_per_second: number | null;
 }
 
 export interface PullModelRequest {
@@ -35,6 +135,143 @@ export interface PullModelRequest {
   stream?: boolean;
 }
 
+export interface ImportGgufRequest {
+  path: string;
+  name: string;
+  template?: string;
+}
+
+export interface ImportGgufResult {
+  model: string;
+  digest: string;
+  size: number;
+  architecture: string | null;
+  context_length: number | null;
+  quantization: string | null;
+  chat_template: string | null;
+  modelfile: string;
+  uploaded: boolean;
+}
+
+export interface GgufTensorInfo {
+  name: string;
+  shape: number[];
+  ggml_type: string;
+  offset: number;
+  elements: number;
+  size_bytes: number | null;
+}
+
+export interface GgufInspection {
+  path: string;
+  file_size: number;
+  version: number;
+  name: string | null;
+  architecture: string | null;
+  file_type: string | null;
+  parameter_count: number;
+  context_length: number | null;
+  layer_count: number | null;
+  embedding_length: number | null;
+  feed_forward_length: number | null;
+  head_count: number | null;
+  head_count_kv: number | null;
+  rope: {
+    dimension_count: number | null;
+    freq_base: number | null;
+    scaling_type: string | null;
+    scaling_factor: number | null;
+    original_context_length: number | null;
+  };
+  tokenizer_model: string | null;
+  vocab_size: number | null;
+  chat_template: string | null;
+  tensor_types: {
+    ggml_type: string;
+    tensors: number;
+    elements: number;
+    size_bytes: number;
+  }[];
+  tensors: GgufTensorInfo[];
+  metadata: Record<string, unknown>;
+}
+
+export interface MemoryEstimateRequest {
+  model?: string;
+  parameter_size?: string;
+  quantization_level?: string;
+  file_size?: number;
+  num_ctx?: number;
+  gpu_memory_bytes?: number;
+}
+
+export interface MemoryEstimate {
+  fit: "fit" | "partial_offload" | "wont_fit";
+  parameter_count: number | null;
+  quantization: string | null;
+  num_ctx: number;
+  weights_bytes: number;
+  kv_cache_bytes: number;
+  overhead_bytes: number;
+  total_bytes: number;
+  kv_cache_exact: boolean;
+  system_total_bytes: number;
+  system_available_bytes: number;
+  gpu_memory_bytes: number | null;
+  gpu_layers: number | null;
+  warnings: string[];
+}
+
+export interface AppInfo {
+  name: string;
+  version: string;
+  git_commit: string;
+  git_dirty: boolean;
+  build_date: string;
+  target: string;
+  profile: string;
+  os: string;
+  arch: string;
+  tauri_version: string;
+}
+
+export interface NetworkSettings {
+  connect_timeout_secs: number;
+  read_timeout_secs: number;
+  max_retries: number;
+  proxy_url: string | null;
+  no_proxy: string | null;
+  ca_cert_path: string | null;
+}
+
+export type Provider = "ollama" | "gemini";
+
+export interface ProviderStatus {
+  provider: Provider;
+  configured: boolean;
+}
+
+export interface ServerStatus {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+  base_url: string | null;
+  token: string | null;
+}
+
+export interface LogSettings {
+  level: string;
+  filters: Record<string, string>;
+  redact_content: boolean;
+}
+
+export interface LogEntry {
+  timestamp: string | null;
+  level: string | null;
+  target: string | null;
+  message: string;
+}
+
 export interface DeleteModelRequest {
   name: string;
 }
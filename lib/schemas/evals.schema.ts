@@ -0,0 +1,89 @@
+import type { GenerationStats } from "./client.schema";
+
+export interface AssertionResult {
+  kind: string; // `contains`, `not_contains`, `regex`, `json_schema` or `judge`
+  expected: string;
+  passed: boolean;
+  score: number; // 0 to 1
+  detail: string | null; // Why it failed, or the judge's reasoning
+}
+
+export interface CaseResult {
+  model: string;
+  case: string;
+  prompt: string;
+  output: string | null;
+  error: string | null;
+  passed: boolean;
+  score: number;
+  assertions: AssertionResult[];
+  duration_ms: number;
+  stats: GenerationStats | null;
+}
+
+export interface ModelScore {
+  model: string;
+  score: number; // Mean case score, 0 to 1
+  passed: number;
+  failed: number;
+  errors: number;
+  avg_duration_ms: number;
+}
+
+export interface EvalReport {
+  id: string;
+  suite: string;
+  suite_hash: string;
+  created_at: number;
+  duration_ms: number;
+  baseline_id: string | null; // The previous report of the same suite
+  models: ModelScore[];
+  results: CaseResult[];
+}
+
+export type EvalReportSummary = Omit<
+  EvalReport,
+  "duration_ms" | "baseline_id" | "results"
+>;
+
+export type CaseChangeKind =
+  | "regressed"
+  | "fixed"
+  | "score_changed"
+  | "output_changed"
+  | "added"
+  | "removed";
+
+export interface CaseChange {
+  model: string;
+  case: string;
+  kind: CaseChangeKind;
+  base_score: number | null;
+  head_score: number | null;
+  base_output: string | null;
+  head_output: string | null;
+}
+
+export interface EvalDiff {
+  base_id: string;
+  head_id: string;
+  same_suite: boolean; // False when the suite changed between the runs
+  models: Array<{
+    model: string;
+    base_score: number | null;
+    head_score: number | null;
+    delta: number | null;
+  }>;
+  changes: CaseChange[];
+  regressions: number;
+  fixes: number;
+}
+
+export interface EvalProgress {
+  run_id: string;
+  model: string;
+  case: string;
+  completed: number;
+  total: number;
+  passed: boolean;
+}
@@ -1,12 +1,70 @@
+export interface HfSibling {
+  rfilename: string;
+  size: number | null;
+}
+
 export interface HfModel {
   modelId: string;
-  sha: string;
+  sha: string | null;
   private: boolean;
-  pipeline_tag: string;
+  gated: boolean | "auto" | "manual";
+  pipeline_tag: string | null;
+  library_name: string | null;
   tags: string[];
-  author: string;
-  gated: boolean;
-  lastModified: string;
-  downloads: number;
-  likes: number;
+  author: string | null;
+  lastModified: string | null;
+  createdAt: string | null;
+  downloads: number | null;
+  likes: number | null;
+  siblings: HfSibling[];
+  gguf: {
+    total: number | null;
+    architecture: string | null;
+    context_length: number | null;
+  } | null;
+  safetensors: { total: number | null } | null;
+}
+
+export interface HfSearchParams {
+  search?: string;
+  author?: string;
+  library?: string;
+  license?: string;
+  pipelineTag?: string;
+  tags?: string[];
+  minParams?: string;
+  maxParams?: string;
+  sort?: "downloads" | "likes" | "lastModified" | "createdAt" | "trendingScore";
+  ascending?: boolean;
+  limit?: number;
+  cursor?: string;
+}
+
+export interface QuantType {
+  name: string;
+  bits_per_weight: number;
+}
+
+export interface HfGgufFile {
+  path: string;
+  size: number;
+  sha256: string | null;
+  quant: QuantType | null;
+  shard: [number, number] | null;
+  is_projector: boolean;
+}
+
+export interface HfGgufQuantization {
+  quant: string;
+  bits_per_weight: number | null;
+  files: string[];
+  total_size: number;
+  estimated_memory_bytes: number;
+  pull_name: string | null;
+}
+
+export interface HfGgufListing {
+  repo: string;
+  files: HfGgufFile[];
+  quantizations: HfGgufQuantization[];
 }
@@ -0,0 +1,60 @@
+import type { GenerationStats } from "./client.schema";
+import type { ParameterSet } from "./presets.schema";
+
+export type MessageRole = "user" | "assistant" | "system";
+
+// A message on the conversation's active path
+export interface Message {
+  id: string;
+  parentId: string | null;
+  role: MessageRole;
+  content: string;
+  timestamp: number;
+  model?: string;
+  stats?: GenerationStats; // Set on assistant replies that reported metrics
+  thinking?: string; // Reasoning, kept apart from the answer
+  siblingIndex: number; // Position among edits/regenerations of this message
+  siblingCount: number;
+}
+
+export interface ForkOrigin {
+  conversationId: string;
+  messageId: string;
+}
+
+export interface Conversation {
+  id: string;
+  title: string;
+  model: string;
+  createdAt: number;
+  updatedAt: number;
+  messages: Message[];
+  forkedFrom: ForkOrigin | null;
+  summary: string | null; // One paragraph written by the titling model
+  parameters: ParameterSet | null; // Overrides of the model and global presets
+}
+
+// Which local model names and summarizes conversations after the first exchange
+export interface TitleSettings {
+  enabled: boolean;
+  model: string | null; // Null: the conversation's own Ollama model
+}
+
+export interface Branch {
+  messageId: string;
+  preview: string;
+  timestamp: number;
+  model: string | null;
+  active: boolean;
+  replyCount: number;
+}
+
+// Flat history format kept in localStorage by earlier versions
+export interface LegacyConversation {
+  id: string;
+  title: string;
+  model: string;
+  createdAt: number;
+  updatedAt: number;
+  messages: Array<{ role: MessageRole; content: string; timestamp: number }>;
+}
@@ -20,3 +20,21 @@ export interface OllamaDBResponse {
   skip: number;
   data_updated: string;
 }
+
+export interface CatalogPage<T> {
+  models: T[];
+  total: number;
+  fetched_at: number;
+  stale: boolean;
+  from_cache: boolean;
+}
+
+export interface CatalogFilter {
+  search?: string;
+  labels?: string[];
+  capability?: string;
+  official_only?: boolean;
+  sort?: "popularity" | "name" | "updated" | "likes";
+  ascending?: boolean;
+  limit?: number;
+}
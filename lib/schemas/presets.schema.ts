@@ -0,0 +1,33 @@
+// Ollama options such as `temperature` or `num_ctx`, plus an optional system prompt
+export interface ParameterSet {
+  system?: string;
+  options?: Record<string, unknown>;
+}
+
+// Model keys are a full name, a name without tag (every tag) or a `*` pattern
+export interface Presets {
+  global: ParameterSet;
+  models: Record<string, ParameterSet>;
+}
+
+export type OptionSource =
+  | "request"
+  | "conversation"
+  | "model"
+  | "global"
+  | "modelfile";
+
+export interface ResolvedValue {
+  key: string; // An Ollama option, or `system`
+  value: unknown;
+  source: OptionSource;
+  preset?: string; // Model preset key the value came from
+  overrides: OptionSource[]; // Lower levels that also set this key
+}
+
+export interface ResolvedOptions {
+  model: string;
+  options: Record<string, unknown>;
+  system: string | null;
+  values: ResolvedValue[];
+}
@@ -0,0 +1,47 @@
+export type PromptKind = "system" | "user";
+
+export interface PromptVersion {
+  version: number;
+  content: string;
+  createdAt: number;
+  note?: string;
+}
+
+// A library prompt; `content` and `variables` are those of the current (latest) version
+export interface Prompt {
+  id: string;
+  name: string;
+  kind: PromptKind;
+  description?: string;
+  tags: string[];
+  versions: PromptVersion[];
+  createdAt: number;
+  updatedAt: number;
+  content: string;
+  version: number;
+  variables: string[]; // `{{name}}` placeholders that need values
+}
+
+export interface PromptInput {
+  id?: string; // Omitted to create a prompt
+  name: string;
+  kind: PromptKind;
+  description?: string;
+  tags: string[];
+  content: string;
+  note?: string; // What changed in this version
+}
+
+export interface RenderedPrompt {
+  id: string;
+  name: string;
+  kind: PromptKind;
+  version: number;
+  content: string;
+}
+
+export interface PromptImportResult {
+  created: number;
+  updated: number;
+  unchanged: number;
+}
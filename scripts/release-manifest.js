@@ -0,0 +1,123 @@
+#!/usr/bin/env node
+
+// Builds the signed release manifests the in-app updater reads from pkg/{channel}.json.
+//
+//   sign <version> <platform> <kind> <file> <url> <out>
+//     Hashes one updater artifact and signs it with ZAMA_UPDATER_PRIVATE_KEY (base64 Ed25519
+//     seed, the private half of ZAMA_UPDATER_PUBLIC_KEY), writing a manifest fragment to <out>.
+//   publish <version> <fragment>...
+//     Merges the fragments into the manifest of every channel that accepts <version>.
+
+import {
+  createHash,
+  createPrivateKey,
+  createPublicKey,
+  sign,
+} from "node:crypto";
+import { readFileSync, statSync, writeFileSync } from "node:fs";
+import { argv, env, exit } from "node:process";
+
+const CHANNELS = ["stable", "beta", "nightly"];
+const KINDS = ["binary", "app_bundle"];
+// PKCS#8 prefix for a raw 32-byte Ed25519 seed
+const ED25519_PKCS8_PREFIX = Buffer.from(
+  "302e020100300506032b657004220420",
+  "hex",
+);
+
+function fail(message) {
+  console.error(`[zama](failure) ${message}`);
+  exit(1);
+}
+
+// Must match `signed_message` in src-tauri/src/updater.rs
+function signedMessage(version, platform, sha256) {
+  return `zama-update\n${version}\n${platform}\n${sha256.toLowerCase()}`;
+}
+
+// Mirrors `UpdateChannel::accepts`: beta also takes -beta/-rc, nightly takes anything
+function channelsFor(version) {
+  const pre = version.split("-").slice(1).join("-");
+  if (!pre) {
+    return CHANNELS;
+  }
+  if (pre.startsWith("beta") || pre.startsWith("rc")) {
+    return ["beta", "nightly"];
+  }
+  return ["nightly"];
+}
+
+function signArtifact([version, platform, kind, file, url, out]) {
+  if (!out) {
+    fail("usage: sign <version> <platform> <kind> <file> <url> <out>");
+  }
+  if (!KINDS.includes(kind)) {
+    fail(`Unknown artifact kind ${kind}, expected one of ${KINDS.join(", ")}`);
+  }
+  const seed = Buffer.from(env.ZAMA_UPDATER_PRIVATE_KEY ?? "", "base64");
+  if (seed.length !== 32) {
+    fail("ZAMA_UPDATER_PRIVATE_KEY must be a base64 32-byte Ed25519 seed");
+  }
+  const key = createPrivateKey({
+    key: Buffer.concat([ED25519_PKCS8_PREFIX, seed]),
+    format: "der",
+    type: "pkcs8",
+  });
+  // Refuse to publish signatures the shipped app would reject
+  const publicKey = createPublicKey(key)
+    .export({ format: "der", type: "spki" })
+    .subarray(-32)
+    .toString("base64");
+  const expected = env.ZAMA_UPDATER_PUBLIC_KEY?.trim();
+  if (expected && expected !== publicKey) {
+    fail("ZAMA_UPDATER_PRIVATE_KEY does not match ZAMA_UPDATER_PUBLIC_KEY");
+  }
+
+  const sha256 = createHash("sha256").update(readFileSync(file)).digest("hex");
+  const signature = sign(
+    null,
+    Buffer.from(signedMessage(version, platform, sha256)),
+    key,
+  ).toString("base64");
+  const fragment = {
+    version,
+    platform,
+    artifact: { url, sha256, signature, size: statSync(file).size, kind },
+  };
+  writeFileSync(out, `${JSON.stringify(fragment, null, 2)}\n`);
+  console.log(`[zama](success) Signed ${file} for ${platform}`);
+}
+
+function publish([version, ...fragments]) {
+  if (!version || fragments.length === 0) {
+    fail("usage: publish <version> <fragment>...");
+  }
+  const platforms = {};
+  for (const path of fragments) {
+    const fragment = JSON.parse(readFileSync(path, "utf8"));
+    if (fragment.version !== version) {
+      fail(`${path} was signed for ${fragment.version}, not ${version}`);
+    }
+    platforms[fragment.platform] = fragment.artifact;
+  }
+  const manifest = {
+    version,
+    notes: env.RELEASE_NOTES || null,
+    pub_date: new Date().toISOString(),
+    platforms,
+  };
+  for (const channel of channelsFor(version)) {
+    const path = `pkg/${channel}.json`;
+    writeFileSync(path, `${JSON.stringify(manifest, null, 2)}\n`);
+    console.log(`[zama](success) Published ${version} to ${path}`);
+  }
+}
+
+const [command, ...args] = argv.slice(2);
+if (command === "sign") {
+  signArtifact(args);
+} else if (command === "publish") {
+  publish(args);
+} else {
+  fail("usage: release-manifest.js sign|publish ...");
+}
@@ -0,0 +1,42 @@
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+// Baked in at compile time so the running binary always reports its own version
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_COMMIT: &str = env!("ZAMA_GIT_COMMIT");
+pub const BUILD_DATE: &str = env!("ZAMA_BUILD_DATE");
+pub const BUILD_TARGET: &str = env!("ZAMA_BUILD_TARGET");
+pub const BUILD_PROFILE: &str = env!("ZAMA_BUILD_PROFILE");
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AppInfo {
+    pub name: String,
+    pub version: String,
+    pub git_commit: String,
+    pub git_dirty: bool, // Built from a checkout with uncommitted changes
+    pub build_date: String,
+    pub target: String,
+    pub profile: String,
+    pub os: String,
+    pub arch: String,
+    pub tauri_version: String,
+}
+
+pub fn app_info<R: Runtime>(app_handle: &AppHandle<R>) -> AppInfo {
+    for_package(&app_handle.package_info().name)
+}
+
+pub fn for_package(name: &str) -> AppInfo {
+    AppInfo {
+        name: name.to_string(),
+        version: VERSION.to_string(),
+        git_commit: GIT_COMMIT.to_string(),
+        git_dirty: env!("ZAMA_GIT_DIRTY") == "true",
+        build_date: BUILD_DATE.to_string(),
+        target: BUILD_TARGET.to_string(),
+        profile: BUILD_PROFILE.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+    }
+}
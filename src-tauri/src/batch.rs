@@ -0,0 +1,422 @@
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::io::AsyncWriteExt;
+
+use crate::conversations::{new_id, unix_millis};
+use crate::error::ZamaError;
+use crate::http::{HttpClient, HttpState};
+use crate::ollama::{self, ChatEvent, ChatRequest, Message};
+use crate::prompts::{self, PromptLibrary};
+use crate::stats::GenerationStats;
+
+const PROGRESS_EVENT: &str = "batch-progress";
+const DEFAULT_CONCURRENCY: usize = 2;
+const MAX_CONCURRENCY: usize = 8; // Ollama queues anything above `OLLAMA_NUM_PARALLEL` anyway
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BatchRequest {
+    pub model: String,
+    pub input_path: String, // `.csv` with a header row, or `.jsonl` with one object per line
+    pub output_path: Option<String>, // Default: `<input>.results.jsonl` next to the input
+    pub template: Option<String>, // `{{column}}` placeholders, filled per row
+    pub prompt_id: Option<String>, // Or a prompt from the library
+    pub prompt_version: Option<u32>,
+    pub system: Option<String>,
+    pub options: Option<Map<String, Value>>,
+    pub concurrency: Option<usize>,
+    pub run_id: Option<String>, // Lets the caller subscribe before the first progress event
+}
+
+struct BatchRow {
+    index: usize, // Position among the data rows, from 0
+    vars: HashMap<String, String>,
+    input_hash: String,
+}
+
+// One line of the output file
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    pub index: usize,
+    pub input_hash: String, // Ties the result to the row's content when resuming
+    pub model: String,
+    pub input: BTreeMap<String, String>,
+    pub prompt: Option<String>,
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+    pub error: Option<ZamaError>,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub first_token_ms: Option<u64>,
+    pub stats: Option<GenerationStats>,
+}
+
+// What resuming needs from earlier output lines
+#[derive(Debug, Deserialize)]
+struct FinishedRow {
+    index: usize,
+    input_hash: String,
+    error: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BatchProgress {
+    pub run_id: String,
+    pub total: usize,
+    pub completed: usize, // Includes `failed` and `skipped`
+    pub failed: usize,
+    pub skipped: usize,       // Already done by an earlier run
+    pub index: Option<usize>, // Row that just finished
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BatchSummary {
+    pub run_id: String,
+    pub output_path: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub duration_ms: u64,
+}
+
+fn row_hash(vars: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<&String, &String> = vars.iter().collect();
+    let mut hasher = DefaultHasher::new();
+    sorted.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn read_csv(path: &Path) -> Result<Vec<HashMap<String, String>>, ZamaError> {
+    let mut reader = csv::Reader::from_path(path)
+        .map_err(|e| ZamaError::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| ZamaError::InvalidInput(format!("Invalid CSV header: {}", e)))?
+        .iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    reader
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let record = record.map_err(|e| {
+                ZamaError::InvalidInput(format!("Invalid CSV row {}: {}", i + 1, e))
+            })?;
+            Ok(headers
+                .iter()
+                .cloned()
+                .zip(record.iter().map(str::to_string))
+                .collect())
+        })
+        .collect()
+}
+
+fn read_jsonl(path: &Path) -> Result<Vec<HashMap<String, String>>, ZamaError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ZamaError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let object: Map<String, Value> = serde_json::from_str(line).map_err(|e| {
+                ZamaError::InvalidInput(format!("Line {} is not a JSON object: {}", i + 1, e))
+            })?;
+            Ok(object
+                .into_iter()
+                .map(|(key, value)| match value {
+                    Value::String(s) => (key, s),
+                    other => (key, other.to_string()),
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn read_rows(path: &Path) -> Result<Vec<BatchRow>, ZamaError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    let rows = match extension.as_deref() {
+        Some("csv") => read_csv(path)?,
+        Some("jsonl") | Some("ndjson") => read_jsonl(path)?,
+        _ => {
+            return Err(ZamaError::InvalidInput(
+                "Batch input must be a .csv or .jsonl file".to_string(),
+            ))
+        }
+    };
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, vars)| BatchRow {
+            index,
+            input_hash: row_hash(&vars),
+            vars,
+        })
+        .collect())
+}
+
+// Rows an earlier run already answered. Failed rows and rows whose content changed run again; a
+// line cut off by a crash is ignored.
+async fn finished_rows(output: &Path) -> HashSet<(usize, String)> {
+    let Ok(content) = tokio::fs::read_to_string(output).await else {
+        return HashSet::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<FinishedRow>(line).ok())
+        .filter(|row| row.error.as_ref().is_none_or(Value::is_null))
+        .map(|row| (row.index, row.input_hash))
+        .collect()
+}
+
+async fn open_output(path: &Path) -> Result<tokio::fs::File, ZamaError> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    // Start on a fresh line if the last run died mid-write
+    let needs_newline = tokio::fs::read(path)
+        .await
+        .map(|bytes| bytes.last().is_some_and(|b| *b != b'\n'))
+        .unwrap_or(false);
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    if needs_newline {
+        file.write_all(b"\n").await?;
+    }
+    Ok(file)
+}
+
+async fn run_row<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    client: &HttpClient,
+    request: &BatchRequest,
+    template: &str,
+    row: BatchRow,
+) -> BatchResult {
+    let started_at = unix_millis();
+    let started = Instant::now();
+    let mut result = BatchResult {
+        index: row.index,
+        input_hash: row.input_hash,
+        model: request.model.clone(),
+        input: row.vars.clone().into_iter().collect(),
+        prompt: None,
+        output: None,
+        thinking: None,
+        error: None,
+        started_at,
+        duration_ms: 0,
+        first_token_ms: None,
+        stats: None,
+    };
+
+    let prompt = match prompts::render(template, &row.vars) {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    let mut messages = Vec::new();
+    if let Some(system) = request.system.as_ref().filter(|s| !s.trim().is_empty()) {
+        messages.push(Message {
+            role: "system".to_string(),
+            content: system.clone(),
+            thinking: None,
+        });
+    }
+    messages.push(Message {
+        role: "user".to_string(),
+        content: prompt.clone(),
+        thinking: None,
+    });
+    let chat = ChatRequest {
+        model: request.model.clone(),
+        messages,
+        stream: true,
+        think: None,
+        options: request.options.clone(),
+        conversation_id: None,
+    };
+
+    let mut output = String::new();
+    let mut thinking = String::new();
+    let outcome = ollama::stream_chat(app_handle, &app_handle.state(), client, &chat, |event| {
+        match event {
+            ChatEvent::Context(_) => {}
+            ChatEvent::Thinking(content) => thinking.push_str(&content),
+            ChatEvent::Chunk(chunk) => {
+                if let Some(message) = chunk.message {
+                    output.push_str(&message.content);
+                }
+                if chunk.done {
+                    result.stats = chunk.stats;
+                }
+            }
+        }
+        Ok(())
+    })
+    .await;
+
+    result.prompt = Some(prompt);
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    result.first_token_ms = result
+        .stats
+        .as_ref()
+        .and_then(|stats| stats.time_to_first_token_ms)
+        .map(|ms| ms as u64);
+    match outcome {
+        Ok(()) => {
+            result.output = Some(output);
+            result.thinking = Some(thinking).filter(|t| !t.is_empty());
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
+
+// Runs every row of a CSV or JSONL file through one model and appends each result to a JSONL
+// file as soon as it's in. Running the same input and output again resumes: rows that already
+// succeeded are skipped. Progress arrives on `batch-progress`.
+#[tauri::command]
+pub async fn run_batch<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    request: BatchRequest,
+) -> Result<BatchSummary, ZamaError> {
+    ollama::validate_model_name(&request.model)?;
+    let template = match (&request.template, &request.prompt_id) {
+        (Some(template), None) => template.clone(),
+        (None, Some(id)) => {
+            let (id, version) = (id.clone(), request.prompt_version);
+            app_handle
+                .state::<PromptLibrary>()
+                .run(move |library| Ok(library.get(&id)?.version(version)?.content.clone()))
+                .await?
+        }
+        _ => {
+            return Err(ZamaError::InvalidInput(
+                "Provide either a template or a prompt from the library".to_string(),
+            ))
+        }
+    };
+    if template.trim().is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "The prompt template is empty".to_string(),
+        ));
+    }
+
+    let input_path = PathBuf::from(&request.input_path);
+    let output_path = request
+        .output_path
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input_path.with_extension("results.jsonl"));
+    if output_path == input_path {
+        return Err(ZamaError::InvalidInput(
+            "The output file must differ from the input".to_string(),
+        ));
+    }
+    let rows = tokio::task::spawn_blocking({
+        let input_path = input_path.clone();
+        move || read_rows(&input_path)
+    })
+    .await??;
+
+    // Catch a template/column mismatch before running hundreds of rows into the same error
+    let columns: HashSet<&String> = rows.iter().flat_map(|row| row.vars.keys()).collect();
+    let unknown: Vec<String> = prompts::variables(&template)
+        .into_iter()
+        .filter(|name| !columns.contains(name))
+        .collect();
+    if !rows.is_empty() && !unknown.is_empty() {
+        return Err(ZamaError::InvalidInput(format!(
+            "The input has no column for: {}",
+            unknown.join(", ")
+        )));
+    }
+
+    let run_id = request.run_id.clone().unwrap_or_else(|| new_id("batch"));
+    let concurrency = request
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+    let total = rows.len();
+    let finished = finished_rows(&output_path).await;
+    let (done, pending): (Vec<BatchRow>, Vec<BatchRow>) = rows
+        .into_iter()
+        .partition(|row| finished.contains(&(row.index, row.input_hash.clone())));
+    let mut progress = BatchProgress {
+        run_id: run_id.clone(),
+        total,
+        completed: done.len(),
+        failed: 0,
+        skipped: done.len(),
+        index: None,
+        error: None,
+    };
+    tracing::info!(
+        %run_id,
+        model = %request.model,
+        total,
+        skipped = progress.skipped,
+        concurrency,
+        "Starting batch run"
+    );
+    app_handle.emit(PROGRESS_EVENT, &progress)?;
+
+    let started = Instant::now();
+    let mut file = open_output(&output_path).await?;
+    let client = http.client();
+    let mut results = stream::iter(pending)
+        .map(|row| run_row(&app_handle, &client, &request, &template, row))
+        .buffer_unordered(concurrency);
+    while let Some(result) = results.next().await {
+        let mut line = serde_json::to_string(&result)
+            .map_err(|e| ZamaError::Internal(format!("Failed to serialize batch result: {}", e)))?;
+        line.push('\n');
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+
+        progress.completed += 1;
+        progress.index = Some(result.index);
+        progress.error = result.error.as_ref().map(|e| e.to_string());
+        if let Some(error) = &result.error {
+            progress.failed += 1;
+            tracing::warn!(%run_id, row = result.index, %error, "Batch row failed");
+        }
+        app_handle.emit(PROGRESS_EVENT, &progress)?;
+    }
+
+    let summary = BatchSummary {
+        run_id,
+        output_path: output_path.display().to_string(),
+        total,
+        succeeded: progress.completed - progress.failed - progress.skipped,
+        failed: progress.failed,
+        skipped: progress.skipped,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    tracing::info!(
+        run_id = %summary.run_id,
+        succeeded = summary.succeeded,
+        failed = summary.failed,
+        "Batch run finished"
+    );
+    Ok(summary)
+}
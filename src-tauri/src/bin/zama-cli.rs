@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+    zama_lib::cli::run()
+}
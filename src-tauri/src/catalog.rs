@@ -0,0 +1,394 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::error::ZamaError;
+use crate::hf::HfModel;
+use crate::paths::{self, AppPaths};
+
+// Catalogs older than this are still served, but a refresh is kicked off in the background
+pub const CATALOG_TTL_SECS: u64 = 6 * 60 * 60;
+
+const CATALOG_DIR: &str = "catalog";
+// Every HF search and cursor is cached on its own, so only the most recently written are kept
+const MAX_CACHED_CATALOGS: usize = 200;
+
+// Keys of catalogs currently being revalidated, so repeated page loads don't stack up requests
+static REVALIDATING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+// --- OllamaDB Schemas ---
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OllamaDbModel {
+    pub model_identifier: String,
+    pub namespace: Option<String>,
+    pub model_name: String,
+    pub model_type: String,
+    pub description: String,
+    pub capability: Option<String>,
+    pub labels: Vec<String>,
+    pub pulls: u64,
+    pub tags: u64,
+    pub last_updated: String,
+    pub last_updated_str: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OllamaDbResponse {
+    pub models: Vec<OllamaDbModel>,
+    pub total_count: u64,
+    pub limit: u64,
+    pub skip: u64,
+    pub data_updated: String,
+}
+
+// --- Cache Schemas ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedCatalog<T> {
+    pub key: String,
+    pub fetched_at: u64, // Unix seconds
+    pub data: T,
+}
+
+impl<T> CachedCatalog<T> {
+    pub fn is_stale(&self) -> bool {
+        unix_now().saturating_sub(self.fetched_at) > CATALOG_TTL_SECS
+    }
+}
+
+// What a catalog command hands back to the frontend
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CatalogPage<T> {
+    pub models: Vec<T>,
+    pub total: usize, // Number of entries before filtering
+    pub fetched_at: u64,
+    pub stale: bool,
+    pub from_cache: bool,
+    pub next_cursor: Option<String>, // Only set for paginated catalogs
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CatalogUpdatedEvent {
+    pub key: String,
+    pub fetched_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogSort {
+    #[default]
+    Popularity,
+    Name,
+    Updated,
+    Likes,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CatalogFilter {
+    pub search: Option<String>,
+    pub labels: Vec<String>,
+    pub capability: Option<String>,
+    pub official_only: bool,
+    pub sort: CatalogSort,
+    pub ascending: bool,
+    pub limit: Option<usize>,
+}
+
+// --- Cache Store ---
+
+pub struct CatalogStore {
+    dir: PathBuf,
+}
+
+impl CatalogStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn init(paths: &impl AppPaths) -> Result<Self, String> {
+        Ok(Self::new(paths.data_dir()?.join(CATALOG_DIR)))
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", cache_file_stem(key)))
+    }
+
+    pub fn read<T: DeserializeOwned>(&self, key: &str) -> Option<CachedCatalog<T>> {
+        match paths::load_json::<CachedCatalog<T>>(&self.path_for(key)) {
+            Ok(Some(cached)) if cached.key == key => Some(cached),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!(%key, error = %e, "Ignoring unreadable catalog cache");
+                None
+            }
+        }
+    }
+
+    pub fn write<T: Serialize>(&self, cached: &CachedCatalog<T>) -> Result<(), ZamaError> {
+        paths::write_json_atomic(&self.path_for(&cached.key), cached)?;
+        self.evict(MAX_CACHED_CATALOGS);
+        Ok(())
+    }
+
+    // Removes the least recently written caches beyond `keep`
+    pub fn evict(&self, keep: usize) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut caches: Vec<(SystemTime, PathBuf)> = entries
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect();
+        if caches.len() <= keep {
+            return;
+        }
+        caches.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+        for (_, path) in caches.drain(keep..) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to evict catalog cache");
+            }
+        }
+    }
+}
+
+// Serves `key` from the cache when possible. Fresh entries are returned as is, stale entries are
+// returned immediately while `fetch` refreshes them in the background (emitting `catalog-updated`
+// once done), and a missing cache is fetched inline. If the network is unreachable the last cached
+// copy is served regardless of its age.
+pub async fn load<R: Runtime, T, F, Fut>(
+    app_handle: &AppHandle<R>,
+    key: &str,
+    force_refresh: bool,
+    fetch: F,
+) -> Result<(CachedCatalog<T>, bool), ZamaError>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, ZamaError>> + Send + 'static,
+{
+    let store = CatalogStore::init(app_handle)?;
+    let cached = store.read::<T>(key);
+
+    match cached {
+        Some(cached) if !force_refresh && !cached.is_stale() => Ok((cached, true)),
+        Some(cached) if !force_refresh => {
+            spawn_revalidation(app_handle.clone(), store, key.to_string(), fetch);
+            Ok((cached, true))
+        }
+        cached => match fetch().await {
+            Ok(data) => {
+                let fresh = CachedCatalog {
+                    key: key.to_string(),
+                    fetched_at: unix_now(),
+                    data,
+                };
+                if let Err(e) = store.write(&fresh) {
+                    tracing::warn!(%key, error = %e, "Failed to cache catalog");
+                }
+                Ok((fresh, false))
+            }
+            Err(e) => match cached {
+                Some(cached) => {
+                    tracing::warn!(%key, error = %e, "Serving cached catalog after fetch failed");
+                    Ok((cached, true))
+                }
+                None => Err(e),
+            },
+        },
+    }
+}
+
+fn spawn_revalidation<R: Runtime, T, F, Fut>(
+    app_handle: AppHandle<R>,
+    store: CatalogStore,
+    key: String,
+    fetch: F,
+) where
+    T: Serialize + Send + 'static,
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, ZamaError>> + Send + 'static,
+{
+    let inflight = REVALIDATING.get_or_init(|| Mutex::new(HashSet::new()));
+    if !inflight.lock().unwrap().insert(key.clone()) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        match fetch().await {
+            Ok(data) => {
+                let fresh = CachedCatalog {
+                    key: key.clone(),
+                    fetched_at: unix_now(),
+                    data,
+                };
+                match store.write(&fresh) {
+                    Ok(()) => {
+                        let event = CatalogUpdatedEvent {
+                            key: key.clone(),
+                            fetched_at: fresh.fetched_at,
+                        };
+                        if let Err(e) = app_handle.emit("catalog-updated", event) {
+                            tracing::error!(error = %e, "Failed to emit catalog-updated event");
+                        }
+                    }
+                    Err(e) => tracing::warn!(%key, error = %e, "Failed to cache catalog"),
+                }
+            }
+            Err(e) => tracing::warn!(%key, error = %e, "Background refresh of catalog failed"),
+        }
+        inflight.lock().unwrap().remove(&key);
+    });
+}
+
+pub fn page<T, D>(
+    cached: &CachedCatalog<D>,
+    from_cache: bool,
+    total: usize,
+    models: Vec<T>,
+) -> CatalogPage<T> {
+    CatalogPage {
+        models,
+        total,
+        fetched_at: cached.fetched_at,
+        stale: cached.is_stale(),
+        from_cache,
+        next_cursor: None,
+    }
+}
+
+// --- Local Filtering ---
+
+pub fn filter_ollama_models(
+    models: &[OllamaDbModel],
+    filter: &CatalogFilter,
+) -> Vec<OllamaDbModel> {
+    let search = normalized_search(filter);
+    let mut matched: Vec<OllamaDbModel> = models
+        .iter()
+        .filter(|m| {
+            search.as_deref().is_none_or(|q| {
+                m.model_name.to_lowercase().contains(q)
+                    || m.model_identifier.to_lowercase().contains(q)
+                    || m.description.to_lowercase().contains(q)
+                    || m.labels.iter().any(|l| l.to_lowercase().contains(q))
+            })
+        })
+        .filter(|m| {
+            filter
+                .labels
+                .iter()
+                .all(|wanted| m.labels.iter().any(|l| l.eq_ignore_ascii_case(wanted)))
+        })
+        .filter(|m| {
+            filter.capability.as_deref().is_none_or(|wanted| {
+                m.capability
+                    .as_deref()
+                    .is_some_and(|c| c.to_lowercase().contains(&wanted.to_lowercase()))
+            })
+        })
+        .filter(|m| !filter.official_only || m.model_type == "official")
+        .cloned()
+        .collect();
+
+    matched.sort_by(|a, b| {
+        let ordering = match filter.sort {
+            // OllamaDB has no likes, so fall back to pulls
+            CatalogSort::Popularity | CatalogSort::Likes => a.pulls.cmp(&b.pulls),
+            CatalogSort::Name => a
+                .model_name
+                .to_lowercase()
+                .cmp(&b.model_name.to_lowercase()),
+            CatalogSort::Updated => a.last_updated.cmp(&b.last_updated),
+        };
+        if filter.ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+
+    if let Some(limit) = filter.limit {
+        matched.truncate(limit);
+    }
+    matched
+}
+
+pub fn normalized_search(filter: &CatalogFilter) -> Option<String> {
+    filter
+        .search
+        .as_deref()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+}
+
+// --- Helpers ---
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// Keys can contain arbitrary user search text, so file names use a stable FNV-1a hash of the key
+fn cache_file_stem(key: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in key.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    let prefix: String = key
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    format!("{}-{:016x}", prefix, hash)
+}
+
+pub fn filter_hf_models(models: &[HfModel], filter: &CatalogFilter) -> Vec<HfModel> {
+    let search = normalized_search(filter);
+    let mut matched: Vec<HfModel> = models
+        .iter()
+        .filter(|m| {
+            search.as_deref().is_none_or(|q| {
+                m.model_id.to_lowercase().contains(q)
+                    || m.tags.iter().any(|t| t.to_lowercase().contains(q))
+            })
+        })
+        .filter(|m| {
+            filter
+                .labels
+                .iter()
+                .all(|wanted| m.tags.iter().any(|t| t.eq_ignore_ascii_case(wanted)))
+        })
+        .cloned()
+        .collect();
+
+    matched.sort_by(|a, b| {
+        let ordering = match filter.sort {
+            CatalogSort::Popularity => a.downloads.cmp(&b.downloads),
+            CatalogSort::Likes => a.likes.cmp(&b.likes),
+            CatalogSort::Name => a.model_id.to_lowercase().cmp(&b.model_id.to_lowercase()),
+            CatalogSort::Updated => a.last_modified.cmp(&b.last_modified),
+        };
+        if filter.ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+
+    if let Some(limit) = filter.limit {
+        matched.truncate(limit);
+    }
+    matched
+}
@@ -0,0 +1,635 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::json;
+use std::io::{IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::app_info;
+use crate::conversations::{self, Conversation, ConversationStore, Role};
+use crate::error::ZamaError;
+use crate::http::{self, HttpClient};
+use crate::logging;
+use crate::memory;
+use crate::ollama::{self, ChatEvent, ChatRequest, DeleteModelRequest, Message, ShowModelRequest};
+use crate::paths::{AppDirs, AppPaths};
+use crate::presets::{self, ParameterSet};
+use crate::stats::GenerationStats;
+
+// `zama-cli`: the desktop app's backend from a terminal. Settings and the conversation history
+// come from the same app directories, so scripts and the app work on the same data.
+
+const REPL_HELP: &str = "/new starts a new conversation, /exit quits (or Ctrl-D)";
+
+#[derive(Parser)]
+#[command(
+    name = "zama-cli",
+    version = app_info::VERSION,
+    about = "Chat with local models and manage them from the terminal"
+)]
+struct Cli {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Chat with a model: interactive without a prompt, one reply with a prompt or piped input
+    Chat(ChatArgs),
+    /// Manage the models installed in Ollama
+    #[command(subcommand)]
+    Models(ModelsCommand),
+    /// Search and export the conversation history shared with the desktop app
+    #[command(subcommand)]
+    History(HistoryCommand),
+    /// Check Ollama, settings and storage
+    Diagnose {
+        /// Also write a diagnostics bundle for a bug report
+        #[arg(long)]
+        export: bool,
+    },
+}
+
+#[derive(Args)]
+struct ChatArgs {
+    /// Prompt for a single reply; piped input is appended to it
+    prompt: Vec<String>,
+    /// Model to chat with, defaults to the conversation's model with --conversation
+    #[arg(short, long)]
+    model: Option<String>,
+    /// Continue a saved conversation
+    #[arg(short, long)]
+    conversation: Option<String>,
+    /// System prompt for this chat
+    #[arg(short, long)]
+    system: Option<String>,
+    /// Show the model's reasoning on stderr
+    #[arg(long)]
+    thinking: bool,
+    /// Don't save the chat to the history
+    #[arg(long)]
+    no_save: bool,
+}
+
+#[derive(Subcommand)]
+enum ModelsCommand {
+    /// List installed models
+    List,
+    /// Show a model's details and Modelfile parameters
+    Show { name: String },
+    /// Download a model with `ollama pull`
+    Pull { name: String },
+    /// Delete an installed model
+    Rm { name: String },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommand {
+    /// Find conversations by title, summary or message text
+    Search {
+        query: Vec<String>,
+        #[arg(short, long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Export conversations, all of them unless ids are given
+    Export {
+        ids: Vec<String>,
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Markdown, // The active path of each conversation
+    Json,     // Every branch, as stored
+}
+
+struct Backend {
+    dirs: AppDirs,
+    client: HttpClient,
+    store: ConversationStore,
+    json: bool,
+}
+
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: Failed to start async runtime: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match runtime.block_on(execute(cli)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn execute(cli: Cli) -> Result<(), ZamaError> {
+    let dirs = AppDirs::system()?;
+    if let Err(e) = logging::init_stderr(&dirs) {
+        eprintln!("Logging disabled: {}", e);
+    }
+    let client = HttpClient::new(&http::load_settings(&dirs)).map_err(ZamaError::InvalidInput)?;
+    let store = conversations::init(&dirs)?;
+    let backend = Backend {
+        dirs,
+        client,
+        store,
+        json: cli.json,
+    };
+
+    match cli.command {
+        Command::Chat(args) => chat(&backend, args).await,
+        Command::Models(command) => models(&backend, command).await,
+        Command::History(command) => history(&backend, command),
+        Command::Diagnose { export } => diagnose(&backend, export).await,
+    }
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<(), ZamaError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| ZamaError::Internal(format!("Failed to serialize output: {}", e)))?;
+    println!("{}", json);
+    Ok(())
+}
+
+// --- Chat ---
+
+// One chat as the CLI keeps it: the messages sent so far, and the saved conversation they go to
+// once the first reply is in
+struct Session {
+    model: String,
+    system: Option<String>,
+    history: Vec<Message>,
+    conversation_id: Option<String>,
+    save: bool,
+}
+
+impl Session {
+    fn open(store: &ConversationStore, args: &ChatArgs) -> Result<Self, ZamaError> {
+        let mut session = Self {
+            model: String::new(),
+            system: args.system.clone().filter(|s| !s.trim().is_empty()),
+            history: Vec::new(),
+            conversation_id: None,
+            save: !args.no_save,
+        };
+        if let Some(id) = args.conversation.as_deref() {
+            let conversation = store.load(id)?;
+            session.history = conversation
+                .active_path()
+                .into_iter()
+                .map(|m| Message {
+                    role: m.role.as_str().to_string(),
+                    content: m.content.clone(),
+                    thinking: None,
+                })
+                .collect();
+            session.model = conversation.model.clone();
+            session.conversation_id = Some(conversation.id);
+        }
+        if let Some(model) = args.model.as_deref() {
+            session.model = model.trim().to_string();
+        }
+        if session.model.is_empty() {
+            return Err(ZamaError::InvalidInput(
+                "Choose a model with --model, eg. `zama-cli chat -m llama3.2`".to_string(),
+            ));
+        }
+        ollama::validate_model_name(&session.model)?;
+        session.push_system();
+        Ok(session)
+    }
+
+    // A system prompt given on the command line wins over the presets for this chat
+    fn push_system(&mut self) {
+        if let Some(system) = self.system.clone() {
+            self.history.retain(|m| m.role != "system");
+            self.history.insert(
+                0,
+                Message {
+                    role: "system".to_string(),
+                    content: system,
+                    thinking: None,
+                },
+            );
+        }
+    }
+
+    fn restart(&mut self) {
+        self.history.clear();
+        self.conversation_id = None;
+        self.push_system();
+    }
+
+    async fn send(
+        &mut self,
+        backend: &Backend,
+        prompt: &str,
+        show_thinking: bool,
+    ) -> Result<(), ZamaError> {
+        self.history.push(Message {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+            thinking: None,
+        });
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: self.history.clone(),
+            stream: true,
+            think: None,
+            options: None,
+            conversation_id: self.conversation_id.clone(),
+        };
+
+        let mut reply = String::new();
+        let mut thinking = String::new();
+        let mut stats = None;
+        let mut stdout = std::io::stdout();
+        let outcome = ollama::stream_chat(
+            &backend.dirs,
+            &backend.store,
+            &backend.client,
+            &request,
+            |event| {
+                match event {
+                    ChatEvent::Context(report) if !report.dropped.is_empty() => eprintln!(
+                        "[{} earlier messages left out to fit the context window]",
+                        report.dropped.len()
+                    ),
+                    ChatEvent::Context(_) => {}
+                    ChatEvent::Thinking(content) => {
+                        if show_thinking {
+                            eprint!("{}", content);
+                        }
+                        thinking.push_str(&content);
+                    }
+                    ChatEvent::Chunk(chunk) => {
+                        if let Some(message) = chunk.message {
+                            if show_thinking && reply.is_empty() && !thinking.is_empty() {
+                                eprintln!();
+                            }
+                            write!(stdout, "{}", message.content)?;
+                            stdout.flush()?;
+                            reply.push_str(&message.content);
+                        }
+                        if chunk.done {
+                            stats = chunk.stats;
+                        }
+                    }
+                }
+                Ok(())
+            },
+        )
+        .await;
+        if let Err(e) = outcome {
+            self.history.pop();
+            return Err(e);
+        }
+        println!();
+
+        self.history.push(Message {
+            role: "assistant".to_string(),
+            content: reply.clone(),
+            thinking: None,
+        });
+        if self.save {
+            self.record(&backend.store, prompt, reply, thinking, stats)?;
+        }
+        Ok(())
+    }
+
+    // Saves the exchange the same way the chat page does, so the app lists it like its own
+    fn record(
+        &mut self,
+        store: &ConversationStore,
+        prompt: &str,
+        reply: String,
+        thinking: String,
+        stats: Option<GenerationStats>,
+    ) -> Result<(), ZamaError> {
+        let id = match self.conversation_id.clone() {
+            Some(id) => id,
+            None => {
+                let mut conversation = Conversation::new(self.model.clone(), None);
+                conversation.parameters = self.system.clone().map(|system| ParameterSet {
+                    system: Some(system),
+                    ..Default::default()
+                });
+                store.create(&conversation)?;
+                conversation.id
+            }
+        };
+        store.update(&id, |conversation| {
+            let parent_id = conversation.active_leaf.clone();
+            let user_id = conversation.push(parent_id, Role::User, prompt.to_string(), None);
+            let reply_id = conversation.push(
+                Some(user_id),
+                Role::Assistant,
+                reply,
+                Some(self.model.clone()),
+            );
+            let message = conversation.message_mut(&reply_id)?;
+            message.stats = stats;
+            message.thinking = Some(thinking).filter(|t| !t.trim().is_empty());
+            Ok(())
+        })?;
+        self.conversation_id = Some(id);
+        Ok(())
+    }
+}
+
+async fn chat(backend: &Backend, args: ChatArgs) -> Result<(), ZamaError> {
+    let mut prompt = args.prompt.join(" ");
+    let interactive = std::io::stdin().is_terminal();
+    if !interactive {
+        let mut piped = String::new();
+        std::io::stdin().lock().read_to_string(&mut piped)?;
+        if !piped.trim().is_empty() {
+            prompt = if prompt.trim().is_empty() {
+                piped
+            } else {
+                format!("{}\n\n{}", prompt.trim(), piped)
+            };
+        }
+    }
+
+    let mut session = Session::open(&backend.store, &args)?;
+    if !prompt.trim().is_empty() {
+        return session.send(backend, prompt.trim(), args.thinking).await;
+    }
+    if !interactive {
+        return Err(ZamaError::InvalidInput(
+            "Nothing to send: pass a prompt or pipe one in".to_string(),
+        ));
+    }
+
+    eprintln!("Chatting with {}. {}", session.model, REPL_HELP);
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    loop {
+        print!(">>> ");
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next_line().await? else {
+            println!();
+            break;
+        };
+        match line.trim() {
+            "" => {}
+            "/exit" | "/quit" | "/bye" => break,
+            "/new" => {
+                session.restart();
+                eprintln!("Started a new conversation");
+            }
+            "/help" | "/?" => eprintln!("{}", REPL_HELP),
+            text => {
+                // A failed reply shouldn't end the session
+                if let Err(e) = session.send(backend, text, args.thinking).await {
+                    eprintln!("error: {}", e);
+                }
+            }
+        }
+    }
+    if let Some(id) = session.conversation_id.as_deref() {
+        eprintln!("Saved as {}", id);
+    }
+    Ok(())
+}
+
+// --- Models ---
+
+async fn models(backend: &Backend, command: ModelsCommand) -> Result<(), ZamaError> {
+    let client = &backend.client;
+    match command {
+        ModelsCommand::List => {
+            let list = ollama::list_models(client).await?;
+            if backend.json {
+                return print_json(&list);
+            }
+            let width = list
+                .models
+                .iter()
+                .map(|m| m.name.len())
+                .max()
+                .unwrap_or_default()
+                .max("NAME".len());
+            println!(
+                "{:<width$}  {:>10}  {:>8}  {:<8}  MODIFIED",
+                "NAME", "SIZE", "PARAMS", "QUANT"
+            );
+            for model in &list.models {
+                println!(
+                    "{:<width$}  {:>10}  {:>8}  {:<8}  {}",
+                    model.name,
+                    memory::format_bytes(model.size),
+                    model.details.parameter_size,
+                    model.details.quantization_level,
+                    model.modified_at
+                );
+            }
+            Ok(())
+        }
+        ModelsCommand::Show { name } => {
+            let show = ollama::fetch_model_info(client, ShowModelRequest { name }).await?;
+            if backend.json {
+                return print_json(&show);
+            }
+            let context_length = show
+                .model_info
+                .iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .and_then(|(_, value)| value.as_u64());
+            println!("Model         {}", show.model);
+            println!("Family        {}", show.details.family);
+            println!("Parameters    {}", show.details.parameter_size);
+            println!("Quantization  {}", show.details.quantization_level);
+            println!("Format        {}", show.details.format);
+            if let Some(context_length) = context_length {
+                println!("Context       {}", context_length);
+            }
+            if !show.capabilities.is_empty() {
+                println!("Capabilities  {}", show.capabilities.join(", "));
+            }
+            let parameters = presets::modelfile_parameters(&show);
+            if !parameters.is_empty() {
+                println!("\nModelfile parameters");
+                for (key, value) in parameters {
+                    println!("  {:<20}  {}", key, value);
+                }
+            }
+            Ok(())
+        }
+        ModelsCommand::Pull { name } => {
+            ollama::pull_model(&name, |line| eprintln!("{}", line)).await?;
+            println!("Pulled {}", name);
+            Ok(())
+        }
+        ModelsCommand::Rm { name } => {
+            ollama::delete_model(client, &DeleteModelRequest { name: name.clone() }).await?;
+            println!("Deleted {}", name);
+            Ok(())
+        }
+    }
+}
+
+// --- History ---
+
+fn history(backend: &Backend, command: HistoryCommand) -> Result<(), ZamaError> {
+    let store = &backend.store;
+    match command {
+        HistoryCommand::Search { query, limit } => {
+            let mut hits = store.search(&query.join(" "));
+            hits.truncate(limit);
+            if backend.json {
+                return print_json(&hits);
+            }
+            for hit in &hits {
+                println!("{}  {} ({})", hit.conversation_id, hit.title, hit.model);
+                if !hit.snippet.is_empty() {
+                    let role = hit.role.map(|r| r.as_str()).unwrap_or("summary");
+                    println!("    {}: {}", role, hit.snippet);
+                }
+            }
+            if hits.is_empty() {
+                eprintln!("No matching conversations");
+            }
+            Ok(())
+        }
+        HistoryCommand::Export {
+            ids,
+            format,
+            output,
+        } => {
+            let conversations = if ids.is_empty() {
+                store.list()
+            } else {
+                ids.iter()
+                    .map(|id| store.load(id))
+                    .collect::<Result<Vec<_>, _>>()?
+            };
+            let content = match format {
+                ExportFormat::Json => {
+                    serde_json::to_string_pretty(&conversations).map_err(|e| {
+                        ZamaError::Internal(format!("Failed to serialize conversations: {}", e))
+                    })?
+                }
+                ExportFormat::Markdown => conversations
+                    .iter()
+                    .map(Conversation::to_markdown)
+                    .collect::<Vec<_>>()
+                    .join("\n---\n\n"),
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, content)?;
+                    eprintln!(
+                        "Exported {} conversations to {}",
+                        conversations.len(),
+                        path.display()
+                    );
+                }
+                None => println!("{}", content),
+            }
+            Ok(())
+        }
+    }
+}
+
+// --- Diagnose ---
+
+async fn diagnose(backend: &Backend, export: bool) -> Result<(), ZamaError> {
+    let info = app_info::for_package("zama");
+    let system = tokio::task::spawn_blocking(memory::system_memory).await?;
+    let network = http::load_settings(&backend.dirs);
+    let ollama_version = ollama::version(&backend.client).await;
+    let model_count = match &ollama_version {
+        Ok(_) => ollama::list_models(&backend.client)
+            .await
+            .map(|list| list.models.len())
+            .ok(),
+        Err(_) => None,
+    };
+    let conversation_count = backend.store.list().len();
+    let dirs = [
+        ("config", backend.dirs.config_dir()?),
+        ("data", backend.dirs.data_dir()?),
+        ("logs", backend.dirs.log_dir()?),
+    ];
+    let bundle = if export {
+        Some(logging::export_bundle(&backend.dirs, info.clone()).await?)
+    } else {
+        None
+    };
+
+    if backend.json {
+        return print_json(&json!({
+            "app": info,
+            "memory": {
+                "total_bytes": system.total_bytes,
+                "available_bytes": system.available_bytes,
+            },
+            "dirs": dirs.iter().map(|(name, dir)| (name.to_string(), json!(dir))).collect::<serde_json::Map<_, _>>(),
+            "network": network,
+            "ollama": {
+                "url": backend.client.endpoints().ollama,
+                "version": ollama_version.as_ref().ok(),
+                "error": ollama_version.as_ref().err().map(|e| e.to_string()),
+                "models": model_count,
+            },
+            "conversations": conversation_count,
+            "bundle": bundle,
+        }));
+    }
+
+    println!(
+        "Zama {} ({}, built {}, {} {})",
+        info.version, info.git_commit, info.build_date, info.target, info.profile
+    );
+    println!("System        {} {}", info.os, info.arch);
+    println!(
+        "Memory        {} available of {}",
+        memory::format_bytes(system.available_bytes),
+        memory::format_bytes(system.total_bytes)
+    );
+    for (name, dir) in &dirs {
+        println!("{:<13} {}", format!("Dir ({})", name), dir.display());
+    }
+    println!(
+        "Network       proxy {}, custom CA {}",
+        network.proxy_url.as_deref().unwrap_or("none"),
+        if network.ca_cert_path.is_some() {
+            "yes"
+        } else {
+            "no"
+        }
+    );
+    match &ollama_version {
+        Ok(version) => println!(
+            "Ollama        {} at {}, {} models",
+            version,
+            backend.client.endpoints().ollama,
+            model_count.map_or("?".to_string(), |n| n.to_string())
+        ),
+        Err(e) => println!(
+            "Ollama        not reachable at {}: {}",
+            backend.client.endpoints().ollama,
+            e
+        ),
+    }
+    println!("History       {} conversations", conversation_count);
+    if let Some(path) = bundle {
+        println!("Bundle        {}", path.display());
+    }
+    Ok(())
+}
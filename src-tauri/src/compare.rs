@@ -0,0 +1,335 @@
+use futures::future::join_all;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::credentials::{self, Provider};
+use crate::error::ZamaError;
+use crate::http::{self, HttpClient, HttpState};
+use crate::ollama::{self, ChatResponse, Message, OLLAMA_SERVICE};
+use crate::server::route_model;
+use crate::GEMINI_SERVICE;
+
+const MAX_MODELS: usize = 8;
+
+// Every event carries the run id and the model, so one listener can route chunks to columns
+const CHUNK_EVENT: &str = "compare-chunk";
+const RESULT_EVENT: &str = "compare-result";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CompareChunk {
+    pub run_id: String,
+    pub model: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CompareResult {
+    pub run_id: String,
+    pub model: String, // As requested, eg. `gemini/gemini-2.5-flash`
+    pub provider: Provider,
+    pub content: String,
+    pub error: Option<ZamaError>, // One model failing doesn't fail the run
+    pub first_token_ms: Option<u64>, // Latency until the first streamed token
+    pub total_ms: u64,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub tokens_per_second: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct Usage {
+    prompt_tokens: Option<u64>,
+    completion_tokens: Option<u64>,
+    generation_time: Option<Duration>, // Reported by the provider, excludes prompt processing
+}
+
+// Collects one model's reply and emits it chunk by chunk
+struct Run<R: Runtime> {
+    app_handle: AppHandle<R>,
+    run_id: String,
+    model: String,
+    started: Instant,
+    first_token: Option<Duration>,
+    content: String,
+}
+
+impl<R: Runtime> Run<R> {
+    fn push(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.first_token
+            .get_or_insert_with(|| self.started.elapsed());
+        self.content.push_str(text);
+        let chunk = CompareChunk {
+            run_id: self.run_id.clone(),
+            model: self.model.clone(),
+            content: text.to_string(),
+        };
+        if let Err(e) = self.app_handle.emit(CHUNK_EVENT, chunk) {
+            tracing::warn!(error = %e, "Failed to emit compare chunk");
+        }
+    }
+}
+
+async fn run_ollama<R: Runtime>(
+    client: &HttpClient,
+    run: &mut Run<R>,
+    model: &str,
+    messages: &[Message],
+    options: &Map<String, Value>,
+) -> Result<Usage, ZamaError> {
+    let body = json!({
+        "model": model,
+        "messages": messages,
+        "stream": true,
+        "options": options,
+    });
+    let res = client
+        .post(&format!("{}/api/chat", client.endpoints().ollama))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, Some(model)).await);
+    }
+
+    let mut usage = Usage::default();
+    http::for_each_line(OLLAMA_SERVICE, res, |line| {
+        match serde_json::from_str::<ChatResponse>(line) {
+            Ok(chunk) => {
+                if let Some(message) = &chunk.message {
+                    run.push(&message.content);
+                }
+                if chunk.done {
+                    usage = Usage {
+                        prompt_tokens: chunk.prompt_eval_count,
+                        completion_tokens: chunk.eval_count,
+                        generation_time: chunk.eval_duration.map(Duration::from_nanos),
+                    };
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to parse chat response chunk"),
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(usage)
+}
+
+// Maps Ollama-style options onto Gemini's generation config
+fn gemini_generation_config(options: &Map<String, Value>) -> Map<String, Value> {
+    [
+        ("temperature", "temperature"),
+        ("top_p", "topP"),
+        ("top_k", "topK"),
+        ("num_predict", "maxOutputTokens"),
+        ("stop", "stopSequences"),
+        ("seed", "seed"),
+    ]
+    .into_iter()
+    .filter_map(|(from, to)| Some((to.to_string(), options.get(from)?.clone())))
+    .collect()
+}
+
+async fn run_gemini<R: Runtime>(
+    client: &HttpClient,
+    run: &mut Run<R>,
+    model: &str,
+    api_key: &str,
+    messages: &[Message],
+    options: &Map<String, Value>,
+) -> Result<Usage, ZamaError> {
+    let system: Vec<Value> = messages
+        .iter()
+        .filter(|m| m.role == "system")
+        .map(|m| json!({ "text": m.content }))
+        .collect();
+    let contents: Vec<Value> = messages
+        .iter()
+        .filter(|m| m.role != "system")
+        .map(|m| {
+            let role = if m.role == "assistant" {
+                "model"
+            } else {
+                "user"
+            };
+            json!({ "role": role, "parts": [{ "text": m.content }] })
+        })
+        .collect();
+    let mut body = json!({
+        "contents": contents,
+        "generationConfig": gemini_generation_config(options),
+    });
+    if !system.is_empty() {
+        body["systemInstruction"] = json!({ "parts": system });
+    }
+
+    let res = client
+        .post(&format!(
+            "{}/models/{}:streamGenerateContent?alt=sse",
+            client.endpoints().gemini,
+            model
+        ))
+        .header("x-goog-api-key", api_key)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(GEMINI_SERVICE, e))?;
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(GEMINI_SERVICE, res, Some(model))
+            .await
+            .gemini_auth());
+    }
+
+    let mut usage = Usage::default();
+    http::for_each_line(GEMINI_SERVICE, res, |line| {
+        let Some(data) = line.strip_prefix("data:") else {
+            return Ok(());
+        };
+        let Ok(event) = serde_json::from_str::<Value>(data.trim()) else {
+            tracing::warn!("Failed to parse Gemini stream event");
+            return Ok(());
+        };
+        let parts = event["candidates"][0]["content"]["parts"].as_array();
+        for part in parts.into_iter().flatten() {
+            if let Some(text) = part["text"].as_str() {
+                run.push(text);
+            }
+        }
+        // Sent with every event; the last one has the final counts
+        if let Some(metadata) = event.get("usageMetadata") {
+            usage.prompt_tokens = metadata["promptTokenCount"].as_u64();
+            usage.completion_tokens = metadata["candidatesTokenCount"].as_u64();
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(usage)
+}
+
+async fn run_model<R: Runtime>(
+    app_handle: AppHandle<R>,
+    client: HttpClient,
+    run_id: String,
+    model: String,
+    messages: &[Message],
+    options: &Map<String, Value>,
+) -> CompareResult {
+    let (provider, upstream_model) = route_model(&model);
+    let mut run = Run {
+        app_handle: app_handle.clone(),
+        run_id: run_id.clone(),
+        model: model.clone(),
+        started: Instant::now(),
+        first_token: None,
+        content: String::new(),
+    };
+
+    let outcome = match provider {
+        Provider::Ollama => run_ollama(&client, &mut run, upstream_model, messages, options).await,
+        Provider::Gemini => match credentials::load(&app_handle).api_key(Provider::Gemini) {
+            Some(key) => {
+                let key = key.to_string();
+                run_gemini(&client, &mut run, upstream_model, &key, messages, options).await
+            }
+            None => Err(ZamaError::ProviderAuth {
+                provider: GEMINI_SERVICE.to_string(),
+                message: "No Gemini API key configured".to_string(),
+            }),
+        },
+    };
+
+    let total = run.started.elapsed();
+    let (usage, error) = match outcome {
+        Ok(usage) => (usage, None),
+        Err(e) => {
+            tracing::warn!(%model, error = %e, "Compare run failed");
+            (Usage::default(), Some(e))
+        }
+    };
+    // Providers that don't report generation time are measured from the first token
+    let generation_time = usage
+        .generation_time
+        .or_else(|| run.first_token.map(|first| total.saturating_sub(first)));
+    let tokens_per_second = usage
+        .completion_tokens
+        .zip(generation_time)
+        .filter(|(_, time)| !time.is_zero())
+        .map(|(tokens, time)| tokens as f64 / time.as_secs_f64());
+
+    let result = CompareResult {
+        run_id,
+        model,
+        provider,
+        content: run.content,
+        error,
+        first_token_ms: run.first_token.map(|d| d.as_millis() as u64),
+        total_ms: total.as_millis() as u64,
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        tokens_per_second,
+    };
+    if let Err(e) = app_handle.emit(RESULT_EVENT, &result) {
+        tracing::warn!(error = %e, "Failed to emit compare result");
+    }
+    result
+}
+
+// Sends the same conversation to every model at once. Chunks arrive on `compare-chunk` and each
+// model's stats on `compare-result` as it finishes; the returned results keep the request order.
+#[tauri::command]
+pub async fn compare_chat<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    models: Vec<String>,
+    messages: Vec<Message>,
+    options: Option<Map<String, Value>>,
+    run_id: Option<String>, // Lets the caller subscribe before the first chunk
+) -> Result<Vec<CompareResult>, ZamaError> {
+    if models.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Select at least one model to compare".to_string(),
+        ));
+    }
+    if models.len() > MAX_MODELS {
+        return Err(ZamaError::InvalidInput(format!(
+            "At most {} models can be compared at once",
+            MAX_MODELS
+        )));
+    }
+    for (i, model) in models.iter().enumerate() {
+        ollama::validate_model_name(model)?;
+        if models[..i].contains(model) {
+            return Err(ZamaError::InvalidInput(format!(
+                "{} is listed more than once",
+                model
+            )));
+        }
+    }
+    if messages.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Chat request must include at least one message".to_string(),
+        ));
+    }
+
+    let run_id = run_id.unwrap_or_else(|| crate::conversations::new_id("compare"));
+    let options = options.unwrap_or_default();
+    let client = http.client();
+    tracing::info!(%run_id, models = models.len(), "Starting compare run");
+
+    let runs = models.into_iter().map(|model| {
+        run_model(
+            app_handle.clone(),
+            client.clone(),
+            run_id.clone(),
+            model,
+            &messages,
+            &options,
+        )
+    });
+    Ok(join_all(runs).await)
+}
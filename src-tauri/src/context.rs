@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Runtime};
+
+use crate::error::ZamaError;
+use crate::gguf;
+use crate::http::{HttpClient, HttpState};
+use crate::ollama::{self, Message, ShowModelResponse};
+use crate::paths::{self, AppPaths};
+
+const SETTINGS_FILE: &str = "context.json";
+
+// Rough tokenizer-independent estimate: ~4 characters per token for English text and code, plus
+// the role markers and separators every chat template adds per message
+const CHARS_PER_TOKEN: usize = 4;
+const MESSAGE_OVERHEAD_TOKENS: u64 = 4;
+const PREVIEW_CHARS: usize = 80;
+const MAX_CACHED_SUMMARIES: usize = 64;
+
+const SUMMARY_PROMPT: &str = "Summarize the conversation below in a few sentences. Keep names, \
+facts, decisions and open questions; leave out pleasantries. Reply with the summary only.";
+
+// Summaries of the same dropped turns are reused, so a long chat isn't re-summarized every turn.
+// Least recently used first; only the newest few are kept.
+static SUMMARIES: LazyLock<Mutex<VecDeque<(u64, String)>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+fn cached_summary(key: u64) -> Option<String> {
+    let mut summaries = SUMMARIES.lock().unwrap();
+    let at = summaries.iter().position(|(k, _)| *k == key)?;
+    let entry = summaries.remove(at)?;
+    let summary = entry.1.clone();
+    summaries.push_back(entry);
+    Some(summary)
+}
+
+fn cache_summary(key: u64, summary: String) {
+    let mut summaries = SUMMARIES.lock().unwrap();
+    summaries.retain(|(k, _)| *k != key);
+    summaries.push_back((key, summary));
+    while summaries.len() > MAX_CACHED_SUMMARIES {
+        summaries.pop_front();
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextStrategy {
+    SlidingWindow, // Drop the oldest messages, system prompt included
+    #[default]
+    PinSystem, // Drop the oldest turns but always keep system messages
+    Summarize, // Like `PinSystem`, then replace the dropped turns with a summary by the same model
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ContextSettings {
+    pub enabled: bool,
+    pub strategy: ContextStrategy,
+    pub reserve_tokens: u64, // Room left for the reply
+}
+
+impl Default for ContextSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            strategy: ContextStrategy::default(),
+            reserve_tokens: 512,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DroppedMessage {
+    pub index: usize, // Position in the messages the caller sent
+    pub role: String,
+    pub preview: String,
+    pub tokens: u64,
+}
+
+// What the context manager did to a request; emitted with every chat so the UI can show usage
+#[derive(Debug, Serialize, Clone)]
+pub struct ContextReport {
+    pub model: String,
+    pub strategy: ContextStrategy,
+    pub context_length: u64, // Window the request will run with (`num_ctx`)
+    pub model_context_length: Option<u64>, // What the model was trained for
+    pub budget: u64,         // `context_length` minus the reply reserve
+    pub tokens_before: u64,
+    pub tokens_after: u64,
+    pub dropped: Vec<DroppedMessage>,
+    pub summary: Option<String>,
+    pub overflow: bool, // Even the latest message alone doesn't fit
+}
+
+pub fn load_settings(paths: &impl AppPaths) -> ContextSettings {
+    paths::load_settings(paths, SETTINGS_FILE)
+}
+
+fn save_settings(paths: &impl AppPaths, settings: &ContextSettings) -> Result<(), ZamaError> {
+    paths::save_settings(paths, SETTINGS_FILE, settings)
+}
+
+pub fn estimate_tokens(message: &Message) -> u64 {
+    message.content.chars().count().div_ceil(CHARS_PER_TOKEN) as u64 + MESSAGE_OVERHEAD_TOKENS
+}
+
+fn total_tokens(messages: &[Message]) -> u64 {
+    messages.iter().map(estimate_tokens).sum()
+}
+
+fn preview(content: &str) -> String {
+    let cleaned = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.chars().count() > PREVIEW_CHARS {
+        format!(
+            "{}...",
+            cleaned.chars().take(PREVIEW_CHARS).collect::<String>()
+        )
+    } else {
+        cleaned
+    }
+}
+
+// `num_ctx` from a request's Ollama options
+pub fn num_ctx(options: Option<&serde_json::Map<String, Value>>) -> Option<u64> {
+    options?.get("num_ctx")?.as_u64()
+}
+
+// `num_ctx` set in the Modelfile, eg. `PARAMETER num_ctx 8192`
+fn modelfile_num_ctx(show: &ShowModelResponse) -> Option<u64> {
+    show.modelfile.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        (parts.next()? == "PARAMETER" && parts.next()? == "num_ctx")
+            .then(|| parts.next()?.parse().ok())
+            .flatten()
+    })
+}
+
+// The trained context length from `/api/show`, falling back to the GGUF header
+async fn model_context_length(
+    client: &HttpClient,
+    model: &str,
+    show: &ShowModelResponse,
+) -> Option<u64> {
+    let from_show = show
+        .model_info
+        .iter()
+        .find(|(key, _)| key.ends_with(".context_length"))
+        .and_then(|(_, value)| value.as_u64());
+    if from_show.is_some() {
+        return from_show;
+    }
+    let blob = ollama::model_blob(client, model).await.ok()?;
+    tokio::task::spawn_blocking(move || gguf::inspect(&blob))
+        .await
+        .ok()?
+        .ok()?
+        .context_length
+}
+
+// The window Ollama will actually use: the request's `num_ctx`, then the Modelfile's, then
+// Ollama's default, never more than the model was trained for
+async fn context_length(
+    client: &HttpClient,
+    model: &str,
+    show: Option<&ShowModelResponse>,
+    num_ctx: Option<u64>,
+) -> (u64, Option<u64>) {
+    let (modelfile, trained) = match show {
+        Some(show) => (
+            modelfile_num_ctx(show),
+            model_context_length(client, model, show).await,
+        ),
+        None => (None, None),
+    };
+    let window = num_ctx
+        .or(modelfile)
+        .unwrap_or(crate::memory::DEFAULT_NUM_CTX);
+    (trained.map_or(window, |t| window.min(t)), trained)
+}
+
+async fn summarize(
+    client: &HttpClient,
+    model: &str,
+    messages: &[Message],
+) -> Result<String, ZamaError> {
+    let mut hasher = DefaultHasher::new();
+    model.hash(&mut hasher);
+    for message in messages {
+        message.role.hash(&mut hasher);
+        message.content.hash(&mut hasher);
+    }
+    let key = hasher.finish();
+    if let Some(summary) = cached_summary(key) {
+        return Ok(summary);
+    }
+
+    let transcript = messages
+        .iter()
+        .map(|m| format!("{}: {}", m.role, m.content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let prompt = [
+        Message {
+            role: "system".to_string(),
+            content: SUMMARY_PROMPT.to_string(),
+            thinking: None,
+        },
+        Message {
+            role: "user".to_string(),
+            content: transcript,
+            thinking: None,
+        },
+    ];
+    let summary = ollama::complete(client, model, &prompt, None).await?;
+    if summary.is_empty() {
+        return Err(ZamaError::Parse("Empty summary".to_string()));
+    }
+
+    cache_summary(key, summary.clone());
+    Ok(summary)
+}
+
+// Trims `messages` to fit the model's window and reports what was left out. The latest message
+// is always kept. `show` is the model's `/api/show`, fetched once per turn by the caller.
+pub async fn fit(
+    client: &HttpClient,
+    settings: &ContextSettings,
+    model: &str,
+    show: Option<&ShowModelResponse>,
+    messages: &mut Vec<Message>,
+    num_ctx: Option<u64>,
+) -> ContextReport {
+    let (context_length, model_context_length) = context_length(client, model, show, num_ctx).await;
+    let budget = context_length.saturating_sub(settings.reserve_tokens.min(context_length / 2));
+    let tokens_before = total_tokens(messages);
+    let mut report = ContextReport {
+        model: model.to_string(),
+        strategy: settings.strategy,
+        context_length,
+        model_context_length,
+        budget,
+        tokens_before,
+        tokens_after: tokens_before,
+        dropped: Vec::new(),
+        summary: None,
+        overflow: false,
+    };
+    if !settings.enabled || tokens_before <= budget || messages.len() < 2 {
+        report.overflow = tokens_before > budget;
+        return report;
+    }
+
+    let pin_system = settings.strategy != ContextStrategy::SlidingWindow;
+    let last = messages.len() - 1;
+    let mut keep = vec![true; messages.len()];
+    let mut tokens = tokens_before;
+    for i in 0..last {
+        if tokens <= budget {
+            break;
+        }
+        if pin_system && messages[i].role == "system" {
+            continue;
+        }
+        keep[i] = false;
+        tokens -= estimate_tokens(&messages[i]);
+    }
+
+    let dropped: Vec<Message> = messages
+        .iter()
+        .zip(&keep)
+        .filter(|(_, kept)| !**kept)
+        .map(|(m, _)| m.clone())
+        .collect();
+    report.dropped = messages
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !keep[*i])
+        .map(|(index, m)| DroppedMessage {
+            index,
+            role: m.role.clone(),
+            preview: preview(&m.content),
+            tokens: estimate_tokens(m),
+        })
+        .collect();
+    let mut kept: Vec<Message> = messages
+        .drain(..)
+        .zip(keep)
+        .filter_map(|(m, kept)| kept.then_some(m))
+        .collect();
+
+    if settings.strategy == ContextStrategy::Summarize && !dropped.is_empty() {
+        match summarize(client, model, &dropped).await {
+            Ok(summary) => {
+                let note = Message {
+                    role: "system".to_string(),
+                    content: format!("Summary of the earlier conversation: {}", summary),
+                    thinking: None,
+                };
+                // Only worth it if the summary fits next to what's left
+                if tokens + estimate_tokens(&note) <= budget {
+                    let at = kept.iter().take_while(|m| m.role == "system").count();
+                    tokens += estimate_tokens(&note);
+                    kept.insert(at, note);
+                    report.summary = Some(summary);
+                }
+            }
+            Err(e) => tracing::warn!(model, error = %e, "Failed to summarize dropped turns"),
+        }
+    }
+
+    *messages = kept;
+    report.tokens_after = tokens;
+    report.overflow = tokens > budget;
+    tracing::info!(
+        model,
+        dropped = report.dropped.len(),
+        summarized = report.summary.is_some(),
+        tokens_before,
+        tokens_after = tokens,
+        context_length,
+        "Trimmed conversation to fit the context window"
+    );
+    report
+}
+
+// Previews what the next chat request would send, without sending it
+#[tauri::command]
+pub async fn plan_context<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    model: String,
+    mut messages: Vec<Message>,
+    options: Option<serde_json::Map<String, Value>>,
+) -> Result<ContextReport, ZamaError> {
+    ollama::validate_model_name(&model)?;
+    let settings = load_settings(&app_handle);
+    let client = http.client();
+    let show = ollama::show_model(&client, &model).await;
+    Ok(fit(
+        &client,
+        &settings,
+        &model,
+        show.as_ref(),
+        &mut messages,
+        num_ctx(options.as_ref()),
+    )
+    .await)
+}
+
+#[tauri::command]
+pub fn get_context_settings<R: Runtime>(app_handle: AppHandle<R>) -> ContextSettings {
+    load_settings(&app_handle)
+}
+
+#[tauri::command]
+pub fn set_context_settings<R: Runtime>(
+    app_handle: AppHandle<R>,
+    settings: ContextSettings,
+) -> Result<ContextSettings, ZamaError> {
+    save_settings(&app_handle, &settings)?;
+    Ok(settings)
+}
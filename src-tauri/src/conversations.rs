@@ -0,0 +1,831 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Runtime};
+
+use crate::error::ZamaError;
+use crate::paths::{self, AppPaths};
+use crate::presets::ParameterSet;
+use crate::stats::GenerationStats;
+use crate::titles;
+
+const CONVERSATIONS_DIR: &str = "conversations";
+const DEFAULT_TITLE: &str = "New Conversation";
+const PREVIEW_CHARS: usize = 80;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+}
+
+impl Role {
+    // As Ollama names it in chat messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::System => "system",
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        }
+    }
+}
+
+// One message in the conversation tree. Editing or regenerating adds a sibling under the same
+// parent instead of overwriting, so every earlier answer stays reachable.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageNode {
+    pub id: String,
+    pub parent_id: Option<String>, // None for the first message
+    pub role: Role,
+    pub content: String,
+    pub timestamp: u64, // Unix millis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>, // Model that produced an assistant message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<GenerationStats>, // Generation metrics of an assistant message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>, // Reasoning, kept apart from the answer in `content`
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkOrigin {
+    pub conversation_id: String,
+    pub message_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Conversation {
+    pub id: String,
+    pub title: String,
+    pub model: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub messages: Vec<MessageNode>,  // Every branch, in creation order
+    pub active_leaf: Option<String>, // Last message of the path shown in the chat
+    #[serde(default)]
+    pub forked_from: Option<ForkOrigin>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>, // One paragraph by the titling model
+    #[serde(default)]
+    pub title_edited: bool, // Set by the user, so generated titles don't replace it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<ParameterSet>, // Overrides of the model and global presets
+}
+
+impl Conversation {
+    pub(crate) fn new(model: String, title: Option<String>) -> Self {
+        let now = unix_millis();
+        Self {
+            id: new_id("conv"),
+            title_edited: title.is_some(),
+            title: title.unwrap_or_else(|| DEFAULT_TITLE.to_string()),
+            model,
+            created_at: now,
+            updated_at: now,
+            messages: Vec::new(),
+            active_leaf: None,
+            forked_from: None,
+            summary: None,
+            parameters: None,
+        }
+    }
+
+    fn message(&self, id: &str) -> Result<&MessageNode, ZamaError> {
+        self.messages
+            .iter()
+            .find(|m| m.id == id)
+            .ok_or_else(|| ZamaError::InvalidInput(format!("Message {} not found", id)))
+    }
+
+    pub(crate) fn message_mut(&mut self, id: &str) -> Result<&mut MessageNode, ZamaError> {
+        self.messages
+            .iter_mut()
+            .find(|m| m.id == id)
+            .ok_or_else(|| ZamaError::InvalidInput(format!("Message {} not found", id)))
+    }
+
+    fn children<'a>(&'a self, parent_id: Option<&'a str>) -> impl Iterator<Item = &'a MessageNode> {
+        self.messages
+            .iter()
+            .filter(move |m| m.parent_id.as_deref() == parent_id)
+    }
+
+    // Messages from the root to `leaf_id`. A path never has more steps than there are messages,
+    // so a parent cycle in a corrupted file stops there instead of looping.
+    pub fn path_to(&self, leaf_id: &str) -> Vec<&MessageNode> {
+        let mut path = Vec::new();
+        let mut next = Some(leaf_id);
+        while let Some(id) = next {
+            let Some(message) = self.messages.iter().find(|m| m.id == id) else {
+                break;
+            };
+            if path.len() == self.messages.len() {
+                tracing::warn!(conversation = %self.id, "Message parents form a cycle");
+                break;
+            }
+            path.push(message);
+            next = message.parent_id.as_deref();
+        }
+        path.reverse();
+        path
+    }
+
+    pub fn active_path(&self) -> Vec<&MessageNode> {
+        self.active_leaf
+            .as_deref()
+            .map(|leaf| self.path_to(leaf))
+            .unwrap_or_default()
+    }
+
+    // Follows the newest child down from `id`, so switching to a branch shows its latest reply
+    fn newest_leaf(&self, id: &str) -> String {
+        let mut leaf = id.to_string();
+        for _ in 0..self.messages.len() {
+            let Some(child) = self.children(Some(&leaf)).last() else {
+                break;
+            };
+            leaf = child.id.clone();
+        }
+        leaf
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        parent_id: Option<String>,
+        role: Role,
+        content: String,
+        model: Option<String>,
+    ) -> String {
+        let id = new_id("msg");
+        if self.title == DEFAULT_TITLE && role == Role::User && parent_id.is_none() {
+            self.title = title_from_message(&content);
+        }
+        self.messages.push(MessageNode {
+            id: id.clone(),
+            parent_id,
+            role,
+            content,
+            timestamp: unix_millis(),
+            model,
+            stats: None,
+            thinking: None,
+        });
+        self.active_leaf = Some(id.clone());
+        id
+    }
+
+    pub fn view(&self) -> ConversationView {
+        let messages = self
+            .active_path()
+            .into_iter()
+            .map(|message| {
+                let siblings: Vec<&MessageNode> =
+                    self.children(message.parent_id.as_deref()).collect();
+                PathMessage {
+                    message: message.clone(),
+                    sibling_index: siblings
+                        .iter()
+                        .position(|s| s.id == message.id)
+                        .unwrap_or_default(),
+                    sibling_count: siblings.len(),
+                }
+            })
+            .collect();
+        ConversationView {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            model: self.model.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            messages,
+            forked_from: self.forked_from.clone(),
+            summary: self.summary.clone(),
+            parameters: self.parameters.clone(),
+        }
+    }
+
+    // The active path as a readable transcript
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n_Model: {}_\n", self.title, self.model);
+        if let Some(summary) = &self.summary {
+            out.push_str(&format!("\n> {}\n", summary));
+        }
+        for message in self.active_path() {
+            let heading = match message.role {
+                Role::System => "System",
+                Role::User => "User",
+                Role::Assistant => "Assistant",
+            };
+            out.push_str(&format!("\n## {}\n\n{}\n", heading, message.content.trim()));
+        }
+        out
+    }
+}
+
+// A message on the active path plus its position among its siblings, for `< 2/3 >` switchers
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PathMessage {
+    #[serde(flatten)]
+    pub message: MessageNode,
+    pub sibling_index: usize,
+    pub sibling_count: usize,
+}
+
+// What the chat shows: the active path only
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationView {
+    pub id: String,
+    pub title: String,
+    pub model: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub messages: Vec<PathMessage>,
+    pub forked_from: Option<ForkOrigin>,
+    pub summary: Option<String>,
+    pub parameters: Option<ParameterSet>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Branch {
+    pub message_id: String,
+    pub preview: String,
+    pub timestamp: u64,
+    pub model: Option<String>,
+    pub active: bool,
+    pub reply_count: usize, // Messages below this branch on its newest path
+}
+
+// Flat conversation as kept in localStorage before the history moved to the backend
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyConversation {
+    pub id: String,
+    pub title: String,
+    pub model: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub messages: Vec<LegacyMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LegacyMessage {
+    pub role: Role,
+    pub content: String,
+    pub timestamp: u64,
+}
+
+// A conversation matching a history search, with the first matching message if any
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub conversation_id: String,
+    pub title: String,
+    pub model: String,
+    pub updated_at: u64,
+    pub message_id: Option<String>, // None when only the title or summary matched
+    pub role: Option<Role>,
+    pub snippet: String,
+}
+
+// --- Store ---
+
+// One JSON file per conversation under the app data dir. Writes go through `update`, which
+// holds the lock for the whole read-modify-write. Clones share the lock.
+#[derive(Clone)]
+pub struct ConversationStore {
+    dir: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl ConversationStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    // Runs `f` on the blocking pool, since every store operation reads or writes files
+    pub async fn run<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&ConversationStore) -> Result<T, ZamaError> + Send + 'static,
+    ) -> Result<T, ZamaError> {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || f(&store)).await?
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, ZamaError> {
+        // Ids become file names, so only allow what `new_id` generates
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(ZamaError::InvalidInput(format!(
+                "Invalid conversation id '{}'",
+                id
+            )));
+        }
+        Ok(self.dir.join(format!("{}.json", id)))
+    }
+
+    pub fn load(&self, id: &str) -> Result<Conversation, ZamaError> {
+        paths::load_json(&self.path(id)?)?
+            .ok_or_else(|| ZamaError::InvalidInput(format!("Conversation {} not found", id)))
+    }
+
+    fn save(&self, conversation: &Conversation) -> Result<(), ZamaError> {
+        paths::write_json_atomic(&self.path(&conversation.id)?, conversation)
+    }
+
+    pub fn create(&self, conversation: &Conversation) -> Result<(), ZamaError> {
+        let _guard = self.lock.lock().unwrap();
+        self.save(conversation)
+    }
+
+    // Applies `f` and persists the result; `updated_at` is bumped for every change
+    pub fn update<T>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&mut Conversation) -> Result<T, ZamaError>,
+    ) -> Result<(Conversation, T), ZamaError> {
+        let _guard = self.lock.lock().unwrap();
+        let mut conversation = self.load(id)?;
+        let result = f(&mut conversation)?;
+        conversation.updated_at = unix_millis();
+        self.save(&conversation)?;
+        Ok((conversation, result))
+    }
+
+    // Newest first; unreadable files are skipped rather than hiding the whole history
+    pub fn list(&self) -> Vec<Conversation> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut conversations: Vec<Conversation> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let id = conversation_id(&path)?;
+                self.load(&id)
+                    .inspect_err(|e| tracing::warn!(error = %e, "Skipping conversation"))
+                    .ok()
+            })
+            .collect();
+        conversations.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+        conversations
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), ZamaError> {
+        let _guard = self.lock.lock().unwrap();
+        match fs::remove_file(self.path(id)?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    // Case-insensitive substring match over titles, summaries and every branch, newest first
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let needle = query.trim().to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        self.list()
+            .into_iter()
+            .filter_map(|conversation| {
+                let message = conversation
+                    .messages
+                    .iter()
+                    .find(|m| m.content.to_lowercase().contains(&needle));
+                let matched = message.is_some()
+                    || conversation.title.to_lowercase().contains(&needle)
+                    || conversation
+                        .summary
+                        .as_deref()
+                        .is_some_and(|s| s.to_lowercase().contains(&needle));
+                matched.then(|| SearchHit {
+                    conversation_id: conversation.id.clone(),
+                    title: conversation.title.clone(),
+                    model: conversation.model.clone(),
+                    updated_at: conversation.updated_at,
+                    message_id: message.map(|m| m.id.clone()),
+                    role: message.map(|m| m.role),
+                    snippet: match message {
+                        Some(m) => snippet(&m.content, &needle),
+                        None => preview(
+                            conversation.summary.as_deref().unwrap_or_default(),
+                            PREVIEW_CHARS,
+                        ),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn exists(&self, id: &str) -> bool {
+        self.path(id).map(|p| p.exists()).unwrap_or(false)
+    }
+}
+
+fn conversation_id(path: &Path) -> Option<String> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    path.file_stem()?.to_str().map(str::to_string)
+}
+
+pub fn init(paths: &impl AppPaths) -> Result<ConversationStore, String> {
+    let dir = paths.data_dir()?.join(CONVERSATIONS_DIR);
+    Ok(ConversationStore::new(dir))
+}
+
+pub(crate) fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+pub(crate) fn new_id(prefix: &str) -> String {
+    let mut bytes = [0u8; 6];
+    // Falls back to the clock alone; ids only need to be unique within this machine
+    let _ = getrandom::fill(&mut bytes);
+    format!(
+        "{}_{}_{}",
+        prefix,
+        unix_millis(),
+        bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    )
+}
+
+fn preview(content: &str, max_chars: usize) -> String {
+    let cleaned = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.chars().count() > max_chars {
+        format!("{}...", cleaned.chars().take(max_chars).collect::<String>())
+    } else {
+        cleaned
+    }
+}
+
+// Text around the first match of `needle` (already lowercased)
+fn snippet(content: &str, needle: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    let needle: Vec<char> = needle.chars().collect();
+    // Lowercasing can change the length of a few characters, so fall back to the start then
+    let at = if lower.len() == chars.len() {
+        lower
+            .windows(needle.len())
+            .position(|w| w == needle.as_slice())
+            .unwrap_or_default()
+    } else {
+        0
+    };
+    let start = at.saturating_sub(PREVIEW_CHARS / 2);
+    let text: String = chars[start..].iter().collect();
+    let text = preview(&text, PREVIEW_CHARS);
+    if start > 0 {
+        format!("...{}", text)
+    } else {
+        text
+    }
+}
+
+// Placeholder title from the first user message, truncated to ~40 chars, until the titling model
+// has replied
+fn title_from_message(content: &str) -> String {
+    preview(content, 40)
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub async fn list_conversations(
+    store: tauri::State<'_, ConversationStore>,
+) -> Result<Vec<ConversationView>, ZamaError> {
+    store
+        .run(|store| Ok(store.list().iter().map(Conversation::view).collect()))
+        .await
+}
+
+#[tauri::command]
+pub async fn get_conversation(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+) -> Result<ConversationView, ZamaError> {
+    store
+        .run(move |store| Ok(store.load(&conversation_id)?.view()))
+        .await
+}
+
+#[tauri::command]
+pub async fn create_conversation(
+    store: tauri::State<'_, ConversationStore>,
+    model: String,
+    title: Option<String>,
+) -> Result<ConversationView, ZamaError> {
+    let conversation = Conversation::new(model, title);
+    store
+        .run(move |store| {
+            store.create(&conversation)?;
+            Ok(conversation.view())
+        })
+        .await
+}
+
+// Appends to the end of the active path
+#[tauri::command]
+pub async fn append_message(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    role: Role,
+    content: String,
+    model: Option<String>,
+) -> Result<ConversationView, ZamaError> {
+    store
+        .run(move |store| {
+            let (conversation, _) = store.update(&conversation_id, |conversation| {
+                let parent_id = conversation.active_leaf.clone();
+                Ok(conversation.push(parent_id, role, content, model))
+            })?;
+            Ok(conversation.view())
+        })
+        .await
+}
+
+// Replaces a message in place, eg. once a streamed reply is complete along with its stats and
+// thinking
+#[tauri::command]
+pub async fn update_message<R: Runtime>(
+    app_handle: AppHandle<R>,
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    message_id: String,
+    content: String,
+    stats: Option<GenerationStats>,
+    thinking: Option<String>,
+) -> Result<ConversationView, ZamaError> {
+    let (conversation, first_exchange) = store
+        .run(move |store| {
+            store.update(&conversation_id, |conversation| {
+                let message = conversation.message_mut(&message_id)?;
+                message.content = content;
+                if stats.is_some() {
+                    message.stats = stats;
+                }
+                if thinking.is_some() {
+                    message.thinking = thinking.filter(|t| !t.trim().is_empty());
+                }
+                let completed_reply =
+                    message.role == Role::Assistant && !message.content.is_empty();
+                let path = conversation.path_to(&message_id);
+                Ok(completed_reply && path.iter().filter(|m| m.role == Role::User).count() == 1)
+            })
+        })
+        .await?;
+    // Title and summarize once the first reply is in, without holding up the chat
+    if first_exchange && conversation.summary.is_none() {
+        titles::spawn(app_handle, conversation.id.clone(), false);
+    }
+    Ok(conversation.view())
+}
+
+// Adds an edited copy of `message_id` as a sibling and makes it active. The caller then
+// generates a reply to it; the original message and its replies stay on their own branch.
+#[tauri::command]
+pub async fn edit_message(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    message_id: String,
+    content: String,
+) -> Result<ConversationView, ZamaError> {
+    store
+        .run(move |store| {
+            let (conversation, _) = store.update(&conversation_id, |conversation| {
+                let original = conversation.message(&message_id)?;
+                let (parent_id, role) = (original.parent_id.clone(), original.role);
+                Ok(conversation.push(parent_id, role, content, None))
+            })?;
+            Ok(conversation.view())
+        })
+        .await
+}
+
+// Adds an empty assistant sibling of `message_id` for the caller to stream a new reply into
+#[tauri::command]
+pub async fn regenerate_message(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    message_id: String,
+    model: Option<String>,
+) -> Result<ConversationView, ZamaError> {
+    store
+        .run(move |store| {
+            let (conversation, _) = store.update(&conversation_id, |conversation| {
+                let original = conversation.message(&message_id)?;
+                if original.role != Role::Assistant {
+                    return Err(ZamaError::InvalidInput(
+                        "Only assistant replies can be regenerated".to_string(),
+                    ));
+                }
+                let parent_id = original.parent_id.clone();
+                let model = model.or_else(|| original.model.clone());
+                Ok(conversation.push(parent_id, Role::Assistant, String::new(), model))
+            })?;
+            Ok(conversation.view())
+        })
+        .await
+}
+
+// Alternatives at the position of `message_id`, including itself
+#[tauri::command]
+pub async fn list_branches(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    message_id: String,
+) -> Result<Vec<Branch>, ZamaError> {
+    let conversation = store.run(move |store| store.load(&conversation_id)).await?;
+    let message = conversation.message(&message_id)?;
+    let active: Vec<&str> = conversation
+        .active_path()
+        .iter()
+        .map(|m| m.id.as_str())
+        .collect();
+    Ok(conversation
+        .children(message.parent_id.as_deref())
+        .map(|sibling| {
+            let leaf = conversation.newest_leaf(&sibling.id);
+            Branch {
+                message_id: sibling.id.clone(),
+                preview: preview(&sibling.content, PREVIEW_CHARS),
+                timestamp: sibling.timestamp,
+                model: sibling.model.clone(),
+                active: active.contains(&sibling.id.as_str()),
+                reply_count: conversation.path_to(&leaf).len()
+                    - conversation.path_to(&sibling.id).len(),
+            }
+        })
+        .collect())
+}
+
+// Makes the branch containing `message_id` the active path
+#[tauri::command]
+pub async fn switch_branch(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    message_id: String,
+) -> Result<ConversationView, ZamaError> {
+    store
+        .run(move |store| {
+            let (conversation, _) = store.update(&conversation_id, |conversation| {
+                conversation.message(&message_id)?;
+                conversation.active_leaf = Some(conversation.newest_leaf(&message_id));
+                Ok(())
+            })?;
+            Ok(conversation.view())
+        })
+        .await
+}
+
+// Copies the path up to `message_id` (default: the whole active path) into a new conversation
+#[tauri::command]
+pub async fn fork_conversation(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    message_id: Option<String>,
+) -> Result<ConversationView, ZamaError> {
+    store
+        .run(move |store| {
+            let source = store.load(&conversation_id)?;
+            let leaf_id = match message_id {
+                Some(id) => source.message(&id)?.id.clone(),
+                None => source
+                    .active_leaf
+                    .clone()
+                    .ok_or_else(|| ZamaError::InvalidInput("Conversation is empty".to_string()))?,
+            };
+
+            let mut fork = Conversation::new(
+                source.model.clone(),
+                Some(format!("{} (fork)", source.title)),
+            );
+            fork.messages = source.path_to(&leaf_id).into_iter().cloned().collect();
+            fork.active_leaf = Some(leaf_id.clone());
+            fork.parameters = source.parameters.clone();
+            fork.forked_from = Some(ForkOrigin {
+                conversation_id: source.id.clone(),
+                message_id: leaf_id,
+            });
+            store.create(&fork)?;
+            Ok(fork.view())
+        })
+        .await
+}
+
+#[tauri::command]
+pub async fn rename_conversation(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    title: String,
+) -> Result<ConversationView, ZamaError> {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err(ZamaError::InvalidInput("Title cannot be empty".to_string()));
+    }
+    store
+        .run(move |store| {
+            let (conversation, _) = store.update(&conversation_id, |conversation| {
+                conversation.title = title;
+                conversation.title_edited = true;
+                Ok(())
+            })?;
+            Ok(conversation.view())
+        })
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_conversation(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+) -> Result<(), ZamaError> {
+    store.run(move |store| store.delete(&conversation_id)).await
+}
+
+#[tauri::command]
+pub async fn clear_conversations(
+    store: tauri::State<'_, ConversationStore>,
+) -> Result<(), ZamaError> {
+    store
+        .run(|store| {
+            for conversation in store.list() {
+                store.delete(&conversation.id)?;
+            }
+            Ok(())
+        })
+        .await
+}
+
+// One-time migration of the localStorage history; conversations already imported are skipped
+#[tauri::command]
+pub async fn import_conversations(
+    store: tauri::State<'_, ConversationStore>,
+    conversations: Vec<LegacyConversation>,
+) -> Result<usize, ZamaError> {
+    let imported = store
+        .run(move |store| {
+            let mut imported = 0;
+            for legacy in conversations {
+                let id: String = legacy
+                    .id
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                    .collect();
+                if id.is_empty() || store.exists(&id) {
+                    continue;
+                }
+
+                let mut conversation = Conversation {
+                    id,
+                    title: legacy.title,
+                    model: legacy.model.clone(),
+                    created_at: legacy.created_at,
+                    updated_at: legacy.updated_at,
+                    messages: Vec::new(),
+                    active_leaf: None,
+                    forked_from: None,
+                    summary: None,
+                    title_edited: false,
+                    parameters: None,
+                };
+                for message in legacy.messages {
+                    let id = new_id("msg");
+                    conversation.messages.push(MessageNode {
+                        id: id.clone(),
+                        parent_id: conversation.active_leaf.take(),
+                        role: message.role,
+                        content: message.content,
+                        timestamp: message.timestamp,
+                        model: (message.role == Role::Assistant).then(|| legacy.model.clone()),
+                        stats: None,
+                        thinking: None,
+                    });
+                    conversation.active_leaf = Some(id);
+                }
+                store.create(&conversation)?;
+                imported += 1;
+            }
+            Ok(imported)
+        })
+        .await?;
+    tracing::info!(imported, "Imported conversations");
+    Ok(imported)
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::error::ZamaError;
+use crate::paths::{self, AppPaths};
+
+const CREDENTIALS_FILE: &str = "credentials.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Provider {
+    Ollama,
+    Gemini,
+}
+
+impl Provider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Provider::Ollama => "ollama",
+            Provider::Gemini => "gemini",
+        }
+    }
+}
+
+// Provider keys the backend can use without the frontend passing them along, eg. for the local
+// API server. Never serialized back to the frontend.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Credentials {
+    pub gemini_api_key: Option<String>,
+}
+
+impl Credentials {
+    pub fn api_key(&self, provider: Provider) -> Option<&str> {
+        match provider {
+            Provider::Ollama => None, // Local, no key needed
+            Provider::Gemini => self.gemini_api_key.as_deref(),
+        }
+        .filter(|key| !key.trim().is_empty())
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProviderStatus {
+    pub provider: Provider,
+    pub configured: bool,
+}
+
+pub fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Credentials {
+    paths::load_settings(app_handle, CREDENTIALS_FILE)
+}
+
+// Keys stay readable by the current user only
+fn save<R: Runtime>(app_handle: &AppHandle<R>, credentials: &Credentials) -> Result<(), ZamaError> {
+    let path = app_handle
+        .config_dir()
+        .map_err(ZamaError::Io)?
+        .join(CREDENTIALS_FILE);
+    paths::write_private_json_atomic(&path, credentials)
+}
+
+#[tauri::command]
+pub fn set_provider_key<R: Runtime>(
+    app_handle: AppHandle<R>,
+    provider: Provider,
+    api_key: Option<String>,
+) -> Result<(), ZamaError> {
+    let api_key = api_key
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty());
+    let mut credentials = load(&app_handle);
+    match provider {
+        Provider::Ollama => {
+            return Err(ZamaError::InvalidInput(
+                "Ollama does not use an API key".to_string(),
+            ))
+        }
+        Provider::Gemini => credentials.gemini_api_key = api_key,
+    }
+    save(&app_handle, &credentials)?;
+    tracing::info!(provider = provider.as_str(), "Provider credentials updated");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_provider_status<R: Runtime>(app_handle: AppHandle<R>) -> Vec<ProviderStatus> {
+    let credentials = load(&app_handle);
+    [Provider::Ollama, Provider::Gemini]
+        .into_iter()
+        .map(|provider| ProviderStatus {
+            provider,
+            configured: provider == Provider::Ollama || credentials.api_key(provider).is_some(),
+        })
+        .collect()
+}
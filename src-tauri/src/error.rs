@@ -0,0 +1,258 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+// Error returned by every Tauri command. Serializes as
+// `{ code, message, retryable, ...details }` so the frontend can branch on `code`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZamaError {
+    ConnectionRefused {
+        service: String,
+    }, // Nothing listening, eg. Ollama isn't running
+    Timeout {
+        service: String,
+    },
+    Network {
+        service: String,
+        message: String,
+    },
+    Http {
+        status: u16,
+        body: String,
+    },
+    ModelNotFound {
+        model: String,
+    },
+    InvalidInput(String),
+    ProviderAuth {
+        provider: String,
+        message: String,
+    }, // Missing or rejected API key
+    RateLimited {
+        provider: String,
+        retry_after: Option<u64>,
+    },
+    Parse(String),
+    Cancelled,
+    Io(String),
+    Integrity(String), // Checksum or signature mismatch
+    Internal(String),
+}
+
+impl ZamaError {
+    // Stable identifiers, safe to match on in the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            ZamaError::ConnectionRefused { .. } => "connection_refused",
+            ZamaError::Timeout { .. } => "timeout",
+            ZamaError::Network { .. } => "network",
+            ZamaError::Http { .. } => "http",
+            ZamaError::ModelNotFound { .. } => "model_not_found",
+            ZamaError::InvalidInput(_) => "invalid_input",
+            ZamaError::ProviderAuth { .. } => "provider_auth",
+            ZamaError::RateLimited { .. } => "rate_limited",
+            ZamaError::Parse(_) => "parse",
+            ZamaError::Cancelled => "cancelled",
+            ZamaError::Io(_) => "io",
+            ZamaError::Integrity(_) => "integrity",
+            ZamaError::Internal(_) => "internal",
+        }
+    }
+
+    // Whether repeating the same call later may succeed
+    pub fn retryable(&self) -> bool {
+        match self {
+            ZamaError::ConnectionRefused { .. }
+            | ZamaError::Timeout { .. }
+            | ZamaError::Network { .. }
+            | ZamaError::RateLimited { .. } => true,
+            ZamaError::Http { status, .. } => *status == 408 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    // Classifies a failed request to `service` (eg. "Ollama", "Hugging Face")
+    pub fn request(service: &str, e: reqwest::Error) -> Self {
+        // URLs can carry credentials in their query, and these messages reach logs and the UI
+        let e = e.without_url();
+        if e.is_connect() {
+            ZamaError::ConnectionRefused {
+                service: service.to_string(),
+            }
+        } else if e.is_timeout() {
+            ZamaError::Timeout {
+                service: service.to_string(),
+            }
+        } else if e.is_decode() {
+            ZamaError::Parse(format!("Invalid response from {}: {}", service, e))
+        } else if let Some(status) = e.status() {
+            ZamaError::Http {
+                status: status.as_u16(),
+                body: e.to_string(),
+            }
+        } else {
+            ZamaError::Network {
+                service: service.to_string(),
+                message: e.to_string(),
+            }
+        }
+    }
+
+    // Maps a non-success status; `model` turns Ollama's 404s into `ModelNotFound`
+    pub fn status(
+        provider: &str,
+        status: reqwest::StatusCode,
+        body: String,
+        retry_after: Option<u64>,
+        model: Option<&str>,
+    ) -> Self {
+        match (status.as_u16(), model) {
+            (401 | 403, _) => ZamaError::ProviderAuth {
+                provider: provider.to_string(),
+                message: body,
+            },
+            (429, _) => ZamaError::RateLimited {
+                provider: provider.to_string(),
+                retry_after,
+            },
+            (404, Some(model)) => ZamaError::ModelNotFound {
+                model: model.to_string(),
+            },
+            (code, _) => ZamaError::Http { status: code, body },
+        }
+    }
+
+    // Reads the body of a non-success response and classifies it
+    pub async fn from_response(
+        provider: &str,
+        res: reqwest::Response,
+        model: Option<&str>,
+    ) -> Self {
+        let status = res.status();
+        let retry_after = res
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok());
+        let body = res
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        Self::status(provider, status, body, retry_after, model)
+    }
+
+    // Gemini reports a bad key as 400 INVALID_ARGUMENT rather than 401
+    pub fn gemini_auth(self) -> Self {
+        match self {
+            ZamaError::Http { status: 400, body } if body.contains("API_KEY_INVALID") => {
+                ZamaError::ProviderAuth {
+                    provider: crate::GEMINI_SERVICE.to_string(),
+                    message: "API key not valid".to_string(),
+                }
+            }
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for ZamaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZamaError::ConnectionRefused { service } => {
+                write!(f, "Could not connect to {}. Is it running?", service)
+            }
+            ZamaError::Timeout { service } => write!(f, "{} took too long to respond", service),
+            ZamaError::Network { service, message } => {
+                write!(f, "Failed to reach {}: {}", service, message)
+            }
+            ZamaError::Http { status, body } => {
+                write!(f, "Request failed with status {}: {}", status, body)
+            }
+            ZamaError::ModelNotFound { model } => write!(f, "Model {} not found", model),
+            ZamaError::InvalidInput(message)
+            | ZamaError::Parse(message)
+            | ZamaError::Io(message)
+            | ZamaError::Integrity(message)
+            | ZamaError::Internal(message) => f.write_str(message),
+            ZamaError::ProviderAuth { provider, message } => {
+                write!(f, "{} rejected the credentials: {}", provider, message)
+            }
+            ZamaError::RateLimited {
+                provider,
+                retry_after: Some(secs),
+            } => write!(f, "{} rate limit reached, retry in {}s", provider, secs),
+            ZamaError::RateLimited { provider, .. } => {
+                write!(f, "{} rate limit reached, try again later", provider)
+            }
+            ZamaError::Cancelled => f.write_str("Cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ZamaError {}
+
+impl Serialize for ZamaError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ZamaError", 5)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("retryable", &self.retryable())?;
+        match self {
+            ZamaError::ConnectionRefused { service }
+            | ZamaError::Timeout { service }
+            | ZamaError::Network { service, .. } => state.serialize_field("service", service)?,
+            ZamaError::Http { status, body } => {
+                state.serialize_field("status", status)?;
+                state.serialize_field("body", body)?;
+            }
+            ZamaError::ModelNotFound { model } => state.serialize_field("model", model)?,
+            ZamaError::ProviderAuth { provider, .. } => {
+                state.serialize_field("provider", provider)?
+            }
+            ZamaError::RateLimited {
+                provider,
+                retry_after,
+            } => {
+                state.serialize_field("provider", provider)?;
+                state.serialize_field("retry_after", retry_after)?;
+            }
+            _ => {}
+        }
+        state.end()
+    }
+}
+
+// Helpers that still report plain strings surface as internal errors
+impl From<String> for ZamaError {
+    fn from(message: String) -> Self {
+        ZamaError::Internal(message)
+    }
+}
+
+impl From<&str> for ZamaError {
+    fn from(message: &str) -> Self {
+        ZamaError::Internal(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for ZamaError {
+    fn from(e: std::io::Error) -> Self {
+        ZamaError::Io(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for ZamaError {
+    fn from(e: tauri::Error) -> Self {
+        ZamaError::Internal(e.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for ZamaError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        if e.is_cancelled() {
+            ZamaError::Cancelled
+        } else {
+            ZamaError::Internal(format!("Background task failed: {}", e))
+        }
+    }
+}
@@ -0,0 +1,822 @@
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::conversations::{new_id, unix_millis};
+use crate::error::ZamaError;
+use crate::http::{HttpClient, HttpState};
+use crate::ollama::{self, ChatEvent, ChatRequest, Message};
+use crate::stats::GenerationStats;
+use crate::{json_schema, paths, prompts};
+
+const REPORTS_DIR: &str = "evals";
+const PROGRESS_EVENT: &str = "eval-progress";
+const DEFAULT_CONCURRENCY: usize = 2;
+const MAX_CONCURRENCY: usize = 8;
+const DEFAULT_JUDGE_THRESHOLD: f64 = 0.7;
+// Scores that move less than this between runs are noise, not a change
+const SCORE_EPSILON: f64 = 0.001;
+
+const JUDGE_PROMPT: &str = "You grade an AI assistant's answer against a rubric. Reply with \
+JSON: `score` is an integer from 0 (fails the rubric entirely) to 10 (fully meets it); `reason` \
+is one sentence explaining the score.";
+
+// --- Suites ---
+
+// A test suite, written in YAML (or JSON). Each case's input is sent to every model and its
+// reply checked against the case's assertions.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvalSuite {
+    pub name: String,
+    #[serde(default)]
+    pub models: Vec<String>, // Used when the run doesn't name any
+    pub system: Option<String>,
+    pub prompt: Option<String>, // Template around each case, with `{{input}}` and the case's vars
+    pub options: Option<Map<String, Value>>,
+    pub judge_model: Option<String>, // Default for `judge` assertions; else the model under test
+    pub concurrency: Option<usize>,
+    pub cases: Vec<EvalCase>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvalCase {
+    pub name: Option<String>, // Default: `case <n>`
+    #[serde(default)]
+    pub input: String,
+    #[serde(default)]
+    pub vars: HashMap<String, String>,
+    // `- contains: Paris` rather than the `!contains Paris` tags serde_yaml expects for enums
+    #[serde(default, with = "serde_yaml::with::singleton_map_recursive")]
+    pub expect: Vec<Assertion>,
+}
+
+// One check on a reply, eg. `- contains: Paris` or `- judge: Answers in one sentence`
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Assertion {
+    Contains(String), // Case-insensitive
+    NotContains(String),
+    Regex(String),
+    JsonSchema(Value), // The reply, or the JSON inside it, must follow the schema
+    Judge(JudgeSpec),  // LLM-as-judge against a rubric
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum JudgeSpec {
+    Rubric(String),
+    Detailed {
+        rubric: String,
+        model: Option<String>,
+        threshold: Option<f64>, // Minimum score from 0 to 1 to pass
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct JudgeVerdict {
+    score: f64,
+    reason: String,
+}
+
+// --- Reports ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssertionResult {
+    pub kind: String, // `contains`, `regex`, `json_schema`, ...
+    pub expected: String,
+    pub passed: bool,
+    pub score: f64,             // 0 to 1; a judge's grade, else 0 or 1
+    pub detail: Option<String>, // Why it failed, or the judge's reasoning
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaseResult {
+    pub model: String,
+    pub case: String,
+    pub prompt: String,
+    pub output: Option<String>,
+    pub error: Option<String>, // The model failed to answer; the case scores 0
+    pub passed: bool,
+    pub score: f64, // Mean of the assertion scores
+    pub assertions: Vec<AssertionResult>,
+    pub duration_ms: u64,
+    pub stats: Option<GenerationStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModelScore {
+    pub model: String,
+    pub score: f64, // Mean case score, 0 to 1
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize, // Cases where the model didn't answer
+    pub avg_duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EvalReport {
+    pub id: String,
+    pub suite: String,
+    pub suite_hash: String, // Changes whenever the suite file does
+    pub created_at: u64,
+    pub duration_ms: u64,
+    pub baseline_id: Option<String>, // The previous report of the same suite
+    pub models: Vec<ModelScore>,
+    pub results: Vec<CaseResult>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EvalReportSummary {
+    pub id: String,
+    pub suite: String,
+    pub suite_hash: String,
+    pub created_at: u64,
+    pub models: Vec<ModelScore>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseChangeKind {
+    Regressed, // Passed before, fails now
+    Fixed,
+    ScoreChanged,
+    OutputChanged,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CaseChange {
+    pub model: String,
+    pub case: String,
+    pub kind: CaseChangeKind,
+    pub base_score: Option<f64>,
+    pub head_score: Option<f64>,
+    pub base_output: Option<String>,
+    pub head_output: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModelScoreDelta {
+    pub model: String,
+    pub base_score: Option<f64>,
+    pub head_score: Option<f64>,
+    pub delta: Option<f64>,
+}
+
+// What changed from `base` to `head`; unchanged cases are left out
+#[derive(Debug, Serialize, Clone)]
+pub struct EvalDiff {
+    pub base_id: String,
+    pub head_id: String,
+    pub same_suite: bool, // False when the suite file changed in between
+    pub models: Vec<ModelScoreDelta>,
+    pub changes: Vec<CaseChange>,
+    pub regressions: usize,
+    pub fixes: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EvalProgress {
+    pub run_id: String,
+    pub model: String,
+    pub case: String,
+    pub completed: usize,
+    pub total: usize,
+    pub passed: bool,
+}
+
+// --- Assertions ---
+
+// The JSON in a reply: all of it, a fenced code block, or the outermost braces
+fn extract_json(output: &str) -> Option<Value> {
+    let trimmed = output.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+    if let Some(start) = trimmed.find("```") {
+        let block = &trimmed[start + 3..];
+        let block = block.split_once('\n').map_or(block, |(_, rest)| rest);
+        if let Some(end) = block.find("```") {
+            if let Ok(value) = serde_json::from_str(block[..end].trim()) {
+                return Some(value);
+            }
+        }
+    }
+    let (start, end) = (trimmed.find(['{', '['])?, trimmed.rfind(['}', ']'])?);
+    (start < end)
+        .then(|| serde_json::from_str(&trimmed[start..=end]).ok())
+        .flatten()
+}
+
+fn pass_fail(
+    kind: &str,
+    expected: String,
+    passed: bool,
+    detail: Option<String>,
+) -> AssertionResult {
+    AssertionResult {
+        kind: kind.to_string(),
+        expected,
+        passed,
+        score: if passed { 1.0 } else { 0.0 },
+        detail: (!passed).then_some(detail).flatten(),
+    }
+}
+
+async fn judge(
+    client: &HttpClient,
+    model: &str,
+    rubric: &str,
+    prompt: &str,
+    output: &str,
+) -> Result<JudgeVerdict, ZamaError> {
+    let messages = [
+        Message {
+            role: "system".to_string(),
+            content: JUDGE_PROMPT.to_string(),
+            thinking: None,
+        },
+        Message {
+            role: "user".to_string(),
+            content: format!(
+                "Rubric:\n{}\n\nQuestion:\n{}\n\nAnswer:\n{}",
+                rubric, prompt, output
+            ),
+            thinking: None,
+        },
+    ];
+    let format = json!({
+        "type": "object",
+        "properties": {
+            "score": { "type": "integer" },
+            "reason": { "type": "string" },
+        },
+        "required": ["score", "reason"],
+    });
+    let reply = ollama::complete(client, model, &messages, Some(format)).await?;
+    let mut verdict: JudgeVerdict = serde_json::from_str(&reply)
+        .map_err(|e| ZamaError::Parse(format!("Invalid judge response: {}", e)))?;
+    verdict.score = (verdict.score / 10.0).clamp(0.0, 1.0);
+    Ok(verdict)
+}
+
+async fn check(
+    client: &HttpClient,
+    assertion: &Assertion,
+    judge_model: &str,
+    prompt: &str,
+    output: &str,
+) -> AssertionResult {
+    match assertion {
+        Assertion::Contains(needle) => pass_fail(
+            "contains",
+            needle.clone(),
+            output.to_lowercase().contains(&needle.to_lowercase()),
+            Some("Not found in the reply".to_string()),
+        ),
+        Assertion::NotContains(needle) => pass_fail(
+            "not_contains",
+            needle.clone(),
+            !output.to_lowercase().contains(&needle.to_lowercase()),
+            Some("Found in the reply".to_string()),
+        ),
+        Assertion::Regex(pattern) => match regex::Regex::new(pattern) {
+            Ok(re) => pass_fail(
+                "regex",
+                pattern.clone(),
+                re.is_match(output),
+                Some("No match in the reply".to_string()),
+            ),
+            Err(e) => pass_fail(
+                "regex",
+                pattern.clone(),
+                false,
+                Some(format!("Invalid regex: {}", e)),
+            ),
+        },
+        Assertion::JsonSchema(schema) => {
+            let expected = schema.to_string();
+            match extract_json(output) {
+                Some(value) => {
+                    let errors = json_schema::validate(schema, &value);
+                    pass_fail(
+                        "json_schema",
+                        expected,
+                        errors.is_empty(),
+                        Some(errors.join("; ")),
+                    )
+                }
+                None => pass_fail(
+                    "json_schema",
+                    expected,
+                    false,
+                    Some("The reply contains no JSON".to_string()),
+                ),
+            }
+        }
+        Assertion::Judge(spec) => {
+            let (rubric, model, threshold) = match spec {
+                JudgeSpec::Rubric(rubric) => (rubric, None, None),
+                JudgeSpec::Detailed {
+                    rubric,
+                    model,
+                    threshold,
+                } => (rubric, model.as_deref(), *threshold),
+            };
+            let model = model.unwrap_or(judge_model);
+            let threshold = threshold.unwrap_or(DEFAULT_JUDGE_THRESHOLD);
+            match judge(client, model, rubric, prompt, output).await {
+                Ok(verdict) => AssertionResult {
+                    kind: "judge".to_string(),
+                    expected: rubric.clone(),
+                    passed: verdict.score >= threshold,
+                    score: verdict.score,
+                    detail: Some(verdict.reason),
+                },
+                Err(e) => pass_fail(
+                    "judge",
+                    rubric.clone(),
+                    false,
+                    Some(format!("Judge {} failed: {}", model, e)),
+                ),
+            }
+        }
+    }
+}
+
+// --- Running ---
+
+fn case_name(case: &EvalCase, index: usize) -> String {
+    case.name
+        .clone()
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| format!("case {}", index + 1))
+}
+
+fn render_prompt(suite: &EvalSuite, case: &EvalCase) -> Result<String, ZamaError> {
+    match &suite.prompt {
+        Some(template) => {
+            let mut vars = case.vars.clone();
+            vars.insert("input".to_string(), case.input.clone());
+            prompts::render(template, &vars)
+        }
+        None => Ok(case.input.clone()),
+    }
+}
+
+async fn run_case<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    client: &HttpClient,
+    suite: &EvalSuite,
+    model: &str,
+    case: &EvalCase,
+    name: String,
+) -> CaseResult {
+    let started = Instant::now();
+    let mut result = CaseResult {
+        model: model.to_string(),
+        case: name,
+        prompt: String::new(),
+        output: None,
+        error: None,
+        passed: false,
+        score: 0.0,
+        assertions: Vec::new(),
+        duration_ms: 0,
+        stats: None,
+    };
+    let prompt = match render_prompt(suite, case) {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    result.prompt = prompt.clone();
+
+    let mut messages = Vec::new();
+    if let Some(system) = suite.system.as_ref().filter(|s| !s.trim().is_empty()) {
+        messages.push(Message {
+            role: "system".to_string(),
+            content: system.clone(),
+            thinking: None,
+        });
+    }
+    messages.push(Message {
+        role: "user".to_string(),
+        content: prompt.clone(),
+        thinking: None,
+    });
+    let request = ChatRequest {
+        model: model.to_string(),
+        messages,
+        stream: true,
+        think: None,
+        options: suite.options.clone(),
+        conversation_id: None,
+    };
+
+    let mut output = String::new();
+    let outcome = ollama::stream_chat(app_handle, &app_handle.state(), client, &request, |event| {
+        if let ChatEvent::Chunk(chunk) = event {
+            if let Some(message) = chunk.message {
+                output.push_str(&message.content);
+            }
+            if chunk.done {
+                result.stats = chunk.stats;
+            }
+        }
+        Ok(())
+    })
+    .await;
+    if let Err(e) = outcome {
+        result.error = Some(e.to_string());
+        result.duration_ms = started.elapsed().as_millis() as u64;
+        return result;
+    }
+
+    let judge_model = suite.judge_model.as_deref().unwrap_or(model);
+    for assertion in &case.expect {
+        result
+            .assertions
+            .push(check(client, assertion, judge_model, &prompt, &output).await);
+    }
+    result.passed = result.assertions.iter().all(|a| a.passed);
+    result.score = if result.assertions.is_empty() {
+        1.0 // Nothing to check; the case only has to get an answer
+    } else {
+        result.assertions.iter().map(|a| a.score).sum::<f64>() / result.assertions.len() as f64
+    };
+    result.output = Some(output);
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    result
+}
+
+fn model_score(model: &str, results: &[CaseResult]) -> ModelScore {
+    let results: Vec<&CaseResult> = results.iter().filter(|r| r.model == model).collect();
+    let count = results.len().max(1);
+    ModelScore {
+        model: model.to_string(),
+        score: results.iter().map(|r| r.score).sum::<f64>() / count as f64,
+        passed: results.iter().filter(|r| r.passed).count(),
+        failed: results.iter().filter(|r| !r.passed).count(),
+        errors: results.iter().filter(|r| r.error.is_some()).count(),
+        avg_duration_ms: results.iter().map(|r| r.duration_ms).sum::<u64>() / count as u64,
+    }
+}
+
+pub fn parse_suite(content: &str) -> Result<EvalSuite, ZamaError> {
+    let suite: EvalSuite = serde_yaml::from_str(content)
+        .map_err(|e| ZamaError::InvalidInput(format!("Invalid eval suite: {}", e)))?;
+    if suite.name.trim().is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "The suite needs a name".to_string(),
+        ));
+    }
+    if suite.cases.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "The suite has no cases".to_string(),
+        ));
+    }
+    for (i, case) in suite.cases.iter().enumerate() {
+        if case.input.trim().is_empty() && suite.prompt.is_none() {
+            return Err(ZamaError::InvalidInput(format!(
+                "{} has no input",
+                case_name(case, i)
+            )));
+        }
+        for assertion in &case.expect {
+            if let Assertion::Regex(pattern) = assertion {
+                regex::Regex::new(pattern).map_err(|e| {
+                    ZamaError::InvalidInput(format!(
+                        "{}: invalid regex '{}': {}",
+                        case_name(case, i),
+                        pattern,
+                        e
+                    ))
+                })?;
+            }
+        }
+    }
+    Ok(suite)
+}
+
+fn suite_hash(content: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+// --- Store ---
+
+// One JSON file per report under the app data dir; reports are never modified once written
+fn reports_dir<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, ZamaError> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(REPORTS_DIR))
+        .map_err(|e| ZamaError::Io(format!("Failed to resolve app data dir: {}", e)))
+}
+
+fn report_path<R: Runtime>(app_handle: &AppHandle<R>, id: &str) -> Result<PathBuf, ZamaError> {
+    // Ids become file names, so only allow what `new_id` generates
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(ZamaError::InvalidInput(format!(
+            "Invalid report id '{}'",
+            id
+        )));
+    }
+    Ok(reports_dir(app_handle)?.join(format!("{}.json", id)))
+}
+
+fn load_report<R: Runtime>(app_handle: &AppHandle<R>, id: &str) -> Result<EvalReport, ZamaError> {
+    paths::load_json(&report_path(app_handle, id)?)?
+        .ok_or_else(|| ZamaError::InvalidInput(format!("Eval report {} not found", id)))
+}
+
+fn save_report<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    report: &EvalReport,
+) -> Result<(), ZamaError> {
+    paths::write_json_atomic(&report_path(app_handle, &report.id)?, report)
+}
+
+// Newest first; unreadable files are skipped
+fn load_reports<R: Runtime>(app_handle: &AppHandle<R>) -> Vec<EvalReport> {
+    let Ok(entries) =
+        reports_dir(app_handle).and_then(|dir| fs::read_dir(dir).map_err(ZamaError::from))
+    else {
+        return Vec::new();
+    };
+    let mut reports: Vec<EvalReport> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            let id = path.file_stem()?.to_str()?.to_string();
+            load_report(app_handle, &id)
+                .inspect_err(|e| tracing::warn!(error = %e, "Skipping eval report"))
+                .ok()
+        })
+        .collect();
+    reports.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+    reports
+}
+
+pub fn diff(base: &EvalReport, head: &EvalReport) -> EvalDiff {
+    let key = |r: &CaseResult| (r.model.clone(), r.case.clone());
+    let base_results: HashMap<(String, String), &CaseResult> =
+        base.results.iter().map(|r| (key(r), r)).collect();
+    let head_keys: Vec<(String, String)> = head.results.iter().map(key).collect();
+
+    let mut changes = Vec::new();
+    for result in &head.results {
+        let change = match base_results.get(&key(result)) {
+            None => Some(CaseChangeKind::Added),
+            Some(before) if before.passed && !result.passed => Some(CaseChangeKind::Regressed),
+            Some(before) if !before.passed && result.passed => Some(CaseChangeKind::Fixed),
+            Some(before) if (before.score - result.score).abs() > SCORE_EPSILON => {
+                Some(CaseChangeKind::ScoreChanged)
+            }
+            Some(before) if before.output != result.output => Some(CaseChangeKind::OutputChanged),
+            Some(_) => None,
+        };
+        if let Some(kind) = change {
+            let before = base_results.get(&key(result));
+            changes.push(CaseChange {
+                model: result.model.clone(),
+                case: result.case.clone(),
+                kind,
+                base_score: before.map(|b| b.score),
+                head_score: Some(result.score),
+                base_output: before.and_then(|b| b.output.clone()),
+                head_output: result.output.clone(),
+            });
+        }
+    }
+    for result in &base.results {
+        if !head_keys.contains(&key(result)) {
+            changes.push(CaseChange {
+                model: result.model.clone(),
+                case: result.case.clone(),
+                kind: CaseChangeKind::Removed,
+                base_score: Some(result.score),
+                head_score: None,
+                base_output: result.output.clone(),
+                head_output: None,
+            });
+        }
+    }
+
+    let mut models: Vec<String> = head.models.iter().map(|m| m.model.clone()).collect();
+    for model in &base.models {
+        if !models.contains(&model.model) {
+            models.push(model.model.clone());
+        }
+    }
+    let score = |report: &EvalReport, model: &str| {
+        report
+            .models
+            .iter()
+            .find(|m| m.model == model)
+            .map(|m| m.score)
+    };
+    EvalDiff {
+        base_id: base.id.clone(),
+        head_id: head.id.clone(),
+        same_suite: base.suite_hash == head.suite_hash,
+        models: models
+            .into_iter()
+            .map(|model| {
+                let (base_score, head_score) = (score(base, &model), score(head, &model));
+                ModelScoreDelta {
+                    delta: base_score.zip(head_score).map(|(b, h)| h - b),
+                    model,
+                    base_score,
+                    head_score,
+                }
+            })
+            .collect(),
+        regressions: changes
+            .iter()
+            .filter(|c| c.kind == CaseChangeKind::Regressed)
+            .count(),
+        fixes: changes
+            .iter()
+            .filter(|c| c.kind == CaseChangeKind::Fixed)
+            .count(),
+        changes,
+    }
+}
+
+// --- Commands ---
+
+// Runs a suite against `models` (default: the suite's own list) and stores the scored report.
+// Models run one after another so Ollama doesn't swap them in and out; each model's cases run
+// `concurrency` at a time. Progress arrives on `eval-progress`.
+#[tauri::command]
+pub async fn run_eval<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    suite: String,
+    models: Option<Vec<String>>,
+    run_id: Option<String>,
+) -> Result<EvalReport, ZamaError> {
+    let parsed = parse_suite(&suite)?;
+    let models = models
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| parsed.models.clone());
+    if models.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Select at least one model to evaluate".to_string(),
+        ));
+    }
+    for model in &models {
+        ollama::validate_model_name(model)?;
+    }
+    if let Some(judge_model) = &parsed.judge_model {
+        ollama::validate_model_name(judge_model)?;
+    }
+
+    // The id names the report file, so reject bad or reused ids before spending time on the run
+    let run_id = run_id.unwrap_or_else(|| new_id("eval"));
+    let path = report_path(&app_handle, &run_id)?;
+    if tokio::task::spawn_blocking(move || path.try_exists()).await?? {
+        return Err(ZamaError::InvalidInput(format!(
+            "Eval report {} already exists",
+            run_id
+        )));
+    }
+    let concurrency = parsed
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+    let client = http.client();
+    let total = models.len() * parsed.cases.len();
+    tracing::info!(%run_id, suite = %parsed.name, models = models.len(), cases = parsed.cases.len(), "Starting eval run");
+
+    let started = Instant::now();
+    let mut results = Vec::with_capacity(total);
+    for model in &models {
+        // Indices rather than `&EvalCase`s keep the future `Send` for the command handler
+        let mut cases = stream::iter(0..parsed.cases.len())
+            .map(|i| {
+                let case = &parsed.cases[i];
+                run_case(
+                    &app_handle,
+                    &client,
+                    &parsed,
+                    model,
+                    case,
+                    case_name(case, i),
+                )
+            })
+            .buffered(concurrency);
+        while let Some(result) = cases.next().await {
+            let progress = EvalProgress {
+                run_id: run_id.clone(),
+                model: model.clone(),
+                case: result.case.clone(),
+                completed: results.len() + 1,
+                total,
+                passed: result.passed,
+            };
+            app_handle.emit(PROGRESS_EVENT, progress)?;
+            results.push(result);
+        }
+    }
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let handle = app_handle.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        let baseline_id = load_reports(&handle)
+            .into_iter()
+            .find(|r| r.suite == parsed.name)
+            .map(|r| r.id);
+        let report = EvalReport {
+            id: run_id,
+            suite: parsed.name,
+            suite_hash: suite_hash(&suite),
+            created_at: unix_millis(),
+            duration_ms,
+            baseline_id,
+            models: models.iter().map(|m| model_score(m, &results)).collect(),
+            results,
+        };
+        save_report(&handle, &report)?;
+        Ok::<_, ZamaError>(report)
+    })
+    .await??;
+    tracing::info!(report = %report.id, suite = %report.suite, "Eval run finished");
+    Ok(report)
+}
+
+// Newest first; `suite` narrows to one suite's history
+#[tauri::command]
+pub async fn list_eval_reports<R: Runtime>(
+    app_handle: AppHandle<R>,
+    suite: Option<String>,
+) -> Result<Vec<EvalReportSummary>, ZamaError> {
+    let reports = tokio::task::spawn_blocking(move || load_reports(&app_handle)).await?;
+    Ok(reports
+        .into_iter()
+        .filter(|r| suite.as_ref().is_none_or(|s| &r.suite == s))
+        .map(|r| EvalReportSummary {
+            id: r.id,
+            suite: r.suite,
+            suite_hash: r.suite_hash,
+            created_at: r.created_at,
+            models: r.models,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_eval_report<R: Runtime>(
+    app_handle: AppHandle<R>,
+    id: String,
+) -> Result<EvalReport, ZamaError> {
+    tokio::task::spawn_blocking(move || load_report(&app_handle, &id)).await?
+}
+
+#[tauri::command]
+pub async fn delete_eval_report<R: Runtime>(
+    app_handle: AppHandle<R>,
+    id: String,
+) -> Result<(), ZamaError> {
+    let path = report_path(&app_handle, &id)?;
+    match tokio::task::spawn_blocking(move || fs::remove_file(path)).await? {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+// What changed from `base_id` to `head_id`; `base_id` defaults to the head's baseline
+#[tauri::command]
+pub async fn diff_eval_reports<R: Runtime>(
+    app_handle: AppHandle<R>,
+    head_id: String,
+    base_id: Option<String>,
+) -> Result<EvalDiff, ZamaError> {
+    tokio::task::spawn_blocking(move || {
+        let head = load_report(&app_handle, &head_id)?;
+        let base_id = base_id
+            .or_else(|| head.baseline_id.clone())
+            .ok_or_else(|| {
+                ZamaError::InvalidInput(format!("Report {} has no earlier run to compare", head_id))
+            })?;
+        let base = load_report(&app_handle, &base_id)?;
+        Ok(diff(&base, &head))
+    })
+    .await?
+}
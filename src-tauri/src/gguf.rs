@@ -0,0 +1,505 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+
+// Guards against corrupt or hostile headers asking us to allocate absurd amounts of memory
+const MAX_STRING_LEN: u64 = 64 * 1024 * 1024;
+const MAX_KV_COUNT: u64 = 1_000_000;
+const MAX_TENSOR_COUNT: u64 = 1_000_000;
+const MAX_TENSOR_DIMS: u32 = 8;
+// Arrays of arrays recurse; real files never nest, so anything deep is corrupt or hostile
+const MAX_ARRAY_DEPTH: usize = 4;
+
+// Arrays such as `tokenizer.ggml.tokens` hold hundreds of thousands of entries; only the
+// length and the first few values are kept
+const MAX_ARRAY_VALUES: usize = 64;
+
+// --- GGUF Schemas ---
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum GgufValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Array(GgufArray),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GgufArray {
+    pub len: u64,
+    pub values: Vec<GgufValue>, // Truncated to the first MAX_ARRAY_VALUES entries
+}
+
+impl GgufValue {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            GgufValue::UInt(v) => Some(*v),
+            GgufValue::Int(v) => u64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            GgufValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            GgufValue::Float(v) => Some(*v),
+            GgufValue::UInt(v) => Some(*v as f64),
+            GgufValue::Int(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GgufHeader {
+    pub version: u32,
+    pub tensor_count: u64,
+    pub metadata: BTreeMap<String, GgufValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GgufTensorInfo {
+    pub name: String,
+    pub shape: Vec<u64>,
+    pub ggml_type: String,
+    pub offset: u64,
+    pub elements: u64,
+    pub size_bytes: Option<u64>, // Unknown for tensor types we have no block layout for
+}
+
+// Tensor count and total size for one ggml type
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GgufTypeSummary {
+    pub ggml_type: String,
+    pub tensors: u64,
+    pub elements: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GgufRope {
+    pub dimension_count: Option<u64>,
+    pub freq_base: Option<f64>,
+    pub scaling_type: Option<String>,
+    pub scaling_factor: Option<f64>,
+    pub original_context_length: Option<u64>,
+}
+
+// Everything `inspect_gguf` reports about a file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GgufInspection {
+    pub path: String,
+    pub file_size: u64,
+    pub version: u32,
+    pub name: Option<String>,
+    pub architecture: Option<String>,
+    pub file_type: Option<String>,
+    pub parameter_count: u64,
+    pub context_length: Option<u64>,
+    pub layer_count: Option<u64>,
+    pub embedding_length: Option<u64>,
+    pub feed_forward_length: Option<u64>,
+    pub head_count: Option<u64>,
+    pub head_count_kv: Option<u64>,
+    pub rope: GgufRope,
+    pub tokenizer_model: Option<String>,
+    pub vocab_size: Option<u64>,
+    pub chat_template: Option<String>,
+    pub tensor_types: Vec<GgufTypeSummary>,
+    pub tensors: Vec<GgufTensorInfo>,
+    pub metadata: BTreeMap<String, GgufValue>,
+}
+
+impl GgufHeader {
+    pub fn get(&self, key: &str) -> Option<&GgufValue> {
+        self.metadata.get(key)
+    }
+
+    pub fn architecture(&self) -> Option<&str> {
+        self.get("general.architecture").and_then(GgufValue::as_str)
+    }
+
+    // Per-architecture keys are namespaced, eg. `llama.context_length`
+    pub fn arch_u64(&self, suffix: &str) -> Option<u64> {
+        let arch = self.architecture()?;
+        self.get(&format!("{}.{}", arch, suffix))
+            .and_then(GgufValue::as_u64)
+    }
+
+    pub fn arch_f64(&self, suffix: &str) -> Option<f64> {
+        let arch = self.architecture()?;
+        self.get(&format!("{}.{}", arch, suffix))
+            .and_then(GgufValue::as_f64)
+    }
+
+    pub fn arch_str(&self, suffix: &str) -> Option<&str> {
+        let arch = self.architecture()?;
+        self.get(&format!("{}.{}", arch, suffix))
+            .and_then(GgufValue::as_str)
+    }
+
+    pub fn context_length(&self) -> Option<u64> {
+        self.arch_u64("context_length")
+    }
+
+    pub fn chat_template(&self) -> Option<&str> {
+        self.get("tokenizer.chat_template")
+            .and_then(GgufValue::as_str)
+    }
+
+    pub fn file_type(&self) -> Option<&'static str> {
+        self.get("general.file_type")
+            .and_then(GgufValue::as_u64)
+            .and_then(file_type_name)
+    }
+
+    // Some models only store the vocabulary as `tokenizer.ggml.tokens`
+    pub fn vocab_size(&self) -> Option<u64> {
+        self.arch_u64("vocab_size")
+            .or_else(|| match self.get("tokenizer.ggml.tokens") {
+                Some(GgufValue::Array(tokens)) => Some(tokens.len),
+                _ => None,
+            })
+    }
+}
+
+// --- Parsing ---
+
+pub fn read_header(path: &Path) -> Result<GgufHeader, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open GGUF file: {}", e))?;
+    let mut reader = GgufReader::new(BufReader::new(file));
+    reader.read_header()
+}
+
+pub fn inspect(path: &Path) -> Result<GgufInspection, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open GGUF file: {}", e))?;
+    let file_size = file
+        .metadata()
+        .map_err(|e| format!("Failed to read GGUF file metadata: {}", e))?
+        .len();
+    let mut reader = GgufReader::new(BufReader::new(file));
+    let header = reader.read_header()?;
+    let tensors = reader.read_tensor_infos(header.tensor_count)?;
+    Ok(build_inspection(path, file_size, header, tensors))
+}
+
+pub fn build_inspection(
+    path: &Path,
+    file_size: u64,
+    header: GgufHeader,
+    tensors: Vec<GgufTensorInfo>,
+) -> GgufInspection {
+    let mut tensor_types: Vec<GgufTypeSummary> = Vec::new();
+    for tensor in &tensors {
+        let size = tensor.size_bytes.unwrap_or_default();
+        match tensor_types
+            .iter_mut()
+            .find(|t| t.ggml_type == tensor.ggml_type)
+        {
+            Some(summary) => {
+                summary.tensors += 1;
+                summary.elements += tensor.elements;
+                summary.size_bytes += size;
+            }
+            None => tensor_types.push(GgufTypeSummary {
+                ggml_type: tensor.ggml_type.clone(),
+                tensors: 1,
+                elements: tensor.elements,
+                size_bytes: size,
+            }),
+        }
+    }
+    tensor_types.sort_by_key(|t| std::cmp::Reverse(t.size_bytes));
+
+    let rope = GgufRope {
+        dimension_count: header.arch_u64("rope.dimension_count"),
+        freq_base: header.arch_f64("rope.freq_base"),
+        scaling_type: header.arch_str("rope.scaling.type").map(str::to_string),
+        scaling_factor: header.arch_f64("rope.scaling.factor"),
+        original_context_length: header.arch_u64("rope.scaling.original_context_length"),
+    };
+
+    GgufInspection {
+        path: path.display().to_string(),
+        file_size,
+        version: header.version,
+        name: header
+            .get("general.name")
+            .and_then(GgufValue::as_str)
+            .map(str::to_string),
+        architecture: header.architecture().map(str::to_string),
+        file_type: header.file_type().map(str::to_string),
+        parameter_count: tensors.iter().map(|t| t.elements).sum(),
+        context_length: header.context_length(),
+        layer_count: header.arch_u64("block_count"),
+        embedding_length: header.arch_u64("embedding_length"),
+        feed_forward_length: header.arch_u64("feed_forward_length"),
+        head_count: header.arch_u64("attention.head_count"),
+        head_count_kv: header.arch_u64("attention.head_count_kv"),
+        rope,
+        tokenizer_model: header
+            .get("tokenizer.ggml.model")
+            .and_then(GgufValue::as_str)
+            .map(str::to_string),
+        vocab_size: header.vocab_size(),
+        chat_template: header.chat_template().map(str::to_string),
+        tensor_types,
+        tensors,
+        metadata: header.metadata,
+    }
+}
+
+pub struct GgufReader<R: Read> {
+    inner: R,
+    version: u32,
+}
+
+impl<R: Read> GgufReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, version: 3 }
+    }
+
+    pub fn read_header(&mut self) -> Result<GgufHeader, String> {
+        let mut magic = [0u8; 4];
+        self.read_exact(&mut magic)?;
+        if &magic != GGUF_MAGIC {
+            return Err("Not a GGUF file (bad magic)".to_string());
+        }
+
+        self.version = self.read_u32()?;
+        if !(1..=3).contains(&self.version) {
+            // A big-endian file reads as a huge version number on little-endian hosts
+            return Err(format!("Unsupported GGUF version {}", self.version));
+        }
+
+        let tensor_count = self.read_count()?;
+        let kv_count = self.read_count()?;
+        if kv_count > MAX_KV_COUNT {
+            return Err(format!(
+                "GGUF header declares {} metadata entries",
+                kv_count
+            ));
+        }
+
+        let mut metadata = BTreeMap::new();
+        for _ in 0..kv_count {
+            let key = self.read_string()?;
+            let value_type = self.read_u32()?;
+            let value = self.read_value(value_type, 0)?;
+            metadata.insert(key, value);
+        }
+
+        Ok(GgufHeader {
+            version: self.version,
+            tensor_count,
+            metadata,
+        })
+    }
+
+    // Tensor infos directly follow the metadata section
+    pub fn read_tensor_infos(&mut self, count: u64) -> Result<Vec<GgufTensorInfo>, String> {
+        if count > MAX_TENSOR_COUNT {
+            return Err(format!("GGUF header declares {} tensors", count));
+        }
+        let mut tensors = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let name = self.read_string()?;
+            let n_dims = self.read_u32()?;
+            if n_dims > MAX_TENSOR_DIMS {
+                return Err(format!("Tensor {} has {} dimensions", name, n_dims));
+            }
+            let shape = (0..n_dims)
+                .map(|_| self.read_count())
+                .collect::<Result<Vec<u64>, String>>()?;
+            let type_id = self.read_u32()?;
+            let offset = self.read_u64()?;
+
+            let elements = shape.iter().try_fold(1u64, |acc, d| acc.checked_mul(*d));
+            let elements =
+                elements.ok_or_else(|| format!("Tensor {} has an overflowing shape", name))?;
+            let (ggml_type, size_bytes) = match ggml_type_layout(type_id) {
+                Some((type_name, block_elements, block_bytes)) => (
+                    type_name.to_string(),
+                    Some(elements.div_ceil(block_elements) * block_bytes),
+                ),
+                None => (format!("type_{}", type_id), None),
+            };
+
+            tensors.push(GgufTensorInfo {
+                name,
+                shape,
+                ggml_type,
+                offset,
+                elements,
+                size_bytes,
+            });
+        }
+        Ok(tensors)
+    }
+
+    fn read_value(&mut self, value_type: u32, depth: usize) -> Result<GgufValue, String> {
+        Ok(match value_type {
+            0 => GgufValue::UInt(self.read_array::<1>()?[0] as u64),
+            1 => GgufValue::Int(self.read_array::<1>()?[0] as i8 as i64),
+            2 => GgufValue::UInt(u16::from_le_bytes(self.read_array()?) as u64),
+            3 => GgufValue::Int(i16::from_le_bytes(self.read_array()?) as i64),
+            4 => GgufValue::UInt(self.read_u32()? as u64),
+            5 => GgufValue::Int(i32::from_le_bytes(self.read_array()?) as i64),
+            6 => GgufValue::Float(f32::from_le_bytes(self.read_array()?) as f64),
+            7 => GgufValue::Bool(self.read_array::<1>()?[0] != 0),
+            8 => GgufValue::String(self.read_string()?),
+            9 => {
+                if depth >= MAX_ARRAY_DEPTH {
+                    return Err(format!(
+                        "GGUF arrays are nested more than {} deep",
+                        MAX_ARRAY_DEPTH
+                    ));
+                }
+                let item_type = self.read_u32()?;
+                let len = self.read_count()?;
+                let mut values = Vec::new();
+                for i in 0..len {
+                    let value = self.read_value(item_type, depth + 1)?;
+                    if (i as usize) < MAX_ARRAY_VALUES {
+                        values.push(value);
+                    }
+                }
+                GgufValue::Array(GgufArray { len, values })
+            }
+            10 => GgufValue::UInt(self.read_u64()?),
+            11 => GgufValue::Int(i64::from_le_bytes(self.read_array()?)),
+            12 => GgufValue::Float(f64::from_le_bytes(self.read_array()?)),
+            other => return Err(format!("Unknown GGUF metadata value type {}", other)),
+        })
+    }
+
+    fn read_string(&mut self) -> Result<String, String> {
+        let len = self.read_count()?;
+        if len > MAX_STRING_LEN {
+            return Err(format!("GGUF string of {} bytes is too long", len));
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.read_exact(&mut buf)?;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    // GGUF v1 used 32-bit lengths and counts, later versions use 64-bit ones
+    fn read_count(&mut self) -> Result<u64, String> {
+        if self.version == 1 {
+            Ok(self.read_u32()? as u64)
+        } else {
+            self.read_u64()
+        }
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut buf = [0u8; N];
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), String> {
+        self.inner
+            .read_exact(buf)
+            .map_err(|e| format!("Failed to read GGUF header: {}", e))
+    }
+}
+
+// Names for `general.file_type` (llama.cpp's `llama_ftype`)
+pub fn file_type_name(file_type: u64) -> Option<&'static str> {
+    Some(match file_type {
+        0 => "F32",
+        1 => "F16",
+        2 => "Q4_0",
+        3 => "Q4_1",
+        7 => "Q8_0",
+        8 => "Q5_0",
+        9 => "Q5_1",
+        10 => "Q2_K",
+        11 => "Q3_K_S",
+        12 => "Q3_K_M",
+        13 => "Q3_K_L",
+        14 => "Q4_K_S",
+        15 => "Q4_K_M",
+        16 => "Q5_K_S",
+        17 => "Q5_K_M",
+        18 => "Q6_K",
+        19 => "IQ2_XXS",
+        20 => "IQ2_XS",
+        21 => "Q2_K_S",
+        22 => "IQ3_XS",
+        23 => "IQ3_XXS",
+        24 => "IQ1_S",
+        25 => "IQ4_NL",
+        26 => "IQ3_S",
+        27 => "IQ3_M",
+        28 => "IQ2_S",
+        29 => "IQ2_M",
+        30 => "IQ4_XS",
+        31 => "IQ1_M",
+        32 => "BF16",
+        36 => "TQ1_0",
+        37 => "TQ2_0",
+        _ => return None,
+    })
+}
+
+// Name, elements per block and bytes per block for each `ggml_type`
+pub fn ggml_type_layout(type_id: u32) -> Option<(&'static str, u64, u64)> {
+    Some(match type_id {
+        0 => ("F32", 1, 4),
+        1 => ("F16", 1, 2),
+        2 => ("Q4_0", 32, 18),
+        3 => ("Q4_1", 32, 20),
+        6 => ("Q5_0", 32, 22),
+        7 => ("Q5_1", 32, 24),
+        8 => ("Q8_0", 32, 34),
+        9 => ("Q8_1", 32, 36),
+        10 => ("Q2_K", 256, 84),
+        11 => ("Q3_K", 256, 110),
+        12 => ("Q4_K", 256, 144),
+        13 => ("Q5_K", 256, 176),
+        14 => ("Q6_K", 256, 210),
+        15 => ("Q8_K", 256, 292),
+        16 => ("IQ2_XXS", 256, 66),
+        17 => ("IQ2_XS", 256, 74),
+        18 => ("IQ3_XXS", 256, 98),
+        19 => ("IQ1_S", 256, 50),
+        20 => ("IQ4_NL", 32, 18),
+        21 => ("IQ3_S", 256, 110),
+        22 => ("IQ2_S", 256, 82),
+        23 => ("IQ4_XS", 256, 136),
+        24 => ("I8", 1, 1),
+        25 => ("I16", 1, 2),
+        26 => ("I32", 1, 4),
+        27 => ("I64", 1, 8),
+        28 => ("F64", 1, 8),
+        29 => ("IQ1_M", 256, 56),
+        30 => ("BF16", 1, 2),
+        34 => ("TQ1_0", 256, 54),
+        35 => ("TQ2_0", 256, 66),
+        39 => ("MXFP4", 32, 17),
+        _ => return None,
+    })
+}
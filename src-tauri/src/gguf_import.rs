@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::error::ZamaError;
+use crate::gguf;
+use crate::http::{self, HttpClient};
+use crate::ollama::OLLAMA_SERVICE;
+
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+const HASH_PROGRESS_STEP: u64 = 256 * 1024 * 1024;
+// Imported models run with their trained window, capped so a 128k model doesn't reserve a
+// KV cache bigger than most machines have
+const MAX_IMPORT_NUM_CTX: u64 = 8192;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GgufImportRequest {
+    pub path: String,
+    pub name: String,
+    pub template: Option<String>, // Ollama Go template; falls back to the GGUF's own chat template
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GgufImportResult {
+    pub model: String,
+    pub digest: String,
+    pub size: u64,
+    pub architecture: Option<String>,
+    pub context_length: Option<u64>,
+    pub quantization: Option<String>,
+    pub chat_template: Option<String>,
+    pub modelfile: String, // The Modelfile equivalent of the create request, not sent to Ollama
+    pub uploaded: bool,    // False when Ollama already had the blob
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GgufImportProgress {
+    pub model: String,
+    pub stage: &'static str, // "reading", "hashing", "uploading", "creating" or "done"
+    pub completed: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateModelRequest<'a> {
+    model: &'a str,
+    files: std::collections::HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameters: Option<CreateModelParameters>,
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateModelParameters {
+    num_ctx: u64,
+}
+
+impl CreateModelRequest<'_> {
+    // `/api/create` takes JSON rather than a Modelfile; this is the Modelfile that would create
+    // the same model, with the weights referenced by file name
+    fn modelfile(&self) -> String {
+        let mut modelfile: String = self
+            .files
+            .keys()
+            .map(|file_name| format!("FROM ./{}\n", file_name))
+            .collect();
+        if let Some(template) = self.template {
+            modelfile.push_str(&format!("TEMPLATE \"\"\"{}\"\"\"\n", template));
+        }
+        if let Some(parameters) = &self.parameters {
+            modelfile.push_str(&format!("PARAMETER num_ctx {}\n", parameters.num_ctx));
+        }
+        modelfile
+    }
+}
+
+pub async fn import_gguf<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    request: GgufImportRequest,
+) -> Result<GgufImportResult, ZamaError> {
+    let path = validate_gguf_path(&request.path).map_err(ZamaError::InvalidInput)?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| ZamaError::InvalidInput("GGUF path has no file name".to_string()))?;
+    let size = std::fs::metadata(&path)
+        .map_err(|e| ZamaError::Io(format!("Failed to read GGUF file metadata: {}", e)))?
+        .len();
+
+    let emit = |stage: &'static str, completed: u64| {
+        let progress = GgufImportProgress {
+            model: request.name.clone(),
+            stage,
+            completed,
+            total: size,
+        };
+        if let Err(e) = app_handle.emit("gguf-import-progress", progress) {
+            tracing::error!(error = %e, "Failed to emit gguf-import-progress event");
+        }
+    };
+
+    let requested_template = request.template.as_deref().filter(|t| !t.trim().is_empty());
+    if let Some(template) = requested_template {
+        validate_template(template).map_err(ZamaError::InvalidInput)?;
+    }
+
+    emit("reading", 0);
+    let header_path = path.clone();
+    let header = tokio::task::spawn_blocking(move || gguf::read_header(&header_path))
+        .await?
+        .map_err(ZamaError::Parse)?;
+    match header.architecture() {
+        None => {
+            return Err(ZamaError::InvalidInput(
+                "GGUF file has no general.architecture, so Ollama can't load it".to_string(),
+            ))
+        }
+        Some("clip") => {
+            return Err(ZamaError::InvalidInput(
+                "GGUF file is a vision projector; import the language model it belongs to"
+                    .to_string(),
+            ))
+        }
+        Some(_) => {}
+    }
+    let template = requested_template.or_else(|| embedded_template(&header));
+    let num_ctx = header
+        .context_length()
+        .map(|trained| trained.min(MAX_IMPORT_NUM_CTX));
+
+    emit("hashing", 0);
+    let hash_path = path.clone();
+    let hash_app = app_handle.clone();
+    let hash_model = request.name.clone();
+    let digest = tokio::task::spawn_blocking(move || {
+        sha256_file(&hash_path, |completed| {
+            let progress = GgufImportProgress {
+                model: hash_model.clone(),
+                stage: "hashing",
+                completed,
+                total: size,
+            };
+            let _ = hash_app.emit("gguf-import-progress", progress);
+        })
+    })
+    .await?
+    .map_err(ZamaError::Io)?;
+    let digest = format!("sha256:{}", digest);
+
+    let client = http::client(app_handle);
+    let uploaded = if blob_exists(&client, &digest).await? {
+        false
+    } else {
+        emit("uploading", 0);
+        upload_blob(&client, &path, &digest).await?;
+        // Ollama hashes the upload itself and rejects mismatches, but confirm it landed
+        if !blob_exists(&client, &digest).await? {
+            return Err(ZamaError::Integrity(format!(
+                "Ollama did not store blob {} after upload",
+                digest
+            )));
+        }
+        true
+    };
+
+    emit("creating", size);
+    let create = CreateModelRequest {
+        model: &request.name,
+        files: [(file_name, digest.clone())].into(),
+        template,
+        parameters: num_ctx.map(|num_ctx| CreateModelParameters { num_ctx }),
+        stream: false,
+    };
+    create_model(&client, &create).await?;
+    emit("done", size);
+
+    Ok(GgufImportResult {
+        model: request.name.clone(),
+        modelfile: create.modelfile(),
+        size,
+        architecture: header.architecture().map(str::to_string),
+        context_length: header.context_length(),
+        quantization: header.file_type().map(str::to_string),
+        chat_template: header.chat_template().map(str::to_string),
+        digest,
+        uploaded,
+    })
+}
+
+pub fn validate_gguf_path(path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path.trim());
+    if !path.is_absolute() {
+        return Err("GGUF path must be absolute".to_string());
+    }
+    if !path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"))
+    {
+        return Err("Only .gguf files can be imported".to_string());
+    }
+    if !path.is_file() {
+        return Err(format!("GGUF file not found: {}", path.display()));
+    }
+    Ok(path.to_path_buf())
+}
+
+pub fn sha256_file(path: &Path, mut on_progress: impl FnMut(u64)) -> Result<String, String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open GGUF file: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; HASH_BUFFER_SIZE];
+    let mut completed = 0u64;
+    let mut next_report = HASH_PROGRESS_STEP;
+
+    loop {
+        let read = file
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read GGUF file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+        completed += read as u64;
+        if completed >= next_report {
+            on_progress(completed);
+            next_report += HASH_PROGRESS_STEP;
+        }
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+// Modelfiles quote templates with `"""` and have no escape for it
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if template.contains("\"\"\"") {
+        return Err("Templates can't contain \"\"\"".to_string());
+    }
+    Ok(())
+}
+
+// The GGUF's own chat template, when it is already an Ollama Go template. Jinja templates are
+// left to Ollama, which maps the ones it recognizes from `tokenizer.chat_template` on create.
+fn embedded_template(header: &gguf::GgufHeader) -> Option<&str> {
+    let template = header.chat_template()?;
+    let is_go_template = !template.contains("{%")
+        && [".Prompt", ".Messages", ".System", ".Response"]
+            .iter()
+            .any(|field| template.contains(field));
+    (is_go_template && validate_template(template).is_ok()).then_some(template)
+}
+
+async fn blob_exists(client: &HttpClient, digest: &str) -> Result<bool, ZamaError> {
+    let url = format!("{}/api/blobs/{}", client.endpoints().ollama, digest);
+    let res = client
+        .send_idempotent(client.head(&url))
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+    match res.status() {
+        reqwest::StatusCode::OK => Ok(true),
+        reqwest::StatusCode::NOT_FOUND => Ok(false),
+        _ => Err(ZamaError::from_response(OLLAMA_SERVICE, res, None).await),
+    }
+}
+
+async fn upload_blob(client: &HttpClient, path: &Path, digest: &str) -> Result<(), ZamaError> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| ZamaError::Io(format!("Failed to open GGUF file: {}", e)))?;
+    let url = format!("{}/api/blobs/{}", client.endpoints().ollama, digest);
+    let res = client
+        .post(&url)
+        .body(reqwest::Body::from(file))
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, None).await);
+    }
+    Ok(())
+}
+
+async fn create_model(
+    client: &HttpClient,
+    request: &CreateModelRequest<'_>,
+) -> Result<(), ZamaError> {
+    let url = format!("{}/api/create", client.endpoints().ollama);
+    let res = client
+        .post(&url)
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, None).await);
+    }
+    Ok(())
+}
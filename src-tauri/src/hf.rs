@@ -0,0 +1,480 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::ZamaError;
+use crate::http::HttpClient;
+use crate::quant::{estimate_runtime_bytes, QuantType};
+
+pub const HF_BASE_URL: &str = "https://huggingface.co";
+const HF_SERVICE: &str = "Hugging Face";
+
+const DEFAULT_SEARCH: &str = "GGUF";
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 1000;
+
+// --- Hugging Face Client Schemas ---
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HfModel {
+    pub model_id: String,
+    pub author: Option<String>,
+    pub sha: Option<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub gated: HfGated,
+    #[serde(rename = "pipeline_tag")]
+    pub pipeline_tag: Option<String>,
+    #[serde(rename = "library_name")]
+    pub library_name: Option<String>,
+    pub downloads: Option<u64>,
+    pub likes: Option<u64>,
+    pub last_modified: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub siblings: Vec<HfSibling>,
+    pub gguf: Option<HfGgufSummary>,
+    pub safetensors: Option<HfSafetensorsSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfSibling {
+    pub rfilename: String,
+    pub size: Option<u64>,
+}
+
+// HF reports `gated` as `false`, `"auto"` or `"manual"`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum HfGated {
+    Flag(bool),
+    Mode(String),
+}
+
+impl Default for HfGated {
+    fn default() -> Self {
+        HfGated::Flag(false)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfGgufSummary {
+    pub total: Option<u64>,
+    pub architecture: Option<String>,
+    pub context_length: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfSafetensorsSummary {
+    pub total: Option<u64>,
+}
+
+impl HfModel {
+    // Parameter count as reported by the GGUF or safetensors metadata, if HF computed one
+    pub fn parameter_count(&self) -> Option<u64> {
+        self.gguf
+            .as_ref()
+            .and_then(|g| g.total)
+            .or_else(|| self.safetensors.as_ref().and_then(|s| s.total))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HfSort {
+    #[default]
+    Downloads,
+    Likes,
+    LastModified,
+    CreatedAt,
+    TrendingScore,
+}
+
+impl HfSort {
+    fn as_query(&self) -> &'static str {
+        match self {
+            HfSort::Downloads => "downloads",
+            HfSort::Likes => "likes",
+            HfSort::LastModified => "lastModified",
+            HfSort::CreatedAt => "createdAt",
+            HfSort::TrendingScore => "trendingScore",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HfSearchParams {
+    pub search: Option<String>,
+    pub author: Option<String>,
+    pub library: Option<String>, // Eg. "gguf"
+    pub license: Option<String>, // Eg. "apache-2.0"
+    pub pipeline_tag: Option<String>,
+    pub tags: Vec<String>,
+    pub min_params: Option<String>, // Eg. "1B", "500M"
+    pub max_params: Option<String>,
+    pub sort: HfSort,
+    pub ascending: bool,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+impl HfSearchParams {
+    // The frontend only searches GGUF text-generation models, so those stay the defaults
+    pub fn with_defaults(mut self) -> Self {
+        if self.search.as_deref().is_none_or(|s| s.trim().is_empty()) {
+            self.search = Some(DEFAULT_SEARCH.to_string());
+        }
+        if self.pipeline_tag.is_none() {
+            self.pipeline_tag = Some("text-generation".to_string());
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(limit) = self.limit {
+            if limit == 0 || limit > MAX_PAGE_SIZE {
+                return Err(format!("Limit must be between 1 and {}", MAX_PAGE_SIZE));
+            }
+        }
+        for size in [&self.min_params, &self.max_params].into_iter().flatten() {
+            parse_parameter_count(size)
+                .ok_or_else(|| format!("Invalid parameter size '{}'", size))?;
+        }
+        Ok(())
+    }
+
+    // Query pairs for `/api/models`; reqwest takes care of percent-encoding every value
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(search) = non_empty(&self.search) {
+            pairs.push(("search", search.to_string()));
+        }
+        if let Some(author) = non_empty(&self.author) {
+            pairs.push(("author", author.to_string()));
+        }
+        if let Some(pipeline_tag) = non_empty(&self.pipeline_tag) {
+            pairs.push(("pipeline_tag", pipeline_tag.to_string()));
+        }
+        if let Some(library) = non_empty(&self.library) {
+            pairs.push(("filter", library.to_lowercase()));
+        }
+        if let Some(license) = non_empty(&self.license) {
+            pairs.push(("filter", format!("license:{}", license.to_lowercase())));
+        }
+        for tag in self.tags.iter().filter(|t| !t.trim().is_empty()) {
+            pairs.push(("filter", tag.trim().to_string()));
+        }
+        if self.min_params.is_some() || self.max_params.is_some() {
+            pairs.push((
+                "num_parameters",
+                format!(
+                    "min:{},max:{}",
+                    self.min_params.as_deref().unwrap_or("0"),
+                    self.max_params.as_deref().unwrap_or("1000T"),
+                ),
+            ));
+        }
+        pairs.push(("sort", self.sort.as_query().to_string()));
+        pairs.push((
+            "direction",
+            if self.ascending { "1" } else { "-1" }.to_string(),
+        ));
+        pairs.push(("limit", self.limit.unwrap_or(DEFAULT_PAGE_SIZE).to_string()));
+        pairs.push(("full", "true".to_string()));
+        if let Some(cursor) = non_empty(&self.cursor) {
+            pairs.push(("cursor", cursor.to_string()));
+        }
+        pairs
+    }
+
+    // Cache key that covers every parameter affecting the response
+    pub fn cache_key(&self) -> String {
+        let query = self
+            .query_pairs()
+            .into_iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("hf:{}", query)
+    }
+}
+
+// One page of search results plus the cursor for the next one, if any
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HfSearchResult {
+    pub models: Vec<HfModel>,
+    pub next_cursor: Option<String>,
+}
+
+pub async fn search_models(
+    client: &HttpClient,
+    params: HfSearchParams,
+) -> Result<HfSearchResult, ZamaError> {
+    let url = format!("{}/api/models", client.endpoints().hugging_face);
+
+    let res = client
+        .send_idempotent(client.get(&url).query(&params.query_pairs()))
+        .await
+        .map_err(|e| ZamaError::request(HF_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(HF_SERVICE, res, None).await);
+    }
+
+    let next_cursor = res
+        .headers()
+        .get(reqwest::header::LINK)
+        .and_then(|v| v.to_str().ok())
+        .and_then(next_link)
+        .and_then(|link| cursor_from_url(&link));
+
+    let mut models = res.json::<Vec<HfModel>>().await.map_err(|e| {
+        ZamaError::Parse(format!(
+            "Failed to parse Hugging Face models response: {}",
+            e
+        ))
+    })?;
+
+    // `num_parameters` is only honoured for repos HF has analysed, so enforce the range locally too
+    let min = params.min_params.as_deref().and_then(parse_parameter_count);
+    let max = params.max_params.as_deref().and_then(parse_parameter_count);
+    if min.is_some() || max.is_some() {
+        models.retain(|m| match m.parameter_count() {
+            Some(count) => min.is_none_or(|min| count >= min) && max.is_none_or(|max| count <= max),
+            None => true,
+        });
+    }
+
+    Ok(HfSearchResult {
+        models,
+        next_cursor,
+    })
+}
+
+// Extracts the `rel="next"` target from an RFC 8288 `Link` header
+pub fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let mut segments = part.split(';');
+        let target = segments.next()?.trim();
+        let is_next = segments.any(|param| {
+            let param = param.trim().replace(' ', "");
+            param == "rel=\"next\"" || param == "rel=next"
+        });
+        if !is_next {
+            return None;
+        }
+        target
+            .strip_prefix('<')
+            .and_then(|t| t.strip_suffix('>'))
+            .map(|t| t.to_string())
+    })
+}
+
+fn cursor_from_url(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    parsed
+        .query_pairs()
+        .find(|(k, _)| k == "cursor")
+        .map(|(_, v)| v.into_owned())
+}
+
+// Parses sizes such as "7B", "8.0B", "500M", "1.5t" or a plain number of parameters
+pub fn parse_parameter_count(input: &str) -> Option<u64> {
+    let trimmed = input.trim();
+    let (number, multiplier) = match trimmed.chars().last()?.to_ascii_uppercase() {
+        'K' => (&trimmed[..trimmed.len() - 1], 1e3),
+        'M' => (&trimmed[..trimmed.len() - 1], 1e6),
+        'B' => (&trimmed[..trimmed.len() - 1], 1e9),
+        'T' => (&trimmed[..trimmed.len() - 1], 1e12),
+        _ => (trimmed, 1.0),
+    };
+    let value: f64 = number.trim().parse().ok()?;
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+    Some((value * multiplier).round() as u64)
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+// --- GGUF File Listing ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfTreeEntry {
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub path: String,
+    #[serde(default)]
+    pub size: u64,
+    pub lfs: Option<HfLfsInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfLfsInfo {
+    pub oid: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfGgufFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: Option<String>,
+    pub quant: Option<QuantType>,
+    pub shard: Option<(u32, u32)>, // (index, total) for split files
+    pub is_projector: bool,        // Vision projectors (mmproj) aren't runnable on their own
+}
+
+// All files making up one quantization, with what it should take to run it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfGgufQuantization {
+    pub quant: String,
+    pub bits_per_weight: Option<f64>,
+    pub files: Vec<String>,
+    pub total_size: u64,
+    pub estimated_memory_bytes: u64,
+    pub pull_name: Option<String>, // What to hand to `pull_model`; none when the quant is unknown
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HfGgufListing {
+    pub repo: String,
+    pub files: Vec<HfGgufFile>,
+    pub quantizations: Vec<HfGgufQuantization>,
+}
+
+pub async fn list_gguf_files(client: &HttpClient, repo: &str) -> Result<HfGgufListing, ZamaError> {
+    let repo = normalize_repo_id(repo).map_err(ZamaError::InvalidInput)?;
+    let mut url = format!(
+        "{}/api/models/{}/tree/main?recursive=true",
+        client.endpoints().hugging_face,
+        repo
+    );
+    let mut entries = Vec::new();
+
+    // The tree endpoint is paginated through the same `Link` header as model search
+    loop {
+        let res = client
+            .send_idempotent(client.get(&url))
+            .await
+            .map_err(|e| ZamaError::request(HF_SERVICE, e))?;
+
+        if !res.status().is_success() {
+            return Err(ZamaError::from_response(HF_SERVICE, res, None).await);
+        }
+
+        let next = res
+            .headers()
+            .get(reqwest::header::LINK)
+            .and_then(|v| v.to_str().ok())
+            .and_then(next_link);
+
+        let page = res.json::<Vec<HfTreeEntry>>().await.map_err(|e| {
+            ZamaError::Parse(format!("Failed to parse Hugging Face tree response: {}", e))
+        })?;
+        entries.extend(page);
+
+        match next {
+            Some(next) => url = next,
+            None => break,
+        }
+    }
+
+    Ok(build_gguf_listing(&repo, entries))
+}
+
+pub fn build_gguf_listing(repo: &str, entries: Vec<HfTreeEntry>) -> HfGgufListing {
+    let files: Vec<HfGgufFile> = entries
+        .into_iter()
+        .filter(|e| e.entry_type == "file" && e.path.to_lowercase().ends_with(".gguf"))
+        .map(|e| {
+            let filename = e.path.rsplit('/').next().unwrap_or(&e.path).to_string();
+            HfGgufFile {
+                size: e.lfs.as_ref().map(|l| l.size).unwrap_or(e.size),
+                sha256: e.lfs.map(|l| l.oid),
+                quant: QuantType::from_filename(&filename),
+                shard: parse_shard(&filename),
+                is_projector: filename.to_lowercase().contains("mmproj"),
+                path: e.path,
+            }
+        })
+        .collect();
+
+    let mut quantizations: Vec<HfGgufQuantization> = Vec::new();
+    for file in files.iter().filter(|f| !f.is_projector) {
+        let quant_name = file
+            .quant
+            .as_ref()
+            .map(|q| q.name.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        match quantizations.iter_mut().find(|q| q.quant == quant_name) {
+            Some(existing) => {
+                existing.files.push(file.path.clone());
+                existing.total_size += file.size;
+            }
+            None => quantizations.push(HfGgufQuantization {
+                // Ollama pulls by quant tag, so files without one can't be pulled by name
+                pull_name: file
+                    .quant
+                    .as_ref()
+                    .map(|_| format!("hf.co/{}:{}", repo, quant_name)),
+                quant: quant_name,
+                bits_per_weight: file.quant.as_ref().map(|q| q.bits_per_weight),
+                files: vec![file.path.clone()],
+                total_size: file.size,
+                estimated_memory_bytes: 0,
+            }),
+        }
+    }
+    for quantization in quantizations.iter_mut() {
+        quantization.files.sort();
+        quantization.estimated_memory_bytes = estimate_runtime_bytes(quantization.total_size);
+    }
+    quantizations.sort_by_key(|q| q.total_size);
+
+    HfGgufListing {
+        repo: repo.to_string(),
+        files,
+        quantizations,
+    }
+}
+
+// Accepts `user/repo`, `hf.co/user/repo` or a full huggingface.co URL, with an optional `:tag`
+pub fn normalize_repo_id(input: &str) -> Result<String, String> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let without_host = ["https://huggingface.co/", "huggingface.co/", "hf.co/"]
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))
+        .unwrap_or(trimmed);
+    let repo = without_host.split(':').next().unwrap_or(without_host);
+
+    let parts: Vec<&str> = repo.split('/').collect();
+    let valid_part = |p: &&str| {
+        !p.is_empty()
+            && p.len() <= 96
+            && !p.starts_with('.')
+            && p.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    if parts.len() != 2 || !parts.iter().all(valid_part) {
+        return Err(format!(
+            "Invalid Hugging Face repository '{}', expected 'user/repo'",
+            input
+        ));
+    }
+    Ok(repo.to_string())
+}
+
+// Split GGUFs are named `<name>-00001-of-00003.gguf`
+fn parse_shard(filename: &str) -> Option<(u32, u32)> {
+    let stem = filename.strip_suffix(".gguf").unwrap_or(filename);
+    let (rest, total) = stem.rsplit_once("-of-")?;
+    let (_, index) = rest.rsplit_once('-')?;
+    Some((index.parse().ok()?, total.parse().ok()?))
+}
@@ -0,0 +1,326 @@
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::app_info;
+use crate::error::ZamaError;
+use crate::paths::{self, AppPaths};
+use crate::{hf, ollama, GEMINI_BASE_URL, OLLAMADB_BASE_URL};
+
+const SETTINGS_FILE: &str = "network.json";
+
+// Retries start at 250ms and double, capped so a flapping server doesn't stall the UI for long
+const RETRY_BASE_DELAY_MS: u64 = 250;
+const RETRY_MAX_DELAY_MS: u64 = 8_000;
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub connect_timeout_secs: u64,
+    pub read_timeout_secs: u64, // Longest gap between bytes; streaming chats count per chunk
+    pub max_retries: u32,       // Only idempotent requests are retried
+    pub proxy_url: Option<String>, // HTTP(S) proxy for all traffic; system proxy env vars apply otherwise
+    pub no_proxy: Option<String>,  // Comma-separated hosts that bypass the proxy
+    pub ca_cert_path: Option<String>, // PEM bundle trusted in addition to the system roots
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 10,
+            read_timeout_secs: 300, // Loading a large model before the first token can take minutes
+            max_retries: 3,
+            proxy_url: None,
+            no_proxy: Some("localhost,127.0.0.1,::1".to_string()),
+            ca_cert_path: None,
+        }
+    }
+}
+
+impl NetworkSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.connect_timeout_secs == 0 || self.connect_timeout_secs > 300 {
+            return Err("Connect timeout must be between 1 and 300 seconds".to_string());
+        }
+        if self.read_timeout_secs == 0 || self.read_timeout_secs > 3600 {
+            return Err("Read timeout must be between 1 and 3600 seconds".to_string());
+        }
+        if self.max_retries > 10 {
+            return Err("At most 10 retries are allowed".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Base URLs of the services Zama talks to, without a trailing slash. Only tests point these
+// anywhere else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoints {
+    pub ollama: String,
+    pub ollamadb: String,
+    pub hugging_face: String,
+    pub gemini: String, // Up to the API version, eg. `.../v1beta`
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            ollama: ollama::OLLAMA_BASE_URL.to_string(),
+            ollamadb: OLLAMADB_BASE_URL.to_string(),
+            hugging_face: hf::HF_BASE_URL.to_string(),
+            gemini: GEMINI_BASE_URL.to_string(),
+        }
+    }
+}
+
+// Cheap to clone; every clone shares the same connection pool
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    max_retries: u32,
+    endpoints: Arc<Endpoints>,
+}
+
+impl HttpClient {
+    pub fn new(settings: &NetworkSettings) -> Result<Self, String> {
+        Ok(Self {
+            client: build_client(settings)?,
+            max_retries: settings.max_retries,
+            endpoints: Arc::default(),
+        })
+    }
+
+    pub fn with_endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = Arc::new(endpoints);
+        self
+    }
+
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
+    pub fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.post(url)
+    }
+
+    pub fn head(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.head(url)
+    }
+
+    pub fn delete(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client.delete(url)
+    }
+
+    // Sends a request that is safe to repeat, retrying connection failures, timeouts and
+    // 408/429/5xx responses with exponential backoff. Streaming bodies can't be cloned and are
+    // sent once.
+    pub async fn send_idempotent(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let mut attempt = 0;
+        loop {
+            let Some(next) = request.try_clone().filter(|_| attempt < self.max_retries) else {
+                return request.send().await;
+            };
+            match next.send().await {
+                Ok(res) if retryable_status(res.status()) => {
+                    let delay = retry_after(&res).unwrap_or_else(|| backoff(attempt));
+                    tracing::debug!(status = %res.status(), attempt, ?delay, "Retrying request");
+                    tokio::time::sleep(delay).await;
+                }
+                Ok(res) => return Ok(res),
+                Err(e) if e.is_connect() || e.is_timeout() => {
+                    let delay = backoff(attempt);
+                    tracing::debug!(error = %e, attempt, ?delay, "Retrying request");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+            attempt += 1;
+        }
+    }
+}
+
+fn retryable_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 502 | 503 | 504)
+}
+
+fn retry_after(res: &reqwest::Response) -> Option<Duration> {
+    res.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(|secs| Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)))
+}
+
+// Jittered so concurrent callers don't retry in lockstep
+fn backoff(attempt: u32) -> Duration {
+    let ceiling = RETRY_BASE_DELAY_MS
+        .saturating_mul(1 << attempt.min(16))
+        .min(RETRY_MAX_DELAY_MS);
+    let jitter = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or_default();
+    Duration::from_millis(ceiling / 2 + jitter % (ceiling / 2 + 1))
+}
+
+// Calls `f` for every non-empty line of a streamed body, eg. Ollama's NDJSON or SSE. Lines can
+// span network chunks, so they are buffered until complete.
+pub async fn for_each_line(
+    service: &str,
+    res: reqwest::Response,
+    mut f: impl FnMut(&str) -> Result<(), ZamaError>,
+) -> Result<(), ZamaError> {
+    let mut stream = res.bytes_stream();
+    let mut buffer = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        buffer.extend_from_slice(&chunk.map_err(|e| ZamaError::request(service, e))?);
+        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            if !line.trim().is_empty() {
+                f(line.trim())?;
+            }
+        }
+    }
+    let rest = String::from_utf8_lossy(&buffer);
+    if !rest.trim().is_empty() {
+        f(rest.trim())?;
+    }
+    Ok(())
+}
+
+pub fn user_agent() -> String {
+    format!(
+        "Zama/{} ({}; {})",
+        app_info::VERSION,
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+pub fn build_client(settings: &NetworkSettings) -> Result<reqwest::Client, String> {
+    settings.validate()?;
+    let mut builder = reqwest::Client::builder()
+        .user_agent(user_agent())
+        .connect_timeout(Duration::from_secs(settings.connect_timeout_secs))
+        .read_timeout(Duration::from_secs(settings.read_timeout_secs));
+
+    if let Some(url) = settings
+        .proxy_url
+        .as_deref()
+        .filter(|u| !u.trim().is_empty())
+    {
+        let proxy = reqwest::Proxy::all(url.trim())
+            .map_err(|e| format!("Invalid proxy URL '{}': {}", url, e))?
+            .no_proxy(
+                settings
+                    .no_proxy
+                    .as_deref()
+                    .and_then(reqwest::NoProxy::from_string),
+            );
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = settings
+        .ca_cert_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        let pem = fs::read(path.trim())
+            .map_err(|e| format!("Failed to read CA certificate '{}': {}", path, e))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| format!("Invalid CA certificate '{}': {}", path, e))?;
+        if certs.is_empty() {
+            return Err(format!("No certificates found in '{}'", path));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+// --- State ---
+
+// Managed by Tauri; the client is rebuilt whenever the network settings change
+pub struct HttpState {
+    client: RwLock<HttpClient>,
+}
+
+impl HttpState {
+    pub fn new(client: HttpClient) -> Self {
+        Self {
+            client: RwLock::new(client),
+        }
+    }
+
+    pub fn client(&self) -> HttpClient {
+        self.client.read().unwrap().clone()
+    }
+
+    fn replace(&self, client: HttpClient) {
+        *self.client.write().unwrap() = client;
+    }
+}
+
+// Falls back to the defaults if the saved settings no longer produce a working client, eg. a
+// CA bundle that has since been deleted
+pub fn init<R: Runtime>(app_handle: &AppHandle<R>) -> HttpState {
+    let settings = load_settings(app_handle);
+    let client = HttpClient::new(&settings).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Ignoring saved network settings");
+        HttpClient::new(&NetworkSettings::default()).expect("default HTTP client builds")
+    });
+    HttpState::new(client)
+}
+
+pub fn client<R: Runtime>(app_handle: &AppHandle<R>) -> HttpClient {
+    app_handle.state::<HttpState>().client()
+}
+
+pub fn load_settings(paths: &impl AppPaths) -> NetworkSettings {
+    paths::load_settings(paths, SETTINGS_FILE)
+}
+
+fn save_settings(paths: &impl AppPaths, settings: &NetworkSettings) -> Result<(), ZamaError> {
+    paths::save_settings(paths, SETTINGS_FILE, settings)
+}
+
+#[tauri::command]
+pub fn get_network_settings<R: Runtime>(app_handle: AppHandle<R>) -> NetworkSettings {
+    load_settings(&app_handle)
+}
+
+#[tauri::command]
+pub fn set_network_settings<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: tauri::State<'_, HttpState>,
+    settings: NetworkSettings,
+) -> Result<NetworkSettings, ZamaError> {
+    // Build first so invalid settings are rejected without touching the running client
+    let client = HttpClient::new(&settings)
+        .map_err(ZamaError::InvalidInput)?
+        .with_endpoints(state.client().endpoints().clone());
+    save_settings(&app_handle, &settings)?;
+    state.replace(client);
+    tracing::info!(
+        proxy = settings.proxy_url.is_some(),
+        custom_ca = settings.ca_cert_path.is_some(),
+        "Network settings updated"
+    );
+    Ok(settings)
+}
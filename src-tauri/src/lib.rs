@@ -1,104 +1,57 @@
-use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use tauri::Emitter;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command as TokioCommand;
-use tokio::time::{timeout, Duration};
-
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+mod app_info;
+mod batch;
+mod catalog;
+pub mod cli;
+mod compare;
+mod context;
+mod conversations;
+mod credentials;
+mod error;
+mod evals;
+mod gguf;
+mod gguf_import;
+mod hf;
+mod http;
+mod json_schema;
+mod logging;
+mod memory;
+pub mod ollama;
+pub mod paths;
+mod presets;
+mod prompts;
+mod quant;
+mod server;
+mod stats;
+mod thinking;
+mod titles;
 mod updater;
 
-const OLLAMA_BASE_URL: &str = "http://localhost:11434";
-const HF_BASE_URL: &str = "https://huggingface.co";
-
-// --- Ollama Client Schemas ---
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Message {
-    pub role: String,
-    pub content: String,
-}
-
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct ChatRequest {
-    pub model: String,
-    pub messages: Vec<Message>,
-    pub stream: bool,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ChatResponse {
-    pub model: String,
-    pub created_at: String,
-    pub message: Option<Message>, // Message might be null for done: true responses
-    pub done: bool,
-    // Add other optional fields if needed, eg., total_duration, load_duration
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PullModelRequest {
-    pub name: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DeleteModelRequest {
-    pub name: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ShowModelRequest {
-    pub name: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ModelDetails {
-    pub parent_model: Option<String>,
-    pub format: String,
-    pub family: String,
-    pub families: Option<Vec<String>>,
-    pub parameter_size: String,
-    pub quantization_level: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ShowModelResponse {
-    pub model: String,
-    pub license: String,
-    pub modelfile: String,
-    pub parameters: Vec<String>,
-    pub template: String,
-    pub details: ModelDetails,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ListModelEntry {
-    pub name: String,
-    pub modified_at: String,
-    pub size: u64,
-    pub digest: String,
-    pub details: ModelDetails,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ListModelsResponse {
-    pub models: Vec<ListModelEntry>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ConfigResponse {
-    pub ollama_url: String, // Assuming this is the only field for now
-}
-
-// --- Hugging Face Client Schemas ---
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct HfModel {
-    pub model_id: String,
-    pub pipeline_tag: Option<String>,
-    pub downloads: Option<u64>,
-    pub last_modified: Option<String>,
-    // Add other fields as needed
-}
+#[cfg(test)]
+mod tests;
+
+use app_info::AppInfo;
+use catalog::{CatalogFilter, CatalogPage, OllamaDbModel, OllamaDbResponse};
+use error::ZamaError;
+use gguf::GgufInspection;
+use gguf_import::{GgufImportRequest, GgufImportResult};
+use hf::{HfGgufListing, HfModel, HfSearchParams};
+use http::{HttpClient, HttpState};
+use memory::{EstimateInputs, MemoryEstimate, MemoryEstimateRequest, ModelShape};
+use ollama::{
+    fetch_model_info, validate_model_name, ChatEvent, ChatRequest, ConfigResponse,
+    DeleteModelRequest, ListModelsResponse, ShowModelRequest, ShowModelResponse,
+};
+use thinking::ThinkingChunk;
+
+const OLLAMADB_BASE_URL: &str = "https://ollamadb.dev";
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+const OLLAMADB_SERVICE: &str = "ollamadb.dev";
+const GEMINI_SERVICE: &str = "Gemini";
+
+const OLLAMADB_CATALOG_KEY: &str = "ollamadb";
 
 // --- Google Gemini Schemas ---
 
@@ -131,60 +84,93 @@ pub struct GeminiChatResponse {
 // --- End of Schemas ---
 
 #[tauri::command]
-async fn send_gemini_chat(
+async fn send_gemini_chat<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
     api_key: String,
     model_name: String,
     messages: Vec<GeminiContent>,
-) -> Result<String, String> {
-    let client = reqwest::Client::new();
+) -> Result<String, ZamaError> {
+    // An empty key falls back to the one stored for the local API server
+    let api_key = Some(api_key.trim().to_string())
+        .filter(|key| !key.is_empty())
+        .or_else(|| {
+            credentials::load(&app_handle)
+                .api_key(credentials::Provider::Gemini)
+                .map(str::to_string)
+        })
+        .ok_or_else(|| ZamaError::ProviderAuth {
+            provider: GEMINI_SERVICE.to_string(),
+            message: "No Gemini API key configured".to_string(),
+        })?;
+    gemini_chat(&http.client(), &api_key, &model_name, messages).await
+}
+
+async fn gemini_chat(
+    client: &HttpClient,
+    api_key: &str,
+    model_name: &str,
+    messages: Vec<GeminiContent>,
+) -> Result<String, ZamaError> {
     let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model_name, api_key
+        "{}/models/{}:generateContent",
+        client.endpoints().gemini,
+        model_name
     );
 
     let request_body = GeminiChatRequest { contents: messages };
 
     let res = client
         .post(&url)
+        .header("x-goog-api-key", api_key) // Keeps the key out of URLs and logs
         .json(&request_body)
         .send()
         .await
-        .map_err(|e| format!("Failed to send request to Gemini: {}", e))?;
+        .map_err(|e| ZamaError::request(GEMINI_SERVICE, e))?;
 
     let status = res.status();
+    let retry_after = res
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok());
     let raw_response_text = res
         .text()
         .await
-        .map_err(|e| format!("Failed to read Gemini raw response text: {}", e))?;
-    println!("Raw Gemini API Response: {}", raw_response_text);
+        .map_err(|e| ZamaError::request(GEMINI_SERVICE, e))?;
+    tracing::debug!(
+        model = %model_name,
+        %status,
+        bytes = raw_response_text.len(),
+        "Gemini response received"
+    );
 
     if !status.is_success() {
-        return Err(format!(
-            "Gemini API returned non-success status: {} - {}",
-            status, raw_response_text
-        ));
+        return Err(ZamaError::status(
+            GEMINI_SERVICE,
+            status,
+            raw_response_text,
+            retry_after,
+            None,
+        )
+        .gemini_auth());
     }
 
-    let gemini_response: GeminiChatResponse =
-        serde_json::from_str(&raw_response_text).map_err(|e| {
-            format!(
-                "Failed to parse Gemini response: {} - Raw: {}",
-                e, raw_response_text
-            )
-        })?;
+    let gemini_response: GeminiChatResponse = serde_json::from_str(&raw_response_text)
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse Gemini response: {}", e)))?;
 
     // Extract the text from the first candidate's first part
     let response_text = gemini_response
         .candidates
-        .get(0)
-        .and_then(|c| c.content.parts.get(0))
+        .first()
+        .and_then(|c| c.content.parts.first())
         .map(|p| p.text.clone())
         .unwrap_or_else(|| {
-            println!("Gemini response candidates or parts are empty.");
+            tracing::warn!(model = %model_name, "Gemini response candidates or parts are empty");
             "No response from Gemini.".to_string()
         });
 
-    println!("Extracted Gemini Response Text: {}", response_text);
+    tracing::debug!(response = %logging::content(&response_text), "Gemini response text");
 
     Ok(response_text)
 }
@@ -192,365 +178,261 @@ async fn send_gemini_chat(
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
 #[tauri::command]
-async fn list_ollama_models() -> Result<ListModelsResponse, String> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/tags", OLLAMA_BASE_URL);
-    let res = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
-
-    if !res.status().is_success() {
-        let status = res.status();
-        let error_text = res
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!(
-            "Ollama API returned non-success status: {} - {}",
-            status, error_text
-        ));
-    }
-
-    res.json::<ListModelsResponse>()
-        .await
-        .map_err(|e| format!("Failed to parse Ollama models response: {}", e))
+async fn list_ollama_models(
+    http: tauri::State<'_, HttpState>,
+) -> Result<ListModelsResponse, ZamaError> {
+    ollama::list_models(&http.client()).await
 }
 
 #[tauri::command]
-async fn delete_ollama_model(request: DeleteModelRequest) -> Result<String, String> {
-    validate_model_name(&request.name)?;
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/delete", OLLAMA_BASE_URL);
-    let res = client
-        .delete(&url)
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
-
-    if !res.status().is_success() {
-        let status = res.status();
-        let error_text = res
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!(
-            "Ollama API returned non-success status: {} - {}",
-            status, error_text
-        ));
-    }
-
+async fn delete_ollama_model(
+    http: tauri::State<'_, HttpState>,
+    request: DeleteModelRequest,
+) -> Result<String, ZamaError> {
+    ollama::delete_model(&http.client(), &request).await?;
     Ok("Model deleted successfully".to_string())
 }
 
 #[tauri::command]
-async fn show_ollama_model(request: ShowModelRequest) -> Result<ShowModelResponse, String> {
-    validate_model_name(&request.name)?;
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/show", OLLAMA_BASE_URL);
-    let res = client
-        .post(&url)
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
-
-    if !res.status().is_success() {
-        let status = res.status();
-        let error_text = res
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!(
-            "Ollama API returned non-success status: {} - {}",
-            status, error_text
-        ));
-    }
-
-    res.json::<ShowModelResponse>()
-        .await
-        .map_err(|e| format!("Failed to parse Ollama show model response: {}", e))
+async fn show_ollama_model(
+    http: tauri::State<'_, HttpState>,
+    request: ShowModelRequest,
+) -> Result<ShowModelResponse, ZamaError> {
+    fetch_model_info(&http.client(), request).await
 }
 
 #[tauri::command]
-async fn get_ollama_config() -> Result<ConfigResponse, String> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/config", OLLAMA_BASE_URL);
-    let res = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request to Ollama: {}", e))?;
-
-    if !res.status().is_success() {
-        let status = res.status();
-        let error_text = res
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!(
-            "Ollama API returned non-success status: {} - {}",
-            status, error_text
-        ));
-    }
-
-    res.json::<ConfigResponse>()
-        .await
-        .map_err(|e| format!("Failed to parse Ollama config response: {}", e))
+async fn get_ollama_config(http: tauri::State<'_, HttpState>) -> Result<ConfigResponse, ZamaError> {
+    ollama::get_config(&http.client()).await
 }
 
 #[tauri::command]
-async fn chat_ollama(app_handle: tauri::AppHandle, request: ChatRequest) -> Result<(), String> {
-    validate_model_name(&request.model)?;
-    if request.messages.is_empty() {
-        return Err("Chat request must include at least one message".to_string());
-    }
-
-    let client = reqwest::Client::new();
-    let url = format!("{}/api/chat", OLLAMA_BASE_URL);
-
-    let mut stream_request = request.clone();
-    stream_request.stream = true; // Ensure streaming is enabled for the API call
-
-    let res = client
-        .post(&url)
-        .json(&stream_request)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send chat request to Ollama: {}", e))?;
+async fn chat_ollama<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    request: ChatRequest,
+) -> Result<(), ZamaError> {
+    ollama::stream_chat(
+        &app_handle,
+        &app_handle.state(),
+        &http.client(),
+        &request,
+        |event| {
+            match event {
+                ChatEvent::Context(report) => app_handle.emit("ollama-chat-context", report)?,
+                ChatEvent::Thinking(content) => app_handle.emit(
+                    "ollama-chat-thinking",
+                    ThinkingChunk {
+                        model: request.model.clone(),
+                        content,
+                    },
+                )?,
+                // Emit each chat response chunk as a Tauri event
+                ChatEvent::Chunk(chunk) => app_handle.emit("ollama-chat-chunk", chunk)?,
+            }
+            Ok(())
+        },
+    )
+    .await
+}
 
-    if !res.status().is_success() {
-        let status = res.status();
-        let error_text = res
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!(
-            "Ollama API returned non-success status: {} - {}",
-            status, error_text
-        ));
+#[tauri::command]
+async fn list_hf_models<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    search: Option<String>,
+    params: Option<HfSearchParams>,
+    filter: Option<CatalogFilter>,
+    refresh: Option<bool>,
+) -> Result<CatalogPage<HfModel>, ZamaError> {
+    let mut params = params.unwrap_or_default();
+    if search.is_some() {
+        params.search = search;
     }
+    let params = params.with_defaults();
+    params.validate().map_err(ZamaError::InvalidInput)?;
 
-    let mut stream = res.bytes_stream();
-
-    while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result.map_err(|e| format!("Error reading stream: {}", e))?;
-        let chunk_str = String::from_utf8_lossy(&chunk);
+    let key = params.cache_key();
+    let client = http.client();
+    let (cached, from_cache) = catalog::load(&app_handle, &key, refresh.unwrap_or(false), {
+        move || async move { hf::search_models(&client, params).await }
+    })
+    .await?;
 
-        for line in chunk_str.lines() {
-            if line.trim().is_empty() {
-                continue;
-            }
-            match serde_json::from_str::<ChatResponse>(line) {
-                Ok(chat_response) => {
-                    // Emit each chat response chunk as a Tauri event
-                    app_handle
-                        .emit("ollama-chat-chunk", chat_response)
-                        .map_err(|e| format!("Failed to emit event: {}", e))?;
-                }
-                Err(e) => {
-                    eprintln!("Failed to parse chat response chunk: {} - {}", e, line);
-                    // Optionally, emit an error event or handle it differently
-                }
-            }
-        }
-    }
+    let models = catalog::filter_hf_models(&cached.data.models, &filter.unwrap_or_default());
+    let mut page = catalog::page(&cached, from_cache, cached.data.models.len(), models);
+    page.next_cursor = cached.data.next_cursor.clone();
+    Ok(page)
+}
 
-    Ok(())
+#[tauri::command]
+async fn list_hf_gguf_files(
+    http: tauri::State<'_, HttpState>,
+    repo: String,
+) -> Result<HfGgufListing, ZamaError> {
+    hf::list_gguf_files(&http.client(), &repo).await
 }
 
 #[tauri::command]
-async fn list_hf_models(search: Option<String>) -> Result<Vec<HfModel>, String> {
-    let client = reqwest::Client::new();
-    let search_query = search.unwrap_or_else(|| "GGUF".to_string());
+async fn get_ollama_models<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    filter: Option<CatalogFilter>,
+    refresh: Option<bool>,
+) -> Result<CatalogPage<OllamaDbModel>, ZamaError> {
+    let (cached, from_cache) = catalog::load(
+        &app_handle,
+        OLLAMADB_CATALOG_KEY,
+        refresh.unwrap_or(false),
+        {
+            let client = http.client();
+            move || async move { fetch_ollamadb_models(&client).await }
+        },
+    )
+    .await?;
+
+    let models = catalog::filter_ollama_models(&cached.data, &filter.unwrap_or_default());
+    Ok(catalog::page(
+        &cached,
+        from_cache,
+        cached.data.len(),
+        models,
+    ))
+}
+
+async fn fetch_ollamadb_models(client: &HttpClient) -> Result<Vec<OllamaDbModel>, ZamaError> {
+    tracing::debug!("Fetching models from ollamadb.dev");
     let url = format!(
-        "{}/api/models?search={}&pipeline_tag=text-generation&sort=downloads&direction=-1&limit=100",
-        HF_BASE_URL, search_query
+        "{}/api/v1/models?limit=200&skip=0",
+        client.endpoints().ollamadb
     );
-
     let res = client
-        .get(&url)
-        .send()
+        .send_idempotent(client.get(&url))
         .await
-        .map_err(|e| format!("Failed to send request to Hugging Face: {}", e))?;
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Error fetching from ollamadb.dev");
+            ZamaError::request(OLLAMADB_SERVICE, e)
+        })?;
 
+    tracing::debug!(status = %res.status(), "Received response from ollamadb.dev");
     if !res.status().is_success() {
-        let status = res.status();
-        let error_text = res
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!(
-            "Hugging Face API returned non-success status: {} - {}",
-            status, error_text
-        ));
+        let error = ZamaError::from_response(OLLAMADB_SERVICE, res, None).await;
+        tracing::warn!(%error, "OllamaDB API returned non-success status");
+        return Err(error);
     }
 
-    res.json::<Vec<HfModel>>()
+    res.json::<OllamaDbResponse>()
         .await
-        .map_err(|e| format!("Failed to parse Hugging Face models response: {}", e))
+        .map(|response| response.models)
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse OllamaDB models response: {}", e)))
 }
 
-// Input validation helper
-fn validate_model_name(name: &str) -> Result<(), String> {
-    if name.is_empty() {
-        return Err("Model name cannot be empty".to_string());
-    }
-    if name.len() > 200 {
-        return Err("Model name is too long (max 200 characters)".to_string());
-    }
-    if name.trim() != name {
-        return Err("Model name cannot have leading or trailing whitespace".to_string());
-    }
-    // Prevent basic injection attempts
-    if name.contains(['<', '>', '"', '\'', '&', ';', '|', '`', '$']) {
-        return Err("Model name contains invalid characters".to_string());
-    }
-    // Ensure it looks like a valid model name (alphanumeric, hyphens, underscores, colons for tags, dots for versions)
-    if !name
-        .chars()
-        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.' | '/'))
-    {
-        return Err("Model name contains invalid characters".to_string());
-    }
-    Ok(())
+#[tauri::command]
+async fn pull_model(model_name: String) -> Result<String, ZamaError> {
+    let output = ollama::pull_model(&model_name, |_| {}).await?;
+    Ok(format!(
+        "Model {} pulled successfully.\n{}",
+        model_name, output
+    ))
 }
 
 #[tauri::command]
-async fn get_ollama_models() -> Result<String, String> {
-    println!("Attempting to fetch models from ollamadb.dev...");
-    let res = reqwest::get("https://ollamadb.dev/api/v1/models?limit=200&skip=0")
-        .await
-        .map_err(|e| {
-            eprintln!("Error fetching from ollamadb.dev: {}", e);
-            format!("Failed to fetch from ollamadb.dev: {}", e.to_string())
-        })?;
-
-    println!(
-        "Received response from ollamadb.dev with status: {}",
-        res.status()
-    );
-    if !res.status().is_success() {
-        let status = res.status();
-        let error_msg = format!("OllamaDB API returned non-success status: {}", status);
-        eprintln!("{}", error_msg);
-        return Err(error_msg);
-    }
+async fn import_gguf<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: String,
+    name: String,
+    template: Option<String>,
+) -> Result<GgufImportResult, ZamaError> {
+    validate_model_name(&name)?;
+    let request = GgufImportRequest {
+        path,
+        name,
+        template,
+    };
+    gguf_import::import_gguf(&app_handle, request).await
+}
 
-    res.text().await.map_err(|e| {
-        eprintln!("Error reading response text: {}", e);
-        format!("Failed to read response text: {}", e.to_string())
-    })
+#[tauri::command]
+async fn inspect_gguf(
+    http: tauri::State<'_, HttpState>,
+    path: Option<String>,
+    model: Option<String>,
+) -> Result<GgufInspection, ZamaError> {
+    let path = match (path, model) {
+        (Some(path), None) => std::path::PathBuf::from(path),
+        (None, Some(model)) => ollama::model_blob(&http.client(), &model).await?,
+        _ => {
+            return Err(ZamaError::InvalidInput(
+                "Provide either a GGUF path or an Ollama model name".to_string(),
+            ))
+        }
+    };
+    tokio::task::spawn_blocking(move || gguf::inspect(&path))
+        .await?
+        .map_err(ZamaError::Parse)
 }
 
 #[tauri::command]
-async fn pull_model(model_name: String) -> Result<String, String> {
-    // Validate input
-    validate_model_name(&model_name)?;
-    let mut command = TokioCommand::new("ollama")
-        .arg("pull")
-        .arg(&model_name)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to spawn ollama process: {}", e.to_string()))?;
-
-    let stdout = command.stdout.take().ok_or("Failed to capture stdout")?;
-    let stderr = command.stderr.take().ok_or("Failed to capture stderr")?;
-
-    let mut stdout_reader = BufReader::new(stdout).lines();
-    let mut stderr_reader = BufReader::new(stderr).lines();
-
-    let mut output = String::new();
-
-    loop {
-        tokio::select! {
-            line = stdout_reader.next_line() => {
-                match line {
-                    Ok(Some(l)) => {
-                        println!("stdout: {}", l);
-                        output.push_str(&l);
-                        output.push_str("\n");
-                    },
-                    Ok(None) => break,
-                    Err(e) => return Err(format!("Error reading stdout: {}", e.to_string())),
-                }
-            }
-            line = stderr_reader.next_line() => {
-                match line {
-                    Ok(Some(l)) => {
-                        println!("stderr: {}", l);
-                        output.push_str(&l);
-                        output.push_str("\n");
-                    },
-                    Ok(None) => break,
-                    Err(e) => return Err(format!("Error reading stderr: {}", e.to_string())),
+async fn estimate_memory(
+    http: tauri::State<'_, HttpState>,
+    request: MemoryEstimateRequest,
+) -> Result<MemoryEstimate, ZamaError> {
+    let mut inputs = EstimateInputs::from_request(&request).map_err(ZamaError::InvalidInput)?;
+
+    // For installed models, fill in whatever the caller left out from Ollama and the GGUF itself
+    if let Some(model) = request.model.as_deref() {
+        validate_model_name(model)?;
+        let client = http.client();
+        let show = fetch_model_info(
+            &client,
+            ShowModelRequest {
+                name: model.to_string(),
+            },
+        )
+        .await?;
+        inputs.parameter_count = inputs
+            .parameter_count
+            .or_else(|| hf::parse_parameter_count(&show.details.parameter_size));
+        inputs.quantization = inputs
+            .quantization
+            .or_else(|| quant::QuantType::from_name(&show.details.quantization_level));
+
+        match ollama::model_blob(&client, model).await {
+            Ok(blob) => match tokio::task::spawn_blocking(move || gguf::inspect(&blob)).await {
+                Ok(Ok(inspection)) => {
+                    inputs.shape = ModelShape::from_inspection(&inspection);
+                    inputs.file_size = inputs.file_size.or(Some(inspection.file_size));
+                    if inspection.parameter_count > 0 {
+                        inputs.parameter_count = Some(inspection.parameter_count);
+                    }
                 }
-            }
+                Ok(Err(e)) => tracing::warn!(%model, error = %e, "Failed to inspect GGUF"),
+                Err(e) => tracing::error!(error = %e, "GGUF inspection task failed"),
+            },
+            Err(e) => tracing::warn!("{}", e),
         }
     }
 
-    let status = command
-        .wait()
-        .await
-        .map_err(|e| format!("Failed to wait for ollama process: {}", e.to_string()))?;
-
-    if status.success() {
-        Ok(format!(
-            "Model {} pulled successfully.\n{}",
-            model_name, output
-        ))
-    } else {
-        Err(format!(
-            "Failed to pull model {}: {}\n{}",
-            model_name, status, output
-        ))
-    }
+    let system = tokio::task::spawn_blocking(memory::system_memory).await?;
+    memory::estimate(&inputs, system).map_err(ZamaError::InvalidInput)
 }
 
 #[tauri::command]
-async fn check_ollama_status() -> Result<String, String> {
-    _check_and_start_ollama_logic().await
+fn app_info<R: Runtime>(app_handle: AppHandle<R>) -> AppInfo {
+    app_info::app_info(&app_handle)
 }
 
-async fn _check_and_start_ollama_logic() -> Result<String, String> {
-    println!("Attempting to check/start Ollama server...");
-    let client = reqwest::Client::new();
-    let ollama_url = "http://localhost:11434";
-
-    // Check if Ollama is already running
-    match timeout(Duration::from_secs(1), client.get(ollama_url).send()).await {
-        Ok(Ok(res)) if res.status().is_success() => {
-            return Ok("Ollama server is already running.".to_string());
-        }
-        _ => {
-            // Ollama is not running, try to start it
-            println!("Ollama server not found, attempting to start...");
-            TokioCommand::new("ollama")
-                .arg("serve")
-                .spawn()
-                .map_err(|e| format!("Failed to spawn ollama serve process: {}", e.to_string()))?;
-
-            // Give Ollama some time to start up
-            tokio::time::sleep(Duration::from_secs(5)).await;
-
-            // Verify if Ollama started successfully
-            match timeout(Duration::from_secs(5), client.get(ollama_url).send()).await {
-                Ok(Ok(res)) if res.status().is_success() => {
-                    Ok("Ollama server started successfully.".to_string())
-                }
-                _ => Err("Failed to start Ollama server or it did not respond.".to_string()),
-            }
-        }
-    }
+#[tauri::command]
+async fn check_ollama_status(http: tauri::State<'_, HttpState>) -> Result<String, ZamaError> {
+    ollama::check_and_start(&http.client()).await
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(updater::UpdaterState::default())
+        .manage(server::ServerState::default())
         .invoke_handler(tauri::generate_handler![
             get_ollama_models,
             list_ollama_models,
@@ -559,20 +441,95 @@ pub fn run() {
             get_ollama_config,
             chat_ollama,
             pull_model,
+            import_gguf,
+            inspect_gguf,
+            estimate_memory,
             check_ollama_status,
             list_hf_models,
-            send_gemini_chat
+            list_hf_gguf_files,
+            send_gemini_chat,
+            app_info,
+            batch::run_batch,
+            compare::compare_chat,
+            context::plan_context,
+            context::get_context_settings,
+            context::set_context_settings,
+            conversations::list_conversations,
+            conversations::get_conversation,
+            conversations::create_conversation,
+            conversations::append_message,
+            conversations::update_message,
+            conversations::edit_message,
+            conversations::regenerate_message,
+            conversations::list_branches,
+            conversations::switch_branch,
+            conversations::fork_conversation,
+            conversations::rename_conversation,
+            conversations::delete_conversation,
+            conversations::clear_conversations,
+            conversations::import_conversations,
+            presets::get_presets,
+            presets::set_global_preset,
+            presets::set_model_preset,
+            presets::set_conversation_parameters,
+            presets::resolve_options,
+            prompts::list_prompts,
+            prompts::get_prompt,
+            prompts::save_prompt,
+            prompts::delete_prompt,
+            prompts::render_prompt,
+            prompts::export_prompts,
+            prompts::import_prompts,
+            stats::model_stats,
+            titles::generate_title,
+            titles::get_title_settings,
+            titles::set_title_settings,
+            credentials::set_provider_key,
+            credentials::get_provider_status,
+            evals::run_eval,
+            evals::list_eval_reports,
+            evals::get_eval_report,
+            evals::delete_eval_report,
+            evals::diff_eval_reports,
+            http::get_network_settings,
+            http::set_network_settings,
+            logging::get_log_settings,
+            logging::set_log_settings,
+            logging::get_recent_logs,
+            logging::export_diagnostics,
+            server::get_server_status,
+            server::set_server_settings,
+            server::regenerate_server_token,
+            updater::check_for_updates,
+            updater::get_update_settings,
+            updater::set_update_settings,
+            updater::skip_update,
+            updater::remind_update_later,
+            updater::install_update,
+            updater::restart_app
         ])
-        .setup(|_app| {
+        .setup(|app| {
+            if let Err(e) = logging::init(app.handle()) {
+                eprintln!("Logging disabled: {}", e);
+            }
+            app.manage(http::init(app.handle()));
+            app.manage(conversations::init(app.handle())?);
+            app.manage(prompts::init(app.handle())?);
+            tauri::async_runtime::spawn(server::start_if_enabled(app.handle().clone()));
+
+            #[cfg(desktop)]
+            let app_handle = app.handle().clone();
             #[cfg(desktop)]
             tauri::async_runtime::spawn(async move {
-                // Check for updates
-                updater::check_and_update().await;
+                let client = http::client(&app_handle);
+
+                // Check for updates, installing only happens once the user agrees
+                updater::check_and_update(app_handle).await;
 
                 // Original Ollama check
-                match _check_and_start_ollama_logic().await {
-                    Ok(msg) => println!("Ollama status: {}", msg),
-                    Err(e) => eprintln!("Error checking/starting Ollama: {}", e),
+                match ollama::check_and_start(&client).await {
+                    Ok(msg) => tracing::info!("Ollama status: {}", msg),
+                    Err(e) => tracing::error!(error = %e, "Error checking/starting Ollama"),
                 }
             });
             Ok(())
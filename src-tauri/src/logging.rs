@@ -0,0 +1,490 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager, Runtime};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::app_info;
+use crate::error::ZamaError;
+use crate::paths::{self, AppPaths};
+
+const SETTINGS_FILE: &str = "logging.json";
+const LOG_FILE_PREFIX: &str = "zama";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7; // One file per day
+const DIAGNOSTICS_DIR: &str = "diagnostics";
+const DEFAULT_RECENT_LOGS: usize = 500;
+const MAX_RECENT_LOGS: usize = 5000;
+
+// Overrides the configured filter, eg. `ZAMA_LOG=debug,reqwest=trace`
+const LOG_ENV: &str = "ZAMA_LOG";
+
+// Prompts and model output are only written out when the user opts in
+static REDACT_CONTENT: AtomicBool = AtomicBool::new(true);
+
+static SECRET_PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct LogSettings {
+    pub level: String, // "error", "warn", "info", "debug" or "trace"
+    pub filters: BTreeMap<String, String>, // Per-target levels, eg. `zama_lib::updater` -> "debug"
+    pub redact_content: bool,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            filters: BTreeMap::new(),
+            redact_content: true,
+        }
+    }
+}
+
+impl LogSettings {
+    pub fn directives(&self) -> String {
+        std::iter::once(self.level.trim().to_string())
+            .chain(
+                self.filters
+                    .iter()
+                    .map(|(target, level)| format!("{}={}", target.trim(), level.trim())),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn env_filter(&self) -> Result<EnvFilter, String> {
+        EnvFilter::try_new(self.directives())
+            .map_err(|e| format!("Invalid log filter '{}': {}", self.directives(), e))
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LogEntry {
+    pub timestamp: Option<String>,
+    pub level: Option<String>,
+    pub target: Option<String>,
+    pub message: String,
+}
+
+// Managed by Tauri so commands can reach the filter and the log directory
+pub struct LoggingState {
+    pub log_dir: PathBuf,
+    filter: reload::Handle<EnvFilter, Registry>,
+    _guard: Mutex<WorkerGuard>, // Flushes the background writer on shutdown
+}
+
+impl LoggingState {
+    // `filter` reloads the level of whichever subscriber holds its layer; `guard` is the log
+    // writer's, kept until shutdown
+    pub fn new(
+        log_dir: PathBuf,
+        filter: reload::Handle<EnvFilter, Registry>,
+        guard: WorkerGuard,
+    ) -> Self {
+        Self {
+            log_dir,
+            filter,
+            _guard: Mutex::new(guard),
+        }
+    }
+}
+
+// --- Setup ---
+
+pub fn init<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    let settings = load_settings(app_handle);
+    REDACT_CONTENT.store(settings.redact_content, Ordering::Relaxed);
+
+    let log_dir = app_handle
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve app log dir: {}", e))?;
+    fs::create_dir_all(&log_dir).map_err(|e| format!("Failed to create app log dir: {}", e))?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+    let (file_writer, guard) = tracing_appender::non_blocking(appender);
+
+    let filter = match std::env::var(LOG_ENV) {
+        Ok(directives) if !directives.trim().is_empty() => EnvFilter::try_new(&directives)
+            .map_err(|e| format!("Invalid {} filter '{}': {}", LOG_ENV, directives, e))?,
+        _ => settings.env_filter()?,
+    };
+    let (filter, filter_handle) = reload::Layer::new(filter);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(Redacting(file_writer)),
+        )
+        // Only echo to the terminal in development builds
+        .with(
+            cfg!(debug_assertions)
+                .then(|| tracing_subscriber::fmt::layer().with_writer(Redacting(io::stderr))),
+        )
+        .try_init()
+        .map_err(|e| format!("Failed to install logger: {}", e))?;
+
+    app_handle.manage(LoggingState::new(log_dir, filter_handle, guard));
+    tracing::info!(
+        version = app_info::VERSION,
+        commit = app_info::GIT_COMMIT,
+        "Zama starting"
+    );
+    Ok(())
+}
+
+// Terminal-only logging for the CLI; the log files belong to the desktop app
+pub fn init_stderr(paths: &impl AppPaths) -> Result<(), String> {
+    let settings = load_settings(paths);
+    REDACT_CONTENT.store(settings.redact_content, Ordering::Relaxed);
+
+    let filter = match std::env::var(LOG_ENV) {
+        Ok(directives) if !directives.trim().is_empty() => EnvFilter::try_new(&directives)
+            .map_err(|e| format!("Invalid {} filter '{}': {}", LOG_ENV, directives, e))?,
+        _ => EnvFilter::new("warn"),
+    };
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(Redacting(io::stderr)))
+        .try_init()
+        .map_err(|e| format!("Failed to install logger: {}", e))
+}
+
+// --- Settings ---
+
+pub fn load_settings(paths: &impl AppPaths) -> LogSettings {
+    paths::load_settings(paths, SETTINGS_FILE)
+}
+
+fn save_settings(paths: &impl AppPaths, settings: &LogSettings) -> Result<(), ZamaError> {
+    paths::save_settings(paths, SETTINGS_FILE, settings)
+}
+
+#[tauri::command]
+pub fn get_log_settings<R: Runtime>(app_handle: AppHandle<R>) -> LogSettings {
+    load_settings(&app_handle)
+}
+
+#[tauri::command]
+pub fn set_log_settings<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: tauri::State<'_, LoggingState>,
+    settings: LogSettings,
+) -> Result<LogSettings, ZamaError> {
+    // Validate before saving so a typo can't leave the app without logs on the next launch
+    let filter = settings.env_filter().map_err(ZamaError::InvalidInput)?;
+    state
+        .filter
+        .reload(filter)
+        .map_err(|e| ZamaError::Internal(format!("Failed to apply log filter: {}", e)))?;
+    REDACT_CONTENT.store(settings.redact_content, Ordering::Relaxed);
+    save_settings(&app_handle, &settings)?;
+    tracing::info!(filter = %settings.directives(), redact_content = settings.redact_content, "Log settings updated");
+    Ok(settings)
+}
+
+// --- Redaction ---
+
+fn secret_patterns() -> &'static [(Regex, &'static str)] {
+    SECRET_PATTERNS.get_or_init(|| {
+        [
+            // Google API keys, also when passed as `?key=` on Gemini URLs
+            (r"AIza[0-9A-Za-z_\-]{35}", "AIza[REDACTED]"),
+            (
+                r"([?&](?:key|api_key|token|access_token)=)[^&\s]+",
+                "${1}[REDACTED]",
+            ),
+            (r"(?i)(bearer\s+)[A-Za-z0-9._~+/\-]+=*", "${1}[REDACTED]"),
+            (r"\bhf_[A-Za-z0-9]{20,}", "hf_[REDACTED]"),
+            (r"\bsk-[A-Za-z0-9_\-]{20,}", "sk-[REDACTED]"),
+            (
+                r#"(?i)("(?:api_?key|apiKey|token|password|secret)"\s*:\s*")[^"]*""#,
+                "${1}[REDACTED]\"",
+            ),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| {
+            (
+                Regex::new(pattern).expect("secret pattern is valid"),
+                replacement,
+            )
+        })
+        .collect()
+    })
+}
+
+// Masks API keys and tokens. Applied to every log line and to files in the diagnostics bundle.
+pub fn redact_secrets(text: &str) -> String {
+    secret_patterns()
+        .iter()
+        .fold(text.to_string(), |text, (pattern, replacement)| {
+            pattern.replace_all(&text, *replacement).into_owned()
+        })
+}
+
+// Wraps user prompts and model output before logging them; only the length is kept unless the
+// user turned content logging on
+pub fn content(text: &str) -> String {
+    if REDACT_CONTENT.load(Ordering::Relaxed) {
+        format!("[{} chars redacted]", text.chars().count())
+    } else {
+        text.to_string()
+    }
+}
+
+#[derive(Clone)]
+struct Redacting<W>(W);
+
+struct RedactingWriter<W: Write>(W);
+
+impl<'a, M> MakeWriter<'a> for Redacting<M>
+where
+    M: MakeWriter<'a>,
+{
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+}
+
+// The formatter hands over one complete event per write
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = redact_secrets(&String::from_utf8_lossy(buf));
+        self.0.write_all(line.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+// --- Reading logs ---
+
+// Log files sorted oldest first; the date in the file name sorts lexically
+fn log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(log_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                        n.starts_with(LOG_FILE_PREFIX) && n.ends_with(LOG_FILE_SUFFIX)
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// Lines look like `2026-10-18T09:12:44.123456Z  INFO zama_lib::updater: message`
+pub fn parse_log_line(line: &str) -> LogEntry {
+    let mut parts = line.split_whitespace();
+    let (timestamp, level, target) = (parts.next(), parts.next(), parts.next());
+    let is_level = |l: &str| matches!(l, "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE");
+
+    match (timestamp, level, target) {
+        (Some(ts), Some(level), Some(target))
+            if is_level(level) && ts.contains('T') && target.ends_with(':') =>
+        {
+            let message = line
+                .split_once(target)
+                .map(|(_, rest)| rest.trim())
+                .unwrap_or_default();
+            LogEntry {
+                timestamp: Some(ts.to_string()),
+                level: Some(level.to_string()),
+                target: Some(target.trim_end_matches(':').to_string()),
+                message: message.to_string(),
+            }
+        }
+        // Continuation of a multi-line message
+        _ => LogEntry {
+            timestamp: None,
+            level: None,
+            target: None,
+            message: line.to_string(),
+        },
+    }
+}
+
+fn level_rank(level: &str) -> u8 {
+    match level.to_ascii_uppercase().as_str() {
+        "ERROR" => 0,
+        "WARN" => 1,
+        "INFO" => 2,
+        "DEBUG" => 3,
+        _ => 4,
+    }
+}
+
+pub fn recent_logs(log_dir: &Path, limit: usize, min_level: Option<&str>) -> Vec<LogEntry> {
+    let max_rank = min_level.map(level_rank).unwrap_or(u8::MAX);
+    let mut entries = Vec::new();
+
+    // Walk files newest first until enough lines are collected
+    for path in log_files(log_dir).iter().rev() {
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        let mut file_entries: Vec<LogEntry> = Vec::new();
+        let mut keep = true;
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            let entry = parse_log_line(line);
+            if let Some(level) = &entry.level {
+                keep = level_rank(level) <= max_rank;
+            }
+            if keep {
+                file_entries.push(entry);
+            }
+        }
+        file_entries.reverse();
+        entries.extend(file_entries);
+        if entries.len() >= limit {
+            break;
+        }
+    }
+
+    entries.truncate(limit);
+    entries.reverse();
+    entries
+}
+
+#[tauri::command]
+pub async fn get_recent_logs(
+    state: tauri::State<'_, LoggingState>,
+    limit: Option<usize>,
+    min_level: Option<String>,
+) -> Result<Vec<LogEntry>, ZamaError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_RECENT_LOGS)
+        .clamp(1, MAX_RECENT_LOGS);
+    let log_dir = state.log_dir.clone();
+    let entries =
+        tokio::task::spawn_blocking(move || recent_logs(&log_dir, limit, min_level.as_deref()))
+            .await?;
+    Ok(entries)
+}
+
+// --- Diagnostics bundle ---
+
+#[derive(Debug, Serialize)]
+struct DiagnosticsSystem {
+    os: &'static str,
+    arch: &'static str,
+    total_memory_bytes: u64,
+    available_memory_bytes: u64,
+}
+
+#[tauri::command]
+pub async fn export_diagnostics<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, ZamaError> {
+    let info = app_info::app_info(&app_handle);
+    let path = export_bundle(&app_handle, info).await?;
+    tracing::info!(path = %path.display(), "Exported diagnostics bundle");
+    Ok(path.to_string_lossy().into_owned())
+}
+
+// Zips logs, build info, system info and settings for a bug report. Secrets are masked again on
+// the way in, in case older log files predate redaction.
+pub async fn export_bundle(
+    paths: &impl AppPaths,
+    info: app_info::AppInfo,
+) -> Result<PathBuf, ZamaError> {
+    let out_dir = paths.data_dir()?.join(DIAGNOSTICS_DIR);
+    let config_dir = paths.config_dir()?;
+    let log_dir = paths.log_dir()?;
+
+    let path = tokio::task::spawn_blocking(move || {
+        let memory = crate::memory::system_memory();
+        let system = DiagnosticsSystem {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            total_memory_bytes: memory.total_bytes,
+            available_memory_bytes: memory.available_bytes,
+        };
+        write_diagnostics(&out_dir, &log_dir, &config_dir, &info, &system)
+    })
+    .await?
+    .map_err(ZamaError::Io)?;
+    Ok(path)
+}
+
+fn write_diagnostics(
+    out_dir: &Path,
+    log_dir: &Path,
+    config_dir: &Path,
+    info: &app_info::AppInfo,
+    system: &DiagnosticsSystem,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(out_dir).map_err(|e| format!("Failed to create diagnostics dir: {}", e))?;
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = out_dir.join(format!("zama-diagnostics-{}.zip", stamp));
+    let file =
+        fs::File::create(&path).map_err(|e| format!("Failed to create diagnostics file: {}", e))?;
+
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut add = |name: &str, content: &str| -> Result<(), String> {
+        zip.start_file(name, options)
+            .and_then(|_| {
+                zip.write_all(redact_secrets(content).as_bytes())
+                    .map_err(Into::into)
+            })
+            .map_err(|e| format!("Failed to write {} to diagnostics bundle: {}", name, e))
+    };
+
+    add(
+        "app_info.json",
+        &serde_json::to_string_pretty(info).unwrap_or_default(),
+    )?;
+    add(
+        "system.json",
+        &serde_json::to_string_pretty(system).unwrap_or_default(),
+    )?;
+
+    for settings_file in [SETTINGS_FILE, "updater.json"] {
+        if let Ok(content) = fs::read_to_string(config_dir.join(settings_file)) {
+            add(&format!("settings/{}", settings_file), &content)?;
+        }
+    }
+
+    for log in log_files(log_dir) {
+        let mut content = String::new();
+        let read = fs::File::open(&log).and_then(|mut f| f.read_to_string(&mut content));
+        if let (Ok(_), Some(name)) = (read, log.file_name().and_then(|n| n.to_str())) {
+            add(&format!("logs/{}", name), &content)?;
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish diagnostics bundle: {}", e))?;
+    Ok(path)
+}
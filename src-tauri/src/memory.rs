@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+
+use crate::gguf::GgufInspection;
+use crate::hf::parse_parameter_count;
+use crate::quant::QuantType;
+
+pub(crate) const DEFAULT_NUM_CTX: u64 = 2048;
+const KV_BYTES_PER_ELEMENT: u64 = 2; // Ollama keeps the KV cache in f16 by default
+
+// Compute graph and runtime buffers on top of weights and KV cache
+const OVERHEAD_BYTES: u64 = 512 * 1024 * 1024;
+const OVERHEAD_RATIO: f64 = 0.05;
+
+// Share of system RAM a model may use before the OS and other apps start swapping
+const USABLE_RAM_RATIO: f64 = 0.85;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MemoryEstimateRequest {
+    pub model: Option<String>, // Installed Ollama model to read details and GGUF metadata from
+    pub parameter_size: Option<String>, // Eg. "8.0B", as in `ModelDetails.parameter_size`
+    pub quantization_level: Option<String>, // Eg. "Q4_K_M"
+    pub file_size: Option<u64>, // Size of the weights on disk, when known
+    pub num_ctx: Option<u64>,
+    pub gpu_memory_bytes: Option<u64>, // Dedicated VRAM; leave empty for CPU-only or unified memory
+}
+
+// Model shape needed for an exact KV cache size
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct ModelShape {
+    pub layer_count: u64,
+    pub embedding_length: u64,
+    pub head_count: u64,
+    pub head_count_kv: u64,
+}
+
+impl ModelShape {
+    pub fn from_inspection(inspection: &GgufInspection) -> Option<Self> {
+        let head_count = inspection.head_count?;
+        Some(ModelShape {
+            layer_count: inspection.layer_count?,
+            embedding_length: inspection.embedding_length?,
+            head_count,
+            head_count_kv: inspection.head_count_kv.unwrap_or(head_count),
+        })
+    }
+
+    pub fn kv_bytes_per_token(&self) -> u64 {
+        let head_dim = self.embedding_length / self.head_count.max(1);
+        // One K and one V vector per layer
+        2 * self.layer_count * self.head_count_kv * head_dim * KV_BYTES_PER_ELEMENT
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryFit {
+    Fit,
+    PartialOffload,
+    WontFit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryEstimate {
+    pub fit: MemoryFit,
+    pub parameter_count: Option<u64>,
+    pub quantization: Option<String>,
+    pub num_ctx: u64,
+    pub weights_bytes: u64,
+    pub kv_cache_bytes: u64,
+    pub overhead_bytes: u64,
+    pub total_bytes: u64,
+    pub kv_cache_exact: bool, // False when the model shape had to be guessed from its size
+    pub system_total_bytes: u64,
+    pub system_available_bytes: u64,
+    pub gpu_memory_bytes: Option<u64>,
+    pub gpu_layers: Option<u64>, // Layers expected to fit on the GPU when partially offloading
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SystemMemory {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+pub fn system_memory() -> SystemMemory {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    SystemMemory {
+        total_bytes: system.total_memory(),
+        available_bytes: system.available_memory(),
+    }
+}
+
+// Inputs after merging the request with whatever could be learned about an installed model
+#[derive(Debug, Clone, Default)]
+pub struct EstimateInputs {
+    pub parameter_count: Option<u64>,
+    pub quantization: Option<QuantType>,
+    pub file_size: Option<u64>,
+    pub num_ctx: Option<u64>,
+    pub shape: Option<ModelShape>,
+    pub gpu_memory_bytes: Option<u64>,
+}
+
+impl EstimateInputs {
+    pub fn from_request(request: &MemoryEstimateRequest) -> Result<Self, String> {
+        let parameter_count = match request.parameter_size.as_deref() {
+            Some(size) => Some(
+                parse_parameter_count(size)
+                    .ok_or_else(|| format!("Invalid parameter size '{}'", size))?,
+            ),
+            None => None,
+        };
+        let quantization = match request.quantization_level.as_deref() {
+            Some(level) => Some(
+                QuantType::from_name(level)
+                    .ok_or_else(|| format!("Unknown quantization level '{}'", level))?,
+            ),
+            None => None,
+        };
+        Ok(EstimateInputs {
+            parameter_count,
+            quantization,
+            file_size: request.file_size,
+            num_ctx: request.num_ctx,
+            shape: None,
+            gpu_memory_bytes: request.gpu_memory_bytes,
+        })
+    }
+}
+
+pub fn estimate(inputs: &EstimateInputs, system: SystemMemory) -> Result<MemoryEstimate, String> {
+    let num_ctx = inputs.num_ctx.unwrap_or(DEFAULT_NUM_CTX);
+    if num_ctx == 0 {
+        return Err("Context length must be greater than zero".to_string());
+    }
+    let mut warnings = Vec::new();
+
+    // The file size already accounts for mixed tensor types, so prefer it over bits-per-weight
+    let weights_bytes = match (
+        inputs.file_size,
+        inputs.parameter_count,
+        &inputs.quantization,
+    ) {
+        (Some(size), _, _) => size,
+        (None, Some(params), Some(quant)) => quant.weight_bytes(params),
+        (None, Some(params), None) => {
+            warnings.push("Quantization unknown, assuming Q4_K_M".to_string());
+            QuantType::from_name("Q4_K_M")
+                .map(|q| q.weight_bytes(params))
+                .unwrap_or_default()
+        }
+        (None, None, _) => {
+            return Err("Provide a parameter size or file size to estimate memory".to_string())
+        }
+    };
+
+    let (kv_per_token, kv_cache_exact) = match (inputs.shape, inputs.parameter_count) {
+        (Some(shape), _) => (shape.kv_bytes_per_token(), true),
+        (None, Some(params)) => (approximate_kv_bytes_per_token(params), false),
+        (None, None) => (
+            approximate_kv_bytes_per_token(estimate_params_from_size(weights_bytes)),
+            false,
+        ),
+    };
+    let kv_cache_bytes = kv_per_token * num_ctx;
+    let overhead_bytes = OVERHEAD_BYTES + (weights_bytes as f64 * OVERHEAD_RATIO) as u64;
+    let total_bytes = weights_bytes + kv_cache_bytes + overhead_bytes;
+
+    let usable_ram = (system.total_bytes as f64 * USABLE_RAM_RATIO) as u64;
+    let (fit, gpu_layers) = match inputs.gpu_memory_bytes {
+        Some(vram) if total_bytes <= vram => (MemoryFit::Fit, inputs.shape.map(|s| s.layer_count)),
+        Some(vram) if total_bytes <= vram + usable_ram => {
+            // Layers are roughly equal in size, so offload the share that fits in VRAM
+            let layers = inputs.shape.map(|s| {
+                let share = vram.saturating_sub(overhead_bytes) as f64 / total_bytes as f64;
+                (s.layer_count as f64 * share).floor() as u64
+            });
+            (MemoryFit::PartialOffload, layers)
+        }
+        Some(_) => (MemoryFit::WontFit, Some(0)),
+        None if total_bytes <= usable_ram => (MemoryFit::Fit, None),
+        None => (MemoryFit::WontFit, None),
+    };
+
+    if fit != MemoryFit::WontFit && total_bytes > system.available_bytes {
+        warnings.push(format!(
+            "Only {} is free right now; close other applications before loading this model",
+            format_bytes(system.available_bytes)
+        ));
+    }
+    if !kv_cache_exact {
+        warnings.push("KV cache size estimated from the parameter count".to_string());
+    }
+
+    Ok(MemoryEstimate {
+        fit,
+        parameter_count: inputs.parameter_count,
+        quantization: inputs.quantization.as_ref().map(|q| q.name.clone()),
+        num_ctx,
+        weights_bytes,
+        kv_cache_bytes,
+        overhead_bytes,
+        total_bytes,
+        kv_cache_exact,
+        system_total_bytes: system.total_bytes,
+        system_available_bytes: system.available_bytes,
+        gpu_memory_bytes: inputs.gpu_memory_bytes,
+        gpu_layers,
+        warnings,
+    })
+}
+
+// Typical KV bytes per token for grouped-query attention models of a given size,
+// eg. Llama 3 8B uses 32 layers with 8 KV heads of 128 dims (128 KiB per token)
+fn approximate_kv_bytes_per_token(parameter_count: u64) -> u64 {
+    let billions = parameter_count as f64 / 1e9;
+    let (layers, kv_dim) = match billions {
+        b if b <= 2.0 => (28, 256),
+        b if b <= 4.0 => (28, 1024),
+        b if b <= 9.0 => (32, 1024),
+        b if b <= 15.0 => (40, 1280),
+        b if b <= 35.0 => (64, 1024),
+        b if b <= 75.0 => (80, 1024),
+        _ => (126, 1024),
+    };
+    2 * layers * kv_dim * KV_BYTES_PER_ELEMENT
+}
+
+// Without a parameter count, assume a ~4.85 bit quantization to work backwards from file size
+fn estimate_params_from_size(weights_bytes: u64) -> u64 {
+    (weights_bytes as f64 * 8.0 / 4.85) as u64
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= GIB {
+        format!("{:.1} GiB", bytes as f64 / GIB)
+    } else {
+        format!("{:.0} MiB", bytes as f64 / MIB)
+    }
+}
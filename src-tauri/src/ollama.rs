@@ -0,0 +1,571 @@
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
+use tokio::time::{timeout, Duration};
+
+use crate::context;
+use crate::conversations::ConversationStore;
+use crate::error::ZamaError;
+use crate::http::{self, HttpClient};
+use crate::logging;
+use crate::paths::AppPaths;
+use crate::presets;
+use crate::stats::GenerationStats;
+use crate::thinking::{self, ThinkTagParser};
+
+// The Ollama client behind both the Tauri commands and the CLI. Nothing in here knows about
+// windows or events; callers pass the HTTP client and where settings live.
+
+pub const OLLAMA_BASE_URL: &str = "http://localhost:11434";
+pub const OLLAMA_SERVICE: &str = "Ollama";
+
+// --- Schemas ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>, // Reasoning from models with a native thinking channel
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChatRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    pub stream: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub think: Option<bool>, // Unset: enabled automatically for models that support it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Map<String, serde_json::Value>>, // Eg. `num_ctx`, `temperature`
+    #[serde(default, skip_serializing)]
+    pub conversation_id: Option<String>, // Whose saved parameters apply; not sent to Ollama
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatResponse {
+    pub model: String,
+    pub created_at: String,
+    pub message: Option<Message>, // Message might be null for done: true responses
+    pub done: bool,
+    // Timings (nanoseconds) and token counts, only sent with the final chunk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_duration: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_duration: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_eval_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_eval_duration: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eval_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eval_duration: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    // Derived by Zama from the fields above and attached to the final chunk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<GenerationStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullModelRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteModelRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShowModelRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModelDetails {
+    pub parent_model: Option<String>,
+    pub format: String,
+    pub family: String,
+    pub families: Option<Vec<String>>,
+    pub parameter_size: String,
+    pub quantization_level: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShowModelResponse {
+    #[serde(default)]
+    pub model: String, // Not sent by Ollama; filled in from the request
+    #[serde(default)]
+    pub license: String,
+    pub modelfile: String,
+    #[serde(default)]
+    pub parameters: String, // One `key value` pair per line, absent for models without any
+    #[serde(default)]
+    pub template: String,
+    pub details: ModelDetails,
+    #[serde(default)]
+    pub capabilities: Vec<String>, // Eg. `completion`, `tools`, `thinking`; empty on older Ollama
+    #[serde(default)]
+    pub model_info: serde_json::Map<String, serde_json::Value>, // GGUF metadata, eg. `llama.context_length`
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListModelEntry {
+    pub name: String,
+    pub modified_at: String,
+    pub size: u64,
+    pub digest: String,
+    pub details: ModelDetails,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListModelsResponse {
+    pub models: Vec<ListModelEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    pub version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigResponse {
+    pub ollama_url: String, // Assuming this is the only field for now
+}
+
+// --- Requests ---
+
+// Input validation helper
+pub fn validate_model_name(name: &str) -> Result<(), ZamaError> {
+    if name.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Model name cannot be empty".to_string(),
+        ));
+    }
+    if name.len() > 200 {
+        return Err(ZamaError::InvalidInput(
+            "Model name is too long (max 200 characters)".to_string(),
+        ));
+    }
+    if name.trim() != name {
+        return Err(ZamaError::InvalidInput(
+            "Model name cannot have leading or trailing whitespace".to_string(),
+        ));
+    }
+    // Prevent basic injection attempts
+    if name.contains(['<', '>', '"', '\'', '&', ';', '|', '`', '$']) {
+        return Err(ZamaError::InvalidInput(
+            "Model name contains invalid characters".to_string(),
+        ));
+    }
+    // Ensure it looks like a valid model name (alphanumeric, hyphens, underscores, colons for tags, dots for versions)
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.' | '/'))
+    {
+        return Err(ZamaError::InvalidInput(
+            "Model name contains invalid characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn list_models(client: &HttpClient) -> Result<ListModelsResponse, ZamaError> {
+    let url = format!("{}/api/tags", client.endpoints().ollama);
+    let res = client
+        .send_idempotent(client.get(&url))
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, None).await);
+    }
+
+    res.json::<ListModelsResponse>()
+        .await
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse Ollama models response: {}", e)))
+}
+
+pub async fn delete_model(
+    client: &HttpClient,
+    request: &DeleteModelRequest,
+) -> Result<(), ZamaError> {
+    validate_model_name(&request.name)?;
+    let url = format!("{}/api/delete", client.endpoints().ollama);
+    let res = client
+        .delete(&url)
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, Some(&request.name)).await);
+    }
+    Ok(())
+}
+
+pub async fn fetch_model_info(
+    client: &HttpClient,
+    request: ShowModelRequest,
+) -> Result<ShowModelResponse, ZamaError> {
+    validate_model_name(&request.name)?;
+    let url = format!("{}/api/show", client.endpoints().ollama);
+    let res = client
+        .send_idempotent(client.post(&url).json(&request))
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, Some(&request.name)).await);
+    }
+
+    let mut show = res.json::<ShowModelResponse>().await.map_err(|e| {
+        ZamaError::Parse(format!("Failed to parse Ollama show model response: {}", e))
+    })?;
+    if show.model.is_empty() {
+        show.model = request.name;
+    }
+    Ok(show)
+}
+
+// `/api/show` for features that degrade gracefully without it, like context sizing
+pub async fn show_model(client: &HttpClient, model: &str) -> Option<ShowModelResponse> {
+    fetch_model_info(
+        client,
+        ShowModelRequest {
+            name: model.to_string(),
+        },
+    )
+    .await
+    .inspect_err(|e| tracing::debug!(model, error = %e, "Could not read model info"))
+    .ok()
+}
+
+pub async fn get_config(client: &HttpClient) -> Result<ConfigResponse, ZamaError> {
+    let url = format!("{}/api/config", client.endpoints().ollama);
+    let res = client
+        .send_idempotent(client.get(&url))
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, None).await);
+    }
+
+    res.json::<ConfigResponse>()
+        .await
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse Ollama config response: {}", e)))
+}
+
+pub async fn version(client: &HttpClient) -> Result<String, ZamaError> {
+    let url = format!("{}/api/version", client.endpoints().ollama);
+    let res = client
+        .send_idempotent(client.get(&url))
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, None).await);
+    }
+
+    res.json::<VersionResponse>()
+        .await
+        .map(|response| response.version)
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse Ollama version response: {}", e)))
+}
+
+// Ollama's generated Modelfile points `FROM` at the weights blob on disk
+pub async fn model_blob(client: &HttpClient, model: &str) -> Result<std::path::PathBuf, ZamaError> {
+    let show = fetch_model_info(
+        client,
+        ShowModelRequest {
+            name: model.to_string(),
+        },
+    )
+    .await?;
+    show.modelfile
+        .lines()
+        .filter_map(|line| line.strip_prefix("FROM "))
+        .map(|path| std::path::PathBuf::from(path.trim()))
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            ZamaError::Internal(format!(
+                "Could not locate the GGUF blob for model {}",
+                model
+            ))
+        })
+}
+
+// --- Chat ---
+
+// One non-streaming `/api/chat` call for background jobs. `format` is passed through, eg. a JSON
+// schema the reply must follow.
+pub async fn complete(
+    client: &HttpClient,
+    model: &str,
+    messages: &[Message],
+    format: Option<serde_json::Value>,
+) -> Result<String, ZamaError> {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": messages,
+        "stream": false,
+    });
+    if let Some(format) = format {
+        body["format"] = format;
+    }
+    let res = client
+        .post(&format!("{}/api/chat", client.endpoints().ollama))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, Some(model)).await);
+    }
+    let response = res
+        .json::<ChatResponse>()
+        .await
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse Ollama chat response: {}", e)))?;
+    Ok(response
+        .message
+        .map(|m| m.content.trim().to_string())
+        .unwrap_or_default())
+}
+
+// What one streamed chat produces, in order: the context report, then reasoning and answer chunks
+pub enum ChatEvent {
+    Context(context::ContextReport),
+    Thinking(String),
+    Chunk(Box<ChatResponse>),
+}
+
+// The chat pipeline behind `chat_ollama`: presets, context fitting, thinking split and stats.
+// Callers decide what to do with each event.
+pub async fn stream_chat<P: AppPaths + Clone + Send + 'static>(
+    paths: &P,
+    store: &ConversationStore,
+    client: &HttpClient,
+    request: &ChatRequest,
+    mut on_event: impl FnMut(ChatEvent) -> Result<(), ZamaError>,
+) -> Result<(), ZamaError> {
+    validate_model_name(&request.model)?;
+    if request.messages.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Chat request must include at least one message".to_string(),
+        ));
+    }
+
+    let url = format!("{}/api/chat", client.endpoints().ollama);
+
+    let mut stream_request = request.clone();
+    stream_request.stream = true; // Ensure streaming is enabled for the API call
+
+    // Whatever the request leaves unset comes from the conversation, model and global presets
+    let (presets, conversation) =
+        presets::load_for_chat(paths, store, stream_request.conversation_id.clone()).await?;
+    presets::apply(&presets, conversation.as_ref(), &mut stream_request);
+
+    // One lookup per turn serves both context sizing and the thinking check
+    let show = show_model(client, &request.model).await;
+
+    // Long conversations are trimmed to the model's window before Ollama silently truncates them
+    let settings = context::load_settings(paths);
+    let report = context::fit(
+        client,
+        &settings,
+        &request.model,
+        show.as_ref(),
+        &mut stream_request.messages,
+        context::num_ctx(stream_request.options.as_ref()),
+    )
+    .await;
+    on_event(ChatEvent::Context(report))?;
+
+    // Ask for the native thinking channel when the model has one; older models get their
+    // `<think>` tags split out below instead
+    if stream_request.think.is_none() && show.as_ref().is_some_and(thinking::supports_thinking) {
+        stream_request.think = Some(true);
+    }
+    let native_thinking = stream_request.think == Some(true);
+
+    let started = std::time::Instant::now();
+    let res = client
+        .post(&url)
+        .json(&stream_request)
+        .send()
+        .await
+        .map_err(|e| ZamaError::request(OLLAMA_SERVICE, e))?;
+
+    if !res.status().is_success() {
+        return Err(ZamaError::from_response(OLLAMA_SERVICE, res, Some(&request.model)).await);
+    }
+
+    let mut first_token = None;
+    let mut tags = ThinkTagParser::default();
+    // Empty reasoning isn't worth an event
+    let mut emit = |event: ChatEvent| -> Result<(), ZamaError> {
+        match &event {
+            ChatEvent::Thinking(content) if content.is_empty() => Ok(()),
+            _ => on_event(event),
+        }
+    };
+
+    http::for_each_line(OLLAMA_SERVICE, res, |line| {
+        let mut chat_response = match serde_json::from_str::<ChatResponse>(line) {
+            Ok(chat_response) => chat_response,
+            Err(e) => {
+                tracing::warn!(error = %e, chunk = %logging::content(line), "Failed to parse chat response chunk");
+                return Ok(());
+            }
+        };
+
+        if let Some(message) = chat_response.message.as_mut() {
+            let thinking = message.thinking.take().unwrap_or_default();
+            if first_token.is_none() && !(thinking.is_empty() && message.content.is_empty()) {
+                first_token = Some(started.elapsed());
+            }
+            emit(ChatEvent::Thinking(thinking))?;
+            if !native_thinking {
+                let split = tags.push(&message.content);
+                emit(ChatEvent::Thinking(split.thinking))?;
+                message.content = split.answer;
+            }
+        }
+        if chat_response.done {
+            // A partial tag held back at the very end was plain text after all
+            let rest = tags.finish();
+            emit(ChatEvent::Thinking(rest.thinking))?;
+            if !rest.answer.is_empty() {
+                chat_response
+                    .message
+                    .get_or_insert_with(|| Message {
+                        role: "assistant".to_string(),
+                        content: String::new(),
+                        thinking: None,
+                    })
+                    .content
+                    .push_str(&rest.answer);
+            }
+            chat_response.stats = Some(GenerationStats::from_final_chunk(
+                &chat_response,
+                first_token,
+            ));
+        }
+        emit(ChatEvent::Chunk(Box::new(chat_response)))
+    })
+    .await
+}
+
+// --- Local server ---
+
+// Runs `ollama pull`, handing each line of its progress output to `on_line`
+pub async fn pull_model(
+    model_name: &str,
+    mut on_line: impl FnMut(&str),
+) -> Result<String, ZamaError> {
+    // Validate input
+    validate_model_name(model_name)?;
+    let mut command = TokioCommand::new("ollama")
+        .arg("pull")
+        .arg(model_name)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| ZamaError::Io(format!("Failed to spawn ollama process: {}", e)))?;
+
+    let stdout = command.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = command.stderr.take().ok_or("Failed to capture stderr")?;
+
+    let mut stdout_reader = BufReader::new(stdout).lines();
+    let mut stderr_reader = BufReader::new(stderr).lines();
+
+    let mut output = String::new();
+    let mut record = |line: String| {
+        tracing::debug!(target: "ollama", "{}", line);
+        on_line(&line);
+        output.push_str(&line);
+        output.push('\n');
+    };
+
+    loop {
+        tokio::select! {
+            line = stdout_reader.next_line() => {
+                match line {
+                    Ok(Some(l)) => record(l),
+                    Ok(None) => break,
+                    Err(e) => return Err(ZamaError::Io(format!("Error reading stdout: {}", e))),
+                }
+            }
+            line = stderr_reader.next_line() => {
+                match line {
+                    Ok(Some(l)) => record(l),
+                    Ok(None) => break,
+                    Err(e) => return Err(ZamaError::Io(format!("Error reading stderr: {}", e))),
+                }
+            }
+        }
+    }
+
+    let status = command
+        .wait()
+        .await
+        .map_err(|e| ZamaError::Io(format!("Failed to wait for ollama process: {}", e)))?;
+
+    if status.success() {
+        Ok(output)
+    } else if output.contains("file does not exist") || output.contains("not found") {
+        Err(ZamaError::ModelNotFound {
+            model: model_name.to_string(),
+        })
+    } else {
+        Err(ZamaError::Internal(format!(
+            "Failed to pull model {}: {}\n{}",
+            model_name, status, output
+        )))
+    }
+}
+
+pub async fn is_running(client: &HttpClient) -> bool {
+    matches!(
+        timeout(Duration::from_secs(1), client.get(&client.endpoints().ollama).send()).await,
+        Ok(Ok(res)) if res.status().is_success()
+    )
+}
+
+pub async fn check_and_start(client: &HttpClient) -> Result<String, ZamaError> {
+    tracing::info!("Checking Ollama server");
+
+    // Check if Ollama is already running
+    if is_running(client).await {
+        return Ok("Ollama server is already running.".to_string());
+    }
+
+    // Ollama is not running, try to start it
+    tracing::info!("Ollama server not found, attempting to start it");
+    TokioCommand::new("ollama")
+        .arg("serve")
+        .spawn()
+        .map_err(|e| ZamaError::Io(format!("Failed to spawn ollama serve process: {}", e)))?;
+
+    // Give Ollama some time to start up
+    tokio::time::sleep(Duration::from_secs(5)).await;
+
+    // Verify if Ollama started successfully
+    match timeout(
+        Duration::from_secs(5),
+        client.get(&client.endpoints().ollama).send(),
+    )
+    .await
+    {
+        Ok(Ok(res)) if res.status().is_success() => {
+            Ok("Ollama server started successfully.".to_string())
+        }
+        _ => Err(ZamaError::ConnectionRefused {
+            service: OLLAMA_SERVICE.to_string(),
+        }),
+    }
+}
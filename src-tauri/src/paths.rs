@@ -0,0 +1,176 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::ZamaError;
+
+// Must match `identifier` in tauri.conf.json, it names the per-user app directories
+pub const IDENTIFIER: &str = "com.zama.desktop";
+
+// Where settings, conversation history and logs live. The desktop app asks Tauri; the CLI
+// resolves the very same directories itself so both work on the same files.
+pub trait AppPaths {
+    fn config_dir(&self) -> Result<PathBuf, String>;
+    fn data_dir(&self) -> Result<PathBuf, String>;
+    fn log_dir(&self) -> Result<PathBuf, String>;
+}
+
+impl<R: Runtime> AppPaths for AppHandle<R> {
+    fn config_dir(&self) -> Result<PathBuf, String> {
+        self.path()
+            .app_config_dir()
+            .map_err(|e| format!("Failed to resolve app config dir: {}", e))
+    }
+
+    fn data_dir(&self) -> Result<PathBuf, String> {
+        self.path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+    }
+
+    fn log_dir(&self) -> Result<PathBuf, String> {
+        self.path()
+            .app_log_dir()
+            .map_err(|e| format!("Failed to resolve app log dir: {}", e))
+    }
+}
+
+// Directories outside of a running Tauri app
+#[derive(Debug, Clone)]
+pub struct AppDirs {
+    pub config: PathBuf,
+    pub data: PathBuf,
+    pub log: PathBuf,
+}
+
+impl AppDirs {
+    // Mirrors Tauri's `app_config_dir`, `app_data_dir` and `app_log_dir`
+    pub fn system() -> Result<Self, String> {
+        let config = dirs::config_dir().ok_or("Failed to resolve app config dir")?;
+        let data = dirs::data_dir().ok_or("Failed to resolve app data dir")?;
+
+        #[cfg(target_os = "macos")]
+        let log = dirs::home_dir()
+            .ok_or("Failed to resolve app log dir")?
+            .join("Library/Logs")
+            .join(IDENTIFIER);
+        #[cfg(not(target_os = "macos"))]
+        let log = dirs::data_local_dir()
+            .ok_or("Failed to resolve app log dir")?
+            .join(IDENTIFIER)
+            .join("logs");
+
+        Ok(Self {
+            config: config.join(IDENTIFIER),
+            data: data.join(IDENTIFIER),
+            log,
+        })
+    }
+
+    // Everything under one directory, eg. for a throwaway profile
+    pub fn in_dir(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            config: root.join("config"),
+            data: root.join("data"),
+            log: root.join("logs"),
+        }
+    }
+}
+
+impl AppPaths for AppDirs {
+    fn config_dir(&self) -> Result<PathBuf, String> {
+        Ok(self.config.clone())
+    }
+
+    fn data_dir(&self) -> Result<PathBuf, String> {
+        Ok(self.data.clone())
+    }
+
+    fn log_dir(&self) -> Result<PathBuf, String> {
+        Ok(self.log.clone())
+    }
+}
+
+// --- JSON files ---
+
+// Reads and parses `path`; a missing file is `None` rather than an error
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, ZamaError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(ZamaError::Io(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| ZamaError::Parse(format!("Corrupt {}: {}", path.display(), e)))
+}
+
+// Writes through a temp file and a rename, so a crash never leaves a half-written file behind
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), ZamaError> {
+    write_json(path, value, false)
+}
+
+// Like `write_json_atomic`, but the file is only ever readable by the current user
+pub fn write_private_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), ZamaError> {
+    write_json(path, value, true)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T, private: bool) -> Result<(), ZamaError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| ZamaError::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+    }
+    let content = serde_json::to_string_pretty(value).map_err(|e| {
+        ZamaError::Internal(format!("Failed to serialize {}: {}", path.display(), e))
+    })?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content)
+        .map_err(|e| ZamaError::Io(format!("Failed to write {}: {}", tmp_path.display(), e)))?;
+    if private {
+        restrict_permissions(&tmp_path)?;
+    }
+    fs::rename(&tmp_path, path)
+        .map_err(|e| ZamaError::Io(format!("Failed to save {}: {}", path.display(), e)))
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<(), ZamaError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+        .map_err(|e| ZamaError::Io(format!("Failed to restrict {}: {}", path.display(), e)))
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<(), ZamaError> {
+    Ok(())
+}
+
+// A settings file in the config dir; missing or unreadable settings fall back to the defaults
+pub fn load_settings<T: DeserializeOwned + Default>(paths: &impl AppPaths, file: &str) -> T {
+    let Ok(path) = paths.config_dir().map(|dir| dir.join(file)) else {
+        return T::default();
+    };
+    load_json(&path)
+        .inspect_err(|e| tracing::warn!(error = %e, "Using default settings"))
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+pub fn save_settings<T: Serialize>(
+    paths: &impl AppPaths,
+    file: &str,
+    settings: &T,
+) -> Result<(), ZamaError> {
+    let path = paths.config_dir().map_err(ZamaError::Io)?.join(file);
+    write_json_atomic(&path, settings)
+}
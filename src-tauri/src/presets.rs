@@ -0,0 +1,418 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::conversations::ConversationStore;
+use crate::error::ZamaError;
+use crate::http::HttpState;
+use crate::ollama::{self, ChatRequest, Message, ShowModelRequest, ShowModelResponse};
+use crate::paths::{self, AppPaths};
+
+const SETTINGS_FILE: &str = "presets.json";
+const SYSTEM_KEY: &str = "system";
+
+// Sampling and runtime parameters, as sent to Ollama. Unset keys fall through to the next level.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ParameterSet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>, // System prompt, used when the chat doesn't bring its own
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub options: Map<String, Value>, // Ollama options, eg. `temperature`, `num_ctx`, `top_p`
+}
+
+impl ParameterSet {
+    fn is_empty(&self) -> bool {
+        self.system.is_none() && self.options.is_empty()
+    }
+
+    // Drops empty values and rejects what Ollama wouldn't accept as an option
+    fn cleaned(self) -> Result<Self, ZamaError> {
+        let mut options = Map::new();
+        for (key, value) in self.options {
+            let key = key.trim().to_string();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+                return Err(ZamaError::InvalidInput(format!(
+                    "'{}' is not an Ollama option",
+                    key
+                )));
+            }
+            let valid = match &value {
+                Value::Null => continue,
+                Value::Bool(_) | Value::Number(_) | Value::String(_) => true,
+                Value::Array(items) => items.iter().all(Value::is_string),
+                Value::Object(_) => false,
+            };
+            if !valid {
+                return Err(ZamaError::InvalidInput(format!(
+                    "Unsupported value for option '{}'",
+                    key
+                )));
+            }
+            options.insert(key, value);
+        }
+        Ok(Self {
+            system: self.system.filter(|s| !s.trim().is_empty()),
+            options,
+        })
+    }
+}
+
+// Global defaults plus per-model presets. Model keys are a full name (`llama3.2:3b`), a name
+// without tag matching every tag (`llama3.2`), or a pattern with `*` (`*coder*`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Presets {
+    pub global: ParameterSet,
+    pub models: BTreeMap<String, ParameterSet>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionSource {
+    Request, // Set explicitly on the chat request
+    Conversation,
+    Model,
+    Global,
+    Modelfile, // Baked into the model; Ollama applies these itself
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResolvedValue {
+    pub key: String, // An Ollama option, or `system`
+    pub value: Value,
+    pub source: OptionSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>, // Model preset key the value came from
+    pub overrides: Vec<OptionSource>, // Lower levels that also set this key
+}
+
+// The effective parameters for a chat and where each one came from
+#[derive(Debug, Serialize, Clone)]
+pub struct ResolvedOptions {
+    pub model: String,
+    pub options: Map<String, Value>,
+    pub system: Option<String>,
+    pub values: Vec<ResolvedValue>, // Sorted by key
+}
+
+pub fn load_presets(paths: &impl AppPaths) -> Presets {
+    paths::load_settings(paths, SETTINGS_FILE)
+}
+
+fn save_presets(paths: &impl AppPaths, presets: &Presets) -> Result<(), ZamaError> {
+    paths::save_settings(paths, SETTINGS_FILE, presets)
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if name.len() < first.len() + last.len() || !name.starts_with(first) || !name.ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+// How closely a preset key matches `model`, higher is closer; None if it doesn't apply
+fn specificity(key: &str, model: &str) -> Option<usize> {
+    if key == model {
+        return Some(usize::MAX);
+    }
+    if key.contains('*') {
+        return wildcard_match(key, model).then(|| key.chars().filter(|c| *c != '*').count());
+    }
+    let base = model.split(':').next().unwrap_or(model);
+    (!key.contains(':') && key == base).then_some(usize::MAX - 1)
+}
+
+// Model presets that apply to `model`, closest first
+fn model_presets<'a>(presets: &'a Presets, model: &str) -> Vec<(&'a String, &'a ParameterSet)> {
+    let mut matches: Vec<(usize, &String, &ParameterSet)> = presets
+        .models
+        .iter()
+        .filter_map(|(key, set)| Some((specificity(key, model)?, key, set)))
+        .collect();
+    matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+    matches
+        .into_iter()
+        .map(|(_, key, set)| (key, set))
+        .collect()
+}
+
+// `PARAMETER` values from `/api/show`, eg. `temperature 0.7`. `stop` may repeat and becomes a list.
+pub fn modelfile_parameters(show: &ShowModelResponse) -> Map<String, Value> {
+    let from_parameters: Vec<&str> = show.parameters.lines().collect();
+    let lines: Vec<&str> = if from_parameters.is_empty() {
+        show.modelfile
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("PARAMETER "))
+            .collect()
+    } else {
+        from_parameters
+    };
+
+    let mut options = Map::new();
+    for line in lines {
+        let Some((key, raw)) = line.trim().split_once(char::is_whitespace) else {
+            continue;
+        };
+        let raw = raw.trim().trim_matches('"');
+        if key == "stop" {
+            if let Value::Array(stops) = options
+                .entry(key)
+                .or_insert_with(|| Value::Array(Vec::new()))
+            {
+                stops.push(Value::String(raw.to_string()));
+            }
+            continue;
+        }
+        let value = raw
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| raw.parse::<f64>().map(Value::from))
+            .or_else(|_| raw.parse::<bool>().map(Value::from))
+            .unwrap_or_else(|_| Value::String(raw.to_string()));
+        options.insert(key.to_string(), value);
+    }
+    options
+}
+
+// Layers from highest to lowest priority: request, conversation, model presets, global, Modelfile
+pub fn resolve(
+    presets: &Presets,
+    model: &str,
+    request: Option<&Map<String, Value>>,
+    conversation: Option<&ParameterSet>,
+    modelfile: Option<&Map<String, Value>>,
+) -> ResolvedOptions {
+    let mut layers: Vec<(OptionSource, Option<&String>, Map<String, Value>)> = Vec::new();
+    if let Some(request) = request {
+        layers.push((OptionSource::Request, None, request.clone()));
+    }
+    let with_system = |set: &ParameterSet| {
+        let mut values = set.options.clone();
+        if let Some(system) = &set.system {
+            values.insert(SYSTEM_KEY.to_string(), Value::String(system.clone()));
+        }
+        values
+    };
+    if let Some(conversation) = conversation {
+        layers.push((OptionSource::Conversation, None, with_system(conversation)));
+    }
+    for (key, set) in model_presets(presets, model) {
+        layers.push((OptionSource::Model, Some(key), with_system(set)));
+    }
+    layers.push((OptionSource::Global, None, with_system(&presets.global)));
+    if let Some(modelfile) = modelfile {
+        layers.push((OptionSource::Modelfile, None, modelfile.clone()));
+    }
+
+    let mut resolved: BTreeMap<String, ResolvedValue> = BTreeMap::new();
+    for (source, preset, values) in layers {
+        for (key, value) in values {
+            if value.is_null() {
+                continue;
+            }
+            match resolved.entry(key) {
+                Entry::Occupied(mut winner) => {
+                    let overrides = &mut winner.get_mut().overrides;
+                    if !overrides.contains(&source) {
+                        overrides.push(source);
+                    }
+                }
+                Entry::Vacant(slot) => {
+                    let key = slot.key().clone();
+                    slot.insert(ResolvedValue {
+                        key,
+                        value,
+                        source,
+                        preset: preset.cloned(),
+                        overrides: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut system = None;
+    let mut options = Map::new();
+    for value in resolved.values() {
+        match (value.key.as_str(), &value.value) {
+            (SYSTEM_KEY, Value::String(s)) => system = Some(s.clone()),
+            (SYSTEM_KEY, _) => {}
+            (key, v) => {
+                options.insert(key.to_string(), v.clone());
+            }
+        }
+    }
+    ResolvedOptions {
+        model: model.to_string(),
+        options,
+        system,
+        values: resolved.into_values().collect(),
+    }
+}
+
+// The saved presets and the conversation's own overrides, read on the blocking pool. `store` is
+// the managed one in the app, so reads take its lock.
+pub async fn load_for_chat<P: AppPaths + Clone + Send + 'static>(
+    paths: &P,
+    store: &ConversationStore,
+    conversation_id: Option<String>,
+) -> Result<(Presets, Option<ParameterSet>), ZamaError> {
+    let paths = paths.clone();
+    store
+        .run(move |store| {
+            let conversation = match conversation_id {
+                Some(id) => store.load(&id)?.parameters,
+                None => None,
+            };
+            Ok((load_presets(&paths), conversation))
+        })
+        .await
+}
+
+// Fills in what `request` leaves unset from the conversation, model and global presets. The
+// Modelfile level is skipped since Ollama applies it anyway.
+pub fn apply(presets: &Presets, conversation: Option<&ParameterSet>, request: &mut ChatRequest) {
+    let resolved = resolve(
+        presets,
+        &request.model,
+        request.options.as_ref(),
+        conversation,
+        None,
+    );
+    if !resolved.options.is_empty() {
+        request.options = Some(resolved.options);
+    }
+    if let Some(system) = resolved.system {
+        if !request.messages.iter().any(|m| m.role == "system") {
+            request.messages.insert(
+                0,
+                Message {
+                    role: "system".to_string(),
+                    content: system,
+                    thinking: None,
+                },
+            );
+        }
+    }
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub async fn get_presets<R: Runtime>(app_handle: AppHandle<R>) -> Result<Presets, ZamaError> {
+    Ok(tokio::task::spawn_blocking(move || load_presets(&app_handle)).await?)
+}
+
+#[tauri::command]
+pub async fn set_global_preset<R: Runtime>(
+    app_handle: AppHandle<R>,
+    preset: ParameterSet,
+) -> Result<Presets, ZamaError> {
+    let preset = preset.cleaned()?;
+    tokio::task::spawn_blocking(move || {
+        let mut presets = load_presets(&app_handle);
+        presets.global = preset;
+        save_presets(&app_handle, &presets)?;
+        Ok(presets)
+    })
+    .await?
+}
+
+// Saves the preset for a model name or pattern; an empty or missing preset removes it
+#[tauri::command]
+pub async fn set_model_preset<R: Runtime>(
+    app_handle: AppHandle<R>,
+    model: String,
+    preset: Option<ParameterSet>,
+) -> Result<Presets, ZamaError> {
+    let model = model.trim().to_string();
+    let name = model.replace('*', "");
+    if model.is_empty() || !name.is_empty() {
+        ollama::validate_model_name(&name)?;
+    }
+    let preset = preset.map(ParameterSet::cleaned).transpose()?;
+    tokio::task::spawn_blocking(move || {
+        let mut presets = load_presets(&app_handle);
+        match preset {
+            Some(preset) if !preset.is_empty() => {
+                presets.models.insert(model, preset);
+            }
+            _ => {
+                presets.models.remove(&model);
+            }
+        }
+        save_presets(&app_handle, &presets)?;
+        Ok(presets)
+    })
+    .await?
+}
+
+// Overrides saved with the conversation; an empty or missing set clears them
+#[tauri::command]
+pub async fn set_conversation_parameters(
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+    parameters: Option<ParameterSet>,
+) -> Result<Option<ParameterSet>, ZamaError> {
+    let parameters = parameters
+        .map(ParameterSet::cleaned)
+        .transpose()?
+        .filter(|p| !p.is_empty());
+    store
+        .run(move |store| {
+            let (conversation, _) = store.update(&conversation_id, |conversation| {
+                conversation.parameters = parameters;
+                Ok(())
+            })?;
+            Ok(conversation.parameters)
+        })
+        .await
+}
+
+// The parameters the next chat with `model` would run with, and which level set each of them.
+// `options` are the ones the chat request itself would carry.
+#[tauri::command]
+pub async fn resolve_options<R: Runtime>(
+    app_handle: AppHandle<R>,
+    http: tauri::State<'_, HttpState>,
+    model: String,
+    conversation_id: Option<String>,
+    options: Option<Map<String, Value>>,
+) -> Result<ResolvedOptions, ZamaError> {
+    ollama::validate_model_name(&model)?;
+    let store = app_handle.state::<ConversationStore>();
+    let (presets, conversation) = load_for_chat(&app_handle, &store, conversation_id).await?;
+    let modelfile = match ollama::fetch_model_info(
+        &http.client(),
+        ShowModelRequest {
+            name: model.clone(),
+        },
+    )
+    .await
+    {
+        Ok(show) => Some(modelfile_parameters(&show)),
+        Err(e) => {
+            tracing::debug!(%model, error = %e, "Could not read Modelfile parameters");
+            None
+        }
+    };
+    Ok(resolve(
+        &presets,
+        &model,
+        options.as_ref(),
+        conversation.as_ref(),
+        modelfile.as_ref(),
+    ))
+}
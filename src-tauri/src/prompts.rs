@@ -0,0 +1,580 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::conversations::{new_id, unix_millis};
+use crate::error::ZamaError;
+use crate::paths;
+
+const PROMPTS_FILE: &str = "prompts.json";
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptKind {
+    #[default]
+    System, // Goes into the system prompt
+    User, // Fills the message box
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptFormat {
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptVersion {
+    pub version: u32, // Starts at 1
+    pub content: String,
+    pub created_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>, // What changed
+}
+
+// A named prompt with its full edit history; the last version is the current one
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Prompt {
+    pub id: String,
+    pub name: String,
+    pub kind: PromptKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub versions: Vec<PromptVersion>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl Prompt {
+    fn current(&self) -> &PromptVersion {
+        self.versions
+            .last()
+            .expect("prompts are saved with at least one version")
+    }
+
+    pub(crate) fn version(&self, version: Option<u32>) -> Result<&PromptVersion, ZamaError> {
+        match version {
+            None => Ok(self.current()),
+            Some(v) => self
+                .versions
+                .iter()
+                .find(|p| p.version == v)
+                .ok_or_else(|| {
+                    ZamaError::InvalidInput(format!("Prompt '{}' has no version {}", self.name, v))
+                }),
+        }
+    }
+
+    // Adds `content` as a new version unless it's what the prompt already says
+    fn push_version(&mut self, content: String, note: Option<String>, created_at: u64) -> bool {
+        if self.versions.last().is_some_and(|v| v.content == content) {
+            return false;
+        }
+        self.versions.push(PromptVersion {
+            version: self.versions.last().map_or(1, |v| v.version + 1),
+            content,
+            created_at,
+            note,
+        });
+        true
+    }
+
+    fn view(&self) -> PromptView {
+        let current = self.current();
+        PromptView {
+            prompt: self.clone(),
+            content: current.content.clone(),
+            version: current.version,
+            variables: variables(&current.content),
+        }
+    }
+}
+
+// What the UI gets: the prompt plus its current content and the variables that need values
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptView {
+    #[serde(flatten)]
+    pub prompt: Prompt,
+    pub content: String,
+    pub version: u32,
+    pub variables: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptInput {
+    pub id: Option<String>, // None creates a new prompt
+    pub name: String,
+    #[serde(default)]
+    pub kind: PromptKind,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub content: String,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedPrompt {
+    pub id: String,
+    pub name: String,
+    pub kind: PromptKind,
+    pub version: u32,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PromptImportResult {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptExport<'a> {
+    format_version: u32,
+    exported_at: u64,
+    prompts: Vec<&'a Prompt>,
+}
+
+// Exports read back as-is; hand-written files only need `name` and `content`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ImportDocument {
+    File { prompts: Vec<ImportedPrompt> },
+    List(Vec<ImportedPrompt>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportedPrompt {
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    kind: PromptKind,
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    content: Option<String>,
+    #[serde(default)]
+    versions: Vec<ImportedVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportedVersion {
+    content: String,
+    created_at: Option<u64>,
+    note: Option<String>,
+}
+
+// `{{ name }}` placeholders, in order of first use. Names are letters, digits, `_`, `-` and `.`;
+// anything else between braces is left as text.
+pub fn variables(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for_each_placeholder(content, |name| {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        None
+    });
+    names
+}
+
+pub fn render(content: &str, vars: &HashMap<String, String>) -> Result<String, ZamaError> {
+    let missing: Vec<String> = variables(content)
+        .into_iter()
+        .filter(|name| !vars.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(ZamaError::InvalidInput(format!(
+            "Missing values for: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(for_each_placeholder(content, |name| {
+        vars.get(name).cloned()
+    }))
+}
+
+// Calls `f` for every placeholder and substitutes what it returns; `None` keeps the placeholder
+fn for_each_placeholder(content: &str, mut f: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let raw = &rest[start..start + 2 + len + 2];
+        let name = raw[2..raw.len() - 2].trim();
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        out.push_str(&rest[..start]);
+        match valid.then(|| f(name)).flatten() {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(raw),
+        }
+        rest = &rest[start + raw.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn clean_tags(tags: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !cleaned.contains(&tag) {
+            cleaned.push(tag);
+        }
+    }
+    cleaned
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// --- Store ---
+
+// The whole library in one JSON file under the app data dir; small enough to rewrite per change.
+// Clones share the lock.
+#[derive(Clone)]
+pub struct PromptLibrary {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl PromptLibrary {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    // Runs `f` on the blocking pool, since every library operation reads or writes the file
+    pub async fn run<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&PromptLibrary) -> Result<T, ZamaError> + Send + 'static,
+    ) -> Result<T, ZamaError> {
+        let library = self.clone();
+        tokio::task::spawn_blocking(move || f(&library)).await?
+    }
+
+    fn load(&self) -> Result<Vec<Prompt>, ZamaError> {
+        Ok(paths::load_json(&self.path)?.unwrap_or_default())
+    }
+
+    fn save(&self, prompts: &[Prompt]) -> Result<(), ZamaError> {
+        paths::write_json_atomic(&self.path, &prompts)
+    }
+
+    pub fn list(&self) -> Result<Vec<Prompt>, ZamaError> {
+        let _guard = self.lock.lock().unwrap();
+        self.load()
+    }
+
+    pub fn get(&self, id: &str) -> Result<Prompt, ZamaError> {
+        self.list()?
+            .into_iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| ZamaError::InvalidInput(format!("Prompt {} not found", id)))
+    }
+
+    // Applies `f` to the whole library and persists the result
+    fn update<T>(
+        &self,
+        f: impl FnOnce(&mut Vec<Prompt>) -> Result<T, ZamaError>,
+    ) -> Result<T, ZamaError> {
+        let _guard = self.lock.lock().unwrap();
+        let mut prompts = self.load()?;
+        let result = f(&mut prompts)?;
+        self.save(&prompts)?;
+        Ok(result)
+    }
+}
+
+pub fn init<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PromptLibrary, String> {
+    let path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?
+        .join(PROMPTS_FILE);
+    Ok(PromptLibrary::new(path))
+}
+
+// --- Commands ---
+
+// Sorted by name; `tag` and `kind` narrow the list
+#[tauri::command]
+pub async fn list_prompts(
+    library: tauri::State<'_, PromptLibrary>,
+    tag: Option<String>,
+    kind: Option<PromptKind>,
+) -> Result<Vec<PromptView>, ZamaError> {
+    let tag = tag.map(|t| t.trim().to_lowercase());
+    let mut prompts: Vec<PromptView> = library
+        .run(|library| library.list())
+        .await?
+        .iter()
+        .filter(|p| kind.is_none_or(|k| p.kind == k))
+        .filter(|p| tag.as_ref().is_none_or(|t| p.tags.contains(t)))
+        .map(Prompt::view)
+        .collect();
+    prompts.sort_by_key(|p| p.prompt.name.to_lowercase());
+    Ok(prompts)
+}
+
+#[tauri::command]
+pub async fn get_prompt(
+    library: tauri::State<'_, PromptLibrary>,
+    id: String,
+) -> Result<PromptView, ZamaError> {
+    library
+        .run(move |library| Ok(library.get(&id)?.view()))
+        .await
+}
+
+// Creates a prompt, or updates one and records a new version when its content changed
+#[tauri::command]
+pub async fn save_prompt(
+    library: tauri::State<'_, PromptLibrary>,
+    prompt: PromptInput,
+) -> Result<PromptView, ZamaError> {
+    let name = prompt.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Prompt name cannot be empty".to_string(),
+        ));
+    }
+    if prompt.content.trim().is_empty() {
+        return Err(ZamaError::InvalidInput(
+            "Prompt content cannot be empty".to_string(),
+        ));
+    }
+    let now = unix_millis();
+    let saved = library
+        .run(move |library| {
+            library.update(|prompts| {
+                let existing = match &prompt.id {
+                    Some(id) => {
+                        Some(prompts.iter_mut().find(|p| &p.id == id).ok_or_else(|| {
+                            ZamaError::InvalidInput(format!("Prompt {} not found", id))
+                        })?)
+                    }
+                    None => None,
+                };
+                let saved = match existing {
+                    Some(existing) => {
+                        existing.name = name;
+                        existing.kind = prompt.kind;
+                        existing.description = non_empty(prompt.description);
+                        existing.tags = clean_tags(prompt.tags);
+                        existing.push_version(prompt.content, non_empty(prompt.note), now);
+                        existing.updated_at = now;
+                        existing.clone()
+                    }
+                    None => {
+                        let mut created = Prompt {
+                            id: new_id("prompt"),
+                            name,
+                            kind: prompt.kind,
+                            description: non_empty(prompt.description),
+                            tags: clean_tags(prompt.tags),
+                            versions: Vec::new(),
+                            created_at: now,
+                            updated_at: now,
+                        };
+                        created.push_version(prompt.content, non_empty(prompt.note), now);
+                        prompts.push(created.clone());
+                        created
+                    }
+                };
+                Ok(saved)
+            })
+        })
+        .await?;
+    tracing::info!(prompt = %saved.id, version = saved.current().version, "Saved prompt");
+    Ok(saved.view())
+}
+
+#[tauri::command]
+pub async fn delete_prompt(
+    library: tauri::State<'_, PromptLibrary>,
+    id: String,
+) -> Result<(), ZamaError> {
+    library
+        .run(move |library| {
+            library.update(|prompts| {
+                prompts.retain(|p| p.id != id);
+                Ok(())
+            })
+        })
+        .await
+}
+
+// Fills in the `{{variables}}` of a prompt; every variable needs a value. `version` defaults to
+// the current one.
+#[tauri::command]
+pub async fn render_prompt(
+    library: tauri::State<'_, PromptLibrary>,
+    id: String,
+    vars: Option<HashMap<String, String>>,
+    version: Option<u32>,
+) -> Result<RenderedPrompt, ZamaError> {
+    let prompt = library.run(move |library| library.get(&id)).await?;
+    let selected = prompt.version(version)?;
+    Ok(RenderedPrompt {
+        content: render(&selected.content, &vars.unwrap_or_default())?,
+        version: selected.version,
+        id: prompt.id,
+        name: prompt.name,
+        kind: prompt.kind,
+    })
+}
+
+// The selected prompts (default: all) with their versions, as a JSON or YAML document
+#[tauri::command]
+pub async fn export_prompts(
+    library: tauri::State<'_, PromptLibrary>,
+    format: PromptFormat,
+    ids: Option<Vec<String>>,
+) -> Result<String, ZamaError> {
+    let prompts = library.run(|library| library.list()).await?;
+    let export = PromptExport {
+        format_version: EXPORT_FORMAT_VERSION,
+        exported_at: unix_millis(),
+        prompts: prompts
+            .iter()
+            .filter(|p| ids.as_ref().is_none_or(|ids| ids.contains(&p.id)))
+            .collect(),
+    };
+    match format {
+        PromptFormat::Json => serde_json::to_string_pretty(&export)
+            .map_err(|e| ZamaError::Internal(format!("Failed to export prompts: {}", e))),
+        PromptFormat::Yaml => serde_yaml::to_string(&export)
+            .map_err(|e| ZamaError::Internal(format!("Failed to export prompts: {}", e))),
+    }
+}
+
+// Reads a JSON or YAML export, or a hand-written list. Prompts are matched by id, then by name;
+// versions that aren't in the library yet are added on top of the existing history.
+#[tauri::command]
+pub async fn import_prompts(
+    library: tauri::State<'_, PromptLibrary>,
+    content: String,
+) -> Result<PromptImportResult, ZamaError> {
+    let document: ImportDocument = serde_json::from_str(&content)
+        .or_else(|_| serde_yaml::from_str(&content))
+        .map_err(|e| ZamaError::InvalidInput(format!("Not a prompt library file: {}", e)))?;
+    let incoming = match document {
+        ImportDocument::File { prompts } | ImportDocument::List(prompts) => prompts,
+    };
+
+    let now = unix_millis();
+    let result = library
+        .run(move |library| {
+            library.update(|prompts| {
+                let mut result = PromptImportResult::default();
+                for imported in incoming {
+                    let name = imported.name.trim().to_string();
+                    let mut versions = imported.versions;
+                    if let Some(content) = imported.content.filter(|c| !c.trim().is_empty()) {
+                        if versions.last().is_none_or(|v| v.content != content) {
+                            versions.push(ImportedVersion {
+                                content,
+                                created_at: None,
+                                note: None,
+                            });
+                        }
+                    }
+                    if name.is_empty() || versions.is_empty() {
+                        continue;
+                    }
+
+                    let position = imported
+                        .id
+                        .as_ref()
+                        .and_then(|id| prompts.iter().position(|p| &p.id == id))
+                        .or_else(|| {
+                            prompts
+                                .iter()
+                                .position(|p| p.name.eq_ignore_ascii_case(&name))
+                        });
+                    let is_new = position.is_none();
+                    let prompt = match position {
+                        Some(i) => &mut prompts[i],
+                        None => {
+                            prompts.push(Prompt {
+                                id: new_id("prompt"),
+                                name: name.clone(),
+                                kind: imported.kind,
+                                description: None,
+                                tags: Vec::new(),
+                                versions: Vec::new(),
+                                created_at: now,
+                                updated_at: now,
+                            });
+                            prompts.last_mut().unwrap()
+                        }
+                    };
+
+                    let mut changed = false;
+                    for version in versions {
+                        if prompt.versions.iter().any(|v| v.content == version.content) {
+                            continue;
+                        }
+                        changed |= prompt.push_version(
+                            version.content,
+                            non_empty(version.note),
+                            version.created_at.unwrap_or(now),
+                        );
+                    }
+                    let description = non_empty(imported.description);
+                    let tags = clean_tags(imported.tags);
+                    if prompt.kind != imported.kind
+                        || prompt.description != description
+                        || prompt.tags != tags
+                    {
+                        prompt.kind = imported.kind;
+                        prompt.description = description;
+                        prompt.tags = tags;
+                        changed = true;
+                    }
+                    prompt.name = name;
+
+                    if is_new {
+                        result.created += 1;
+                    } else if changed {
+                        prompt.updated_at = now;
+                        result.updated += 1;
+                    } else {
+                        result.unchanged += 1;
+                    }
+                }
+                Ok(result)
+            })
+        })
+        .await?;
+    tracing::info!(
+        created = result.created,
+        updated = result.updated,
+        "Imported prompts"
+    );
+    Ok(result)
+}
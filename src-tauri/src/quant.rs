@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+// Approximate bits per weight for each llama.cpp quantization, including block scales.
+// Longer names come before their prefixes so that eg. `Q4_K_M` is matched before `Q4_K`.
+const QUANT_BITS: &[(&str, f64)] = &[
+    ("IQ3_XXS", 3.06),
+    ("IQ2_XXS", 2.06),
+    ("Q4_0_4_4", 4.5),
+    ("Q4_0_4_8", 4.5),
+    ("Q4_0_8_8", 4.5),
+    ("Q2_K_XL", 3.0),
+    ("Q3_K_XL", 4.3),
+    ("Q4_K_XL", 4.9),
+    ("Q5_K_XL", 5.7),
+    ("Q6_K_XL", 6.6),
+    ("Q8_K_XL", 8.6),
+    ("IQ4_XS", 4.25),
+    ("IQ4_NL", 4.5),
+    ("IQ3_XS", 3.3),
+    ("IQ2_XS", 2.31),
+    ("Q2_K_S", 2.7),
+    ("Q3_K_L", 4.27),
+    ("Q3_K_M", 3.91),
+    ("Q3_K_S", 3.5),
+    ("Q4_K_M", 4.85),
+    ("Q4_K_S", 4.58),
+    ("Q5_K_M", 5.69),
+    ("Q5_K_S", 5.54),
+    ("IQ3_S", 3.44),
+    ("IQ3_M", 3.66),
+    ("IQ2_S", 2.5),
+    ("IQ2_M", 2.7),
+    ("IQ1_S", 1.56),
+    ("IQ1_M", 1.75),
+    ("TQ1_0", 1.69),
+    ("TQ2_0", 2.06),
+    ("Q2_K", 2.96),
+    ("Q3_K", 3.91),
+    ("Q4_K", 4.85),
+    ("Q5_K", 5.69),
+    ("Q6_K", 6.56),
+    ("Q8_K", 8.5),
+    ("Q4_0", 4.5),
+    ("Q4_1", 5.0),
+    ("Q5_0", 5.5),
+    ("Q5_1", 6.0),
+    ("Q8_0", 8.5),
+    ("BF16", 16.0),
+    ("F16", 16.0),
+    ("FP16", 16.0),
+    ("F32", 32.0),
+    ("FP32", 32.0),
+];
+
+// Compute buffers and a small default context on top of the weights
+const RUNTIME_OVERHEAD_RATIO: f64 = 0.15;
+const RUNTIME_OVERHEAD_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuantType {
+    pub name: String,
+    pub bits_per_weight: f64,
+}
+
+impl QuantType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let upper = name.trim().to_ascii_uppercase();
+        QUANT_BITS
+            .iter()
+            .find(|(quant, _)| *quant == upper)
+            .map(|(quant, bits)| QuantType {
+                name: quant.to_string(),
+                bits_per_weight: *bits,
+            })
+    }
+
+    // Finds the quantization in a file name such as `Llama-3.2-3B-Instruct-Q4_K_M.gguf` or
+    // `model.iq3_xs-00001-of-00002.gguf`. The match must be delimited by non-alphanumerics.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let stem = filename.rsplit('/').next().unwrap_or(filename);
+        let upper = stem.to_ascii_uppercase();
+        let bytes = upper.as_bytes();
+
+        QUANT_BITS.iter().find_map(|(quant, bits)| {
+            upper.match_indices(quant).find_map(|(start, _)| {
+                let end = start + quant.len();
+                let bounded_left = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+                let bounded_right = end == bytes.len() || !bytes[end].is_ascii_alphanumeric();
+                (bounded_left && bounded_right).then(|| QuantType {
+                    name: quant.to_string(),
+                    bits_per_weight: *bits,
+                })
+            })
+        })
+    }
+
+    pub fn weight_bytes(&self, parameter_count: u64) -> u64 {
+        (parameter_count as f64 * self.bits_per_weight / 8.0).round() as u64
+    }
+}
+
+// Rough memory needed to load a model file of `file_size` bytes with a small context
+pub fn estimate_runtime_bytes(file_size: u64) -> u64 {
+    file_size + (file_size as f64 * RUNTIME_OVERHEAD_RATIO) as u64 + RUNTIME_OVERHEAD_BYTES
+}
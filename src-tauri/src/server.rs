@@ -0,0 +1,489 @@
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::{oneshot, Mutex};
+
+use crate::credentials::{self, Provider};
+use crate::error::ZamaError;
+use crate::http::{self, HttpClient};
+use crate::ollama::OLLAMA_SERVICE;
+use crate::{paths, GEMINI_SERVICE};
+
+const SETTINGS_FILE: &str = "server.json";
+const DEFAULT_PORT: u16 = 11435; // Next to Ollama's 11434
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024; // Room for base64 images in chat requests
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5); // Then open streams are cut off
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: Option<String>, // Bearer token clients must send; generated on first enable
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    pub base_url: Option<String>, // Eg. `http://127.0.0.1:11435/v1`, set while running
+    pub token: Option<String>,
+}
+
+struct RunningServer {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    task: tauri::async_runtime::JoinHandle<()>, // Holds the listener until it finishes
+}
+
+#[derive(Default)]
+pub struct ServerState {
+    running: Mutex<Option<RunningServer>>,
+}
+
+struct ServerContext<R: Runtime> {
+    app_handle: AppHandle<R>,
+    token: String,
+}
+
+// Derived `Clone` would also require `R: Clone`
+impl<R: Runtime> Clone for ServerContext<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app_handle: self.app_handle.clone(),
+            token: self.token.clone(),
+        }
+    }
+}
+
+// --- Settings ---
+
+pub fn load_settings<R: Runtime>(app_handle: &AppHandle<R>) -> ServerSettings {
+    paths::load_settings(app_handle, SETTINGS_FILE)
+}
+
+fn save_settings<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    settings: &ServerSettings,
+) -> Result<(), ZamaError> {
+    paths::save_settings(app_handle, SETTINGS_FILE, settings)
+}
+
+fn generate_token() -> Result<String, ZamaError> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| ZamaError::Internal(format!("Failed to generate server token: {}", e)))?;
+    Ok(format!(
+        "zama-{}",
+        bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    ))
+}
+
+// --- Lifecycle ---
+
+async fn start<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    settings: &ServerSettings,
+) -> Result<SocketAddr, ZamaError> {
+    let token = settings
+        .token
+        .clone()
+        .ok_or_else(|| ZamaError::Internal("Server token missing".to_string()))?;
+    let state = app_handle.state::<ServerState>();
+    let mut running = state.running.lock().await;
+    if let Some(server) = running.as_ref() {
+        return Ok(server.addr);
+    }
+
+    // Loopback only; other machines on the network never see the server
+    let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, settings.port))
+        .await
+        .map_err(|e| ZamaError::Io(format!("Failed to bind port {}: {}", settings.port, e)))?;
+    let addr = listener.local_addr()?;
+    let router = router(ServerContext {
+        app_handle: app_handle.clone(),
+        token,
+    });
+
+    let (shutdown, shutdown_rx) = oneshot::channel();
+    let task = tauri::async_runtime::spawn(async move {
+        let result = axum::serve(listener, router)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+        if let Err(e) = result {
+            tracing::error!(error = %e, "Local API server stopped");
+        }
+    });
+
+    tracing::info!(%addr, "Local API server listening");
+    *running = Some(RunningServer {
+        addr,
+        shutdown,
+        task,
+    });
+    Ok(addr)
+}
+
+async fn stop<R: Runtime>(app_handle: &AppHandle<R>) {
+    let state = app_handle.state::<ServerState>();
+    let server = state.running.lock().await.take();
+    if let Some(mut server) = server {
+        let _ = server.shutdown.send(());
+        // Wait for the port to be released so a restart on the same port can bind it
+        if tokio::time::timeout(SHUTDOWN_GRACE, &mut server.task)
+            .await
+            .is_err()
+        {
+            server.task.abort();
+            let _ = server.task.await;
+        }
+        tracing::info!(addr = %server.addr, "Local API server stopped");
+    }
+}
+
+// Called on launch; the server stays off unless the user turned it on
+pub async fn start_if_enabled<R: Runtime>(app_handle: AppHandle<R>) {
+    let settings = load_settings(&app_handle);
+    if settings.enabled {
+        if let Err(e) = start(&app_handle, &settings).await {
+            tracing::error!(error = %e, "Failed to start local API server");
+        }
+    }
+}
+
+async fn status<R: Runtime>(app_handle: &AppHandle<R>) -> ServerStatus {
+    let settings = load_settings(app_handle);
+    let state = app_handle.state::<ServerState>();
+    let addr = state.running.lock().await.as_ref().map(|s| s.addr);
+    ServerStatus {
+        enabled: settings.enabled,
+        running: addr.is_some(),
+        port: settings.port,
+        base_url: addr.map(|addr| format!("http://{}/v1", addr)),
+        token: settings.token,
+    }
+}
+
+#[tauri::command]
+pub async fn get_server_status<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<ServerStatus, ZamaError> {
+    Ok(status(&app_handle).await)
+}
+
+#[tauri::command]
+pub async fn set_server_settings<R: Runtime>(
+    app_handle: AppHandle<R>,
+    enabled: Option<bool>,
+    port: Option<u16>,
+) -> Result<ServerStatus, ZamaError> {
+    let mut settings = load_settings(&app_handle);
+    if let Some(port) = port {
+        if port < 1024 {
+            return Err(ZamaError::InvalidInput(
+                "Port must be 1024 or higher".to_string(),
+            ));
+        }
+        settings.port = port;
+    }
+    if let Some(enabled) = enabled {
+        settings.enabled = enabled;
+    }
+    if settings.enabled && settings.token.is_none() {
+        settings.token = Some(generate_token()?);
+    }
+    restart(&app_handle, settings).await
+}
+
+// Invalidates the old token immediately
+#[tauri::command]
+pub async fn regenerate_server_token<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<ServerStatus, ZamaError> {
+    let mut settings = load_settings(&app_handle);
+    settings.token = Some(generate_token()?);
+    restart(&app_handle, settings).await
+}
+
+// Restarts so port and token changes take effect, then saves. `enabled` is only saved once the
+// port is bound, so a taken port doesn't leave the server failing on every launch.
+async fn restart<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    mut settings: ServerSettings,
+) -> Result<ServerStatus, ZamaError> {
+    stop(app_handle).await;
+    let started = if settings.enabled {
+        start(app_handle, &settings).await.map(drop)
+    } else {
+        Ok(())
+    };
+    settings.enabled &= started.is_ok();
+    save_settings(app_handle, &settings)?;
+    started?;
+    Ok(status(app_handle).await)
+}
+
+// --- Routes ---
+
+fn router<R: Runtime>(context: ServerContext<R>) -> Router {
+    Router::new()
+        .route("/v1/models", get(list_models::<R>))
+        .route("/v1/chat/completions", post(chat_completions::<R>))
+        .route("/v1/embeddings", post(embeddings::<R>))
+        // Before any extractor, so unauthenticated requests never get their bodies parsed
+        .route_layer(middleware::from_fn_with_state(
+            context.clone(),
+            require_token::<R>,
+        ))
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        .with_state(context)
+}
+
+// OpenAI-style error body, so existing client libraries surface the message
+fn error_response(status: StatusCode, code: &str, message: impl Into<String>) -> Response {
+    let body = json!({
+        "error": {
+            "message": message.into(),
+            "type": if status.is_server_error() { "server_error" } else { "invalid_request_error" },
+            "code": code,
+        }
+    });
+    (status, Json(body)).into_response()
+}
+
+impl IntoResponse for ZamaError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ZamaError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            ZamaError::ModelNotFound { .. } => StatusCode::NOT_FOUND,
+            ZamaError::ProviderAuth { .. } => StatusCode::FAILED_DEPENDENCY,
+            ZamaError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ZamaError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        error_response(status, self.code(), self.to_string())
+    }
+}
+
+fn authorized<R: Runtime>(context: &ServerContext<R>, headers: &HeaderMap) -> bool {
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    constant_time_eq(provided.trim().as_bytes(), context.token.as_bytes())
+}
+
+async fn require_token<R: Runtime>(
+    State(context): State<ServerContext<R>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !authorized(&context, request.headers()) {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "invalid_api_key",
+            "Missing or invalid Zama server token",
+        );
+    }
+    next.run(request).await
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// `gemini/<model>` and `ollama/<model>` pick a provider explicitly; bare `gemini-*` names go to
+// Gemini and everything else to Ollama
+pub fn route_model(model: &str) -> (Provider, &str) {
+    if let Some(name) = model.strip_prefix("gemini/") {
+        (Provider::Gemini, name)
+    } else if let Some(name) = model.strip_prefix("ollama/") {
+        (Provider::Ollama, name)
+    } else if model.starts_with("gemini-") {
+        (Provider::Gemini, model)
+    } else {
+        (Provider::Ollama, model)
+    }
+}
+
+struct Upstream {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+fn upstream<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    client: &HttpClient,
+    provider: Provider,
+) -> Result<Upstream, ZamaError> {
+    match provider {
+        Provider::Ollama => Ok(Upstream {
+            base_url: format!("{}/v1", client.endpoints().ollama),
+            api_key: None,
+        }),
+        Provider::Gemini => {
+            let api_key = credentials::load(app_handle)
+                .api_key(Provider::Gemini)
+                .map(str::to_string)
+                .ok_or_else(|| ZamaError::ProviderAuth {
+                    provider: GEMINI_SERVICE.to_string(),
+                    message: "No Gemini API key configured in Zama".to_string(),
+                })?;
+            // Gemini's own OpenAI-compatible surface, so requests can be forwarded without translation
+            Ok(Upstream {
+                base_url: format!("{}/openai", client.endpoints().gemini),
+                api_key: Some(api_key),
+            })
+        }
+    }
+}
+
+// Forwards an OpenAI request body to the provider that owns `model`, streaming the response back
+// untouched so SSE chunks reach the client as they arrive
+async fn forward<R: Runtime>(
+    context: &ServerContext<R>,
+    path: &str,
+    mut body: Value,
+    idempotent: bool,
+) -> Result<Response, ZamaError> {
+    let model = body
+        .get("model")
+        .and_then(Value::as_str)
+        .filter(|m| !m.trim().is_empty())
+        .ok_or_else(|| ZamaError::InvalidInput("`model` is required".to_string()))?
+        .to_string();
+    let (provider, upstream_model) = route_model(&model);
+    let client = http::client(&context.app_handle);
+    let upstream = upstream(&context.app_handle, &client, provider)?;
+    body["model"] = Value::String(upstream_model.to_string());
+
+    let mut request = client
+        .post(&format!("{}{}", upstream.base_url, path))
+        .json(&body);
+    if let Some(key) = &upstream.api_key {
+        request = request.bearer_auth(key);
+    }
+    let service = match provider {
+        Provider::Ollama => OLLAMA_SERVICE,
+        Provider::Gemini => GEMINI_SERVICE,
+    };
+    let res = if idempotent {
+        client.send_idempotent(request).await
+    } else {
+        request.send().await
+    }
+    .map_err(|e| ZamaError::request(service, e))?;
+
+    tracing::debug!(
+        provider = provider.as_str(),
+        %model,
+        path,
+        status = %res.status(),
+        "Forwarded local API request"
+    );
+    let status = StatusCode::from_u16(res.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+    let content_type = res
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/json")
+        .to_string();
+    Ok((
+        status,
+        [(header::CONTENT_TYPE, content_type)],
+        Body::from_stream(res.bytes_stream()),
+    )
+        .into_response())
+}
+
+async fn chat_completions<R: Runtime>(
+    State(context): State<ServerContext<R>>,
+    Json(body): Json<Value>,
+) -> Response {
+    forward(&context, "/chat/completions", body, false)
+        .await
+        .unwrap_or_else(IntoResponse::into_response)
+}
+
+async fn embeddings<R: Runtime>(
+    State(context): State<ServerContext<R>>,
+    Json(body): Json<Value>,
+) -> Response {
+    forward(&context, "/embeddings", body, true)
+        .await
+        .unwrap_or_else(IntoResponse::into_response)
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    #[serde(default)]
+    data: Vec<Value>,
+}
+
+// Lists the models of every configured provider; a provider that is down is left out rather than
+// failing the whole list
+async fn list_models<R: Runtime>(State(context): State<ServerContext<R>>) -> Response {
+    let client = http::client(&context.app_handle);
+    let mut data = Vec::new();
+
+    for provider in [Provider::Ollama, Provider::Gemini] {
+        let Ok(upstream) = upstream(&context.app_handle, &client, provider) else {
+            continue;
+        };
+        let mut request = client.get(&format!("{}/models", upstream.base_url));
+        if let Some(key) = &upstream.api_key {
+            request = request.bearer_auth(key);
+        }
+        let list = match client.send_idempotent(request).await {
+            Ok(res) if res.status().is_success() => res.json::<ModelList>().await.ok(),
+            Ok(res) => {
+                tracing::warn!(provider = provider.as_str(), status = %res.status(), "Listing models failed");
+                None
+            }
+            Err(e) => {
+                tracing::warn!(provider = provider.as_str(), error = %e, "Listing models failed");
+                None
+            }
+        };
+
+        for mut model in list.map(|l| l.data).unwrap_or_default() {
+            // Gemini reports `models/gemini-2.5-flash`; expose it under the routable prefix
+            if provider == Provider::Gemini {
+                if let Some(id) = model.get("id").and_then(Value::as_str) {
+                    let id = format!("gemini/{}", id.trim_start_matches("models/"));
+                    model["id"] = Value::String(id);
+                }
+            }
+            model["owned_by"] = Value::String(provider.as_str().to_string());
+            data.push(model);
+        }
+    }
+
+    Json(json!({ "object": "list", "data": data })).into_response()
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::conversations::{Conversation, ConversationStore, Role};
+use crate::error::ZamaError;
+use crate::ollama::ChatResponse;
+
+const NANOS_PER_MS: f64 = 1_000_000.0;
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+// Metrics for one generated reply, derived from Ollama's final chunk
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct GenerationStats {
+    pub total_ms: Option<f64>,
+    pub load_ms: Option<f64>, // Time spent loading the model, 0 when it was already resident
+    pub prompt_tokens: Option<u64>,
+    pub prompt_eval_ms: Option<f64>,
+    pub completion_tokens: Option<u64>,
+    pub eval_ms: Option<f64>,
+    pub tokens_per_second: Option<f64>,
+    pub prompt_tokens_per_second: Option<f64>,
+    pub time_to_first_token_ms: Option<f64>, // Wall clock from sending the request
+    pub done_reason: Option<String>,         // Eg. `stop`, `length`
+}
+
+fn per_second(tokens: Option<u64>, nanos: Option<u64>) -> Option<f64> {
+    let (tokens, nanos) = (tokens?, nanos?);
+    (nanos > 0).then(|| tokens as f64 / Duration::from_nanos(nanos).as_secs_f64())
+}
+
+impl GenerationStats {
+    // `first_token` is measured by the caller; Ollama only reports server-side durations
+    pub fn from_final_chunk(chunk: &ChatResponse, first_token: Option<Duration>) -> Self {
+        let ms = |nanos: Option<u64>| nanos.map(|n| n as f64 / NANOS_PER_MS);
+        Self {
+            total_ms: ms(chunk.total_duration),
+            load_ms: ms(chunk.load_duration),
+            prompt_tokens: chunk.prompt_eval_count,
+            prompt_eval_ms: ms(chunk.prompt_eval_duration),
+            completion_tokens: chunk.eval_count,
+            eval_ms: ms(chunk.eval_duration),
+            tokens_per_second: per_second(chunk.eval_count, chunk.eval_duration),
+            prompt_tokens_per_second: per_second(
+                chunk.prompt_eval_count,
+                chunk.prompt_eval_duration,
+            ),
+            time_to_first_token_ms: first_token
+                .map(|d| d.as_secs_f64() * 1000.0)
+                // Without a streamed token, loading plus prompt processing is the closest estimate
+                .or_else(|| Some(ms(chunk.load_duration)? + ms(chunk.prompt_eval_duration)?)),
+            done_reason: chunk.done_reason.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct DailyModelStats {
+    pub day: u64, // Unix millis at the start of the UTC day
+    pub messages: usize,
+    pub avg_tokens_per_second: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ModelStats {
+    pub model: String,
+    pub messages: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub avg_tokens_per_second: Option<f64>,
+    pub avg_time_to_first_token_ms: Option<f64>,
+    pub avg_total_ms: Option<f64>,
+    pub avg_load_ms: Option<f64>,
+    pub first_used: u64,
+    pub last_used: u64,
+    pub daily: Vec<DailyModelStats>, // Oldest first, days without replies are left out
+}
+
+// Running mean that ignores missing samples
+#[derive(Default)]
+struct Mean {
+    sum: f64,
+    count: usize,
+}
+
+impl Mean {
+    fn add(&mut self, value: Option<f64>) {
+        if let Some(value) = value {
+            self.sum += value;
+            self.count += 1;
+        }
+    }
+
+    fn get(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+}
+
+#[derive(Default)]
+struct Accumulator {
+    stats: ModelStats,
+    tokens_per_second: Mean,
+    time_to_first_token: Mean,
+    total_ms: Mean,
+    load_ms: Mean,
+    daily: BTreeMap<u64, (usize, Mean)>,
+}
+
+// Aggregates the stats stored with assistant messages, per model, optionally since `since`
+#[tauri::command]
+pub async fn model_stats(
+    store: tauri::State<'_, ConversationStore>,
+    model: Option<String>,
+    since: Option<u64>,
+) -> Result<Vec<ModelStats>, ZamaError> {
+    let conversations = store.run(|store| Ok(store.list())).await?;
+    Ok(aggregate(&conversations, model, since))
+}
+
+fn aggregate(
+    conversations: &[Conversation],
+    model: Option<String>,
+    since: Option<u64>,
+) -> Vec<ModelStats> {
+    let mut by_model: BTreeMap<String, Accumulator> = BTreeMap::new();
+    for conversation in conversations {
+        for message in &conversation.messages {
+            let (Some(stats), Some(message_model)) = (&message.stats, &message.model) else {
+                continue;
+            };
+            if message.role != Role::Assistant
+                || since.is_some_and(|since| message.timestamp < since)
+                || model.as_ref().is_some_and(|m| m != message_model)
+            {
+                continue;
+            }
+
+            let acc = by_model.entry(message_model.clone()).or_default();
+            let totals = &mut acc.stats;
+            if totals.messages == 0 {
+                totals.first_used = message.timestamp;
+            }
+            totals.messages += 1;
+            totals.prompt_tokens += stats.prompt_tokens.unwrap_or_default();
+            totals.completion_tokens += stats.completion_tokens.unwrap_or_default();
+            totals.first_used = totals.first_used.min(message.timestamp);
+            totals.last_used = totals.last_used.max(message.timestamp);
+            acc.tokens_per_second.add(stats.tokens_per_second);
+            acc.time_to_first_token.add(stats.time_to_first_token_ms);
+            acc.total_ms.add(stats.total_ms);
+            acc.load_ms.add(stats.load_ms);
+
+            let day = acc
+                .daily
+                .entry(message.timestamp / MS_PER_DAY * MS_PER_DAY)
+                .or_default();
+            day.0 += 1;
+            day.1.add(stats.tokens_per_second);
+        }
+    }
+
+    by_model
+        .into_iter()
+        .map(|(model, acc)| ModelStats {
+            model,
+            avg_tokens_per_second: acc.tokens_per_second.get(),
+            avg_time_to_first_token_ms: acc.time_to_first_token.get(),
+            avg_total_ms: acc.total_ms.get(),
+            avg_load_ms: acc.load_ms.get(),
+            daily: acc
+                .daily
+                .into_iter()
+                .map(|(day, (messages, tps))| DailyModelStats {
+                    day,
+                    messages,
+                    avg_tokens_per_second: tps.get(),
+                })
+                .collect(),
+            ..acc.stats
+        })
+        .collect()
+}
@@ -0,0 +1,388 @@
+use axum::http::Method;
+
+use super::mock::{hugging_face, ollamadb, Reply};
+use super::TestApp;
+use crate::catalog::{CachedCatalog, CatalogFilter, CatalogSort, CatalogStore, OllamaDbModel};
+use crate::hf::{HfSearchParams, HfSort};
+
+const LLAMA_REPO: &str = "bartowski/Llama-3.2-3B-Instruct-GGUF";
+
+fn serve_ollamadb(t: &TestApp) {
+    t.mock.on(
+        Method::GET,
+        &ollamadb("/api/v1/models"),
+        Reply::json(include_str!("fixtures/ollamadb/models.json")),
+    );
+}
+
+#[tokio::test]
+async fn ollamadb_catalog_is_fetched_cached_and_filtered() {
+    let t = TestApp::new();
+    serve_ollamadb(&t);
+
+    let page = crate::get_ollama_models(t.handle(), t.state(), None, None)
+        .await
+        .unwrap();
+    assert!(!page.from_cache && !page.stale);
+    assert_eq!(page.total, 3);
+    let ids: Vec<_> = page
+        .models
+        .iter()
+        .map(|m| m.model_identifier.as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        ["llama3.2", "qwen3", "huihui_ai/qwen3-abliterated"],
+        "most pulled first"
+    );
+    let request = &t.mock.requests()[0];
+    assert_eq!(request.query["limit"], "200");
+    assert_eq!(request.query["skip"], "0");
+
+    let filter = CatalogFilter {
+        capability: Some("thinking".to_string()),
+        official_only: true,
+        ..CatalogFilter::default()
+    };
+    let page = crate::get_ollama_models(t.handle(), t.state(), Some(filter), None)
+        .await
+        .unwrap();
+    assert!(page.from_cache, "served from the cache");
+    assert_eq!(page.total, 3);
+    assert_eq!(page.models.len(), 1);
+    assert_eq!(page.models[0].model_identifier, "qwen3");
+    assert_eq!(t.mock.requests().len(), 1);
+
+    let filter = CatalogFilter {
+        search: Some("QWEN3".to_string()),
+        sort: CatalogSort::Name,
+        ascending: true,
+        limit: Some(5),
+        ..CatalogFilter::default()
+    };
+    let page = crate::get_ollama_models(t.handle(), t.state(), Some(filter.clone()), Some(true))
+        .await
+        .unwrap();
+    assert!(!page.from_cache);
+    let names: Vec<_> = page.models.iter().map(|m| m.model_name.as_str()).collect();
+    assert_eq!(names, ["qwen3", "qwen3-abliterated"], "A to Z");
+    assert_eq!(t.mock.requests().len(), 2, "refresh bypasses the cache");
+
+    let filter = CatalogFilter {
+        ascending: false,
+        ..filter
+    };
+    let page = crate::get_ollama_models(t.handle(), t.state(), Some(filter), None)
+        .await
+        .unwrap();
+    let names: Vec<_> = page.models.iter().map(|m| m.model_name.as_str()).collect();
+    assert_eq!(names, ["qwen3-abliterated", "qwen3"], "Z to A by default");
+}
+
+#[tokio::test]
+async fn ollamadb_outage_falls_back_to_the_cache() {
+    let t = TestApp::new();
+    let err = crate::get_ollama_models(t.handle(), t.state(), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "http", "nothing cached yet");
+
+    serve_ollamadb(&t);
+    crate::get_ollama_models(t.handle(), t.state(), None, None)
+        .await
+        .unwrap();
+
+    t.mock.on(
+        Method::GET,
+        &ollamadb("/api/v1/models"),
+        Reply::error(502, "<html>Bad Gateway</html>"),
+    );
+    let page = crate::get_ollama_models(t.handle(), t.state(), None, Some(true))
+        .await
+        .unwrap();
+    assert!(page.from_cache);
+    assert_eq!(page.total, 3);
+
+    t.mock.on(
+        Method::GET,
+        &ollamadb("/api/v1/models"),
+        Reply::json(r#"{"models": "#),
+    );
+    let page = crate::get_ollama_models(t.handle(), t.state(), None, Some(true))
+        .await
+        .unwrap();
+    assert!(page.from_cache, "a malformed response keeps the cache too");
+}
+
+#[tokio::test]
+async fn stale_catalog_is_served_then_revalidated() {
+    let t = TestApp::new();
+    serve_ollamadb(&t);
+    let updated = t.events("catalog-updated");
+
+    let store = CatalogStore::init(&t.handle()).unwrap();
+    store
+        .write(&CachedCatalog::<Vec<OllamaDbModel>> {
+            key: "ollamadb".to_string(),
+            fetched_at: 1,
+            data: Vec::new(),
+        })
+        .unwrap();
+
+    let page = crate::get_ollama_models(t.handle(), t.state(), None, None)
+        .await
+        .unwrap();
+    assert!(page.from_cache && page.stale);
+    assert!(page.models.is_empty());
+
+    let events = updated.wait_for(1).await;
+    assert_eq!(events[0]["key"], "ollamadb");
+    let page = crate::get_ollama_models(t.handle(), t.state(), None, None)
+        .await
+        .unwrap();
+    assert!(!page.stale);
+    assert_eq!(page.models.len(), 3);
+}
+
+#[test]
+fn catalog_cache_evicts_the_least_recently_written() {
+    let dir = super::TempDir::new();
+    let store = CatalogStore::new(dir.path().join("catalog"));
+    for key in ["first", "second", "third"] {
+        store
+            .write(&CachedCatalog {
+                key: key.to_string(),
+                fetched_at: 1,
+                data: Vec::<OllamaDbModel>::new(),
+            })
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    store.evict(2);
+    assert!(store.read::<Vec<OllamaDbModel>>("first").is_none());
+    assert!(store.read::<Vec<OllamaDbModel>>("second").is_some());
+    assert!(store.read::<Vec<OllamaDbModel>>("third").is_some());
+}
+
+#[tokio::test]
+async fn hugging_face_search_sends_the_query_and_follows_cursors() {
+    let t = TestApp::new();
+    let next = t.mock.url(&hugging_face(
+        "/api/models?search=llama&cursor=eyIkb3IiOlt7Il9pZCI6IjY2In1dfQ%3D%3D",
+    ));
+    t.mock.on(
+        Method::GET,
+        &hugging_face("/api/models"),
+        Reply::json(include_str!("fixtures/hf/models.json"))
+            .header("link", format!("<{}>; rel=\"next\"", next)),
+    );
+
+    let params = HfSearchParams {
+        library: Some("GGUF".to_string()),
+        sort: HfSort::Likes,
+        limit: Some(3),
+        ..HfSearchParams::default()
+    };
+    let page = crate::list_hf_models(
+        t.handle(),
+        t.state(),
+        Some("llama".to_string()),
+        Some(params.clone()),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(
+        page.next_cursor.as_deref(),
+        Some("eyIkb3IiOlt7Il9pZCI6IjY2In1dfQ==")
+    );
+    let llama = page
+        .models
+        .iter()
+        .find(|m| m.model_id == LLAMA_REPO)
+        .unwrap();
+    assert_eq!(llama.parameter_count(), Some(3212749888));
+
+    let query = &t.mock.requests()[0].query;
+    assert_eq!(query["search"], "llama");
+    assert_eq!(query["pipeline_tag"], "text-generation");
+    assert_eq!(query["filter"], "gguf");
+    assert_eq!(query["sort"], "likes");
+    assert_eq!(query["direction"], "-1");
+    assert_eq!(query["limit"], "3");
+    assert_eq!(query["full"], "true");
+
+    // Same query again comes from the cache, filtered locally
+    let filter = CatalogFilter {
+        search: Some("qwen".to_string()),
+        ..CatalogFilter::default()
+    };
+    let page = crate::list_hf_models(
+        t.handle(),
+        t.state(),
+        Some("llama".to_string()),
+        Some(params),
+        Some(filter),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(page.from_cache);
+    assert_eq!(page.models.len(), 1);
+    assert_eq!(page.models[0].model_id, "unsloth/Qwen3-8B-GGUF");
+    assert_eq!(t.mock.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn hugging_face_search_filters_parameter_ranges_and_rejects_bad_params() {
+    let t = TestApp::new();
+    t.mock.on(
+        Method::GET,
+        &hugging_face("/api/models"),
+        Reply::json(include_str!("fixtures/hf/models.json")),
+    );
+
+    let params = HfSearchParams {
+        min_params: Some("2B".to_string()),
+        max_params: Some("4B".to_string()),
+        ..HfSearchParams::default()
+    };
+    let page = crate::list_hf_models(t.handle(), t.state(), None, Some(params), None, None)
+        .await
+        .unwrap();
+    let ids: Vec<_> = page.models.iter().map(|m| m.model_id.as_str()).collect();
+    assert_eq!(ids, [LLAMA_REPO]);
+    assert!(page.next_cursor.is_none());
+    assert_eq!(
+        t.mock.requests()[0].query["num_parameters"],
+        "min:2B,max:4B"
+    );
+
+    for params in [
+        HfSearchParams {
+            limit: Some(0),
+            ..HfSearchParams::default()
+        },
+        HfSearchParams {
+            min_params: Some("lots".to_string()),
+            ..HfSearchParams::default()
+        },
+    ] {
+        let err = crate::list_hf_models(t.handle(), t.state(), None, Some(params), None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input");
+    }
+    assert_eq!(t.mock.requests().len(), 1);
+
+    t.mock.on(
+        Method::GET,
+        &hugging_face("/api/models"),
+        Reply::error(429, r#"{"error":"Too many requests"}"#).header("retry-after", "30"),
+    );
+    let err = crate::list_hf_models(
+        t.handle(),
+        t.state(),
+        Some("fresh".to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&err).unwrap()["retry_after"],
+        30,
+        "{}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn gguf_listing_pages_through_the_repo_tree() {
+    let t = TestApp::new();
+    let tree = hugging_face(&format!("/api/models/{}/tree/main", LLAMA_REPO));
+    let next = t.mock.url(&format!("{}?recursive=true&cursor=page2", tree));
+    t.mock.on_sequence(
+        Method::GET,
+        &tree,
+        vec![
+            Reply::json(include_str!("fixtures/hf/tree_page1.json"))
+                .header("link", format!("<{}>; rel=\"next\"", next)),
+            Reply::json(include_str!("fixtures/hf/tree_page2.json")),
+        ],
+    );
+
+    let listing =
+        crate::list_hf_gguf_files(t.state(), format!("https://huggingface.co/{}/", LLAMA_REPO))
+            .await
+            .unwrap();
+    assert_eq!(listing.repo, LLAMA_REPO);
+    assert_eq!(listing.files.len(), 4);
+    assert!(listing.files.iter().any(|f| f.is_projector));
+
+    let quants: Vec<_> = listing
+        .quantizations
+        .iter()
+        .map(|q| (q.quant.as_str(), q.total_size, q.pull_name.as_deref()))
+        .collect();
+    assert_eq!(
+        quants,
+        [
+            ("unknown", 1048576, None),
+            (
+                "Q4_K_M",
+                2019377376,
+                Some("hf.co/bartowski/Llama-3.2-3B-Instruct-GGUF:Q4_K_M")
+            ),
+            (
+                "Q8_0",
+                3421899008,
+                Some("hf.co/bartowski/Llama-3.2-3B-Instruct-GGUF:Q8_0")
+            ),
+        ]
+    );
+    let q4 = listing
+        .files
+        .iter()
+        .find(|f| f.path.ends_with("Q4_K_M.gguf"))
+        .unwrap();
+    assert_eq!(
+        q4.sha256.as_deref(),
+        Some("6c1a2b41161032677be168d354123594c0e6e67d2b9227c84f296ad037c728ff")
+    );
+
+    let requests = t.mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].query["recursive"], "true");
+    assert_eq!(requests[1].query["cursor"], "page2");
+}
+
+#[tokio::test]
+async fn gguf_listing_reports_missing_and_invalid_repos() {
+    let t = TestApp::new();
+    t.mock.on(
+        Method::GET,
+        &hugging_face("/api/models/someone/nothing-here/tree/main"),
+        Reply::error(404, include_str!("fixtures/hf/not_found.json")),
+    );
+
+    let err = crate::list_hf_gguf_files(t.state(), "someone/nothing-here".to_string())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        crate::error::ZamaError::Http { status: 404, .. }
+    ));
+
+    for repo in ["no-slash", "a/b/c", "../etc/passwd", "user/.hidden"] {
+        let err = crate::list_hf_gguf_files(t.state(), repo.to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input", "{}", repo);
+    }
+    assert_eq!(t.mock.requests().len(), 1);
+}
@@ -0,0 +1,370 @@
+use axum::http::Method;
+use serde_json::{json, Value};
+
+use super::mock::{ollama, Reply};
+use super::{message, TestApp};
+use crate::context::{ContextSettings, ContextStrategy};
+use crate::ollama::ChatRequest;
+use crate::presets::{OptionSource, ParameterSet};
+
+fn chat_request(model: &str, content: &str) -> ChatRequest {
+    ChatRequest {
+        model: model.to_string(),
+        messages: vec![message("user", content)],
+        stream: true,
+        think: None,
+        options: None,
+        conversation_id: None,
+    }
+}
+
+fn joined(events: &[Value], field: &str) -> String {
+    events
+        .iter()
+        .filter_map(|event| event.pointer(field)?.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn chat_splits_think_tags_and_skips_malformed_chunks() {
+    let t = TestApp::new().with_ollama();
+    let chunks = t.events("ollama-chat-chunk");
+    let thinking = t.events("ollama-chat-thinking");
+    let context = t.events("ollama-chat-context");
+
+    crate::chat_ollama(
+        t.handle(),
+        t.state(),
+        chat_request("llama3.2:latest", "Hi there"),
+    )
+    .await
+    .unwrap();
+
+    let chunks = chunks.all();
+    assert_eq!(chunks.len(), 5, "the truncated line is dropped");
+    assert_eq!(
+        joined(&chunks, "/message/content"),
+        "Hello! How can I help?"
+    );
+    assert_eq!(
+        joined(&thinking.all(), "/content"),
+        "Greeting, keep it short."
+    );
+
+    let last = chunks.last().unwrap();
+    assert_eq!(last["done"], true);
+    assert_eq!(last["stats"]["prompt_tokens"], 31);
+    assert_eq!(last["stats"]["completion_tokens"], 16);
+    assert_eq!(last["stats"]["done_reason"], "stop");
+    let tokens_per_second = last["stats"]["tokens_per_second"].as_f64().unwrap();
+    assert!((tokens_per_second - 16.0 / 0.36821).abs() < 0.01);
+
+    let report = &context.all()[0];
+    assert_eq!(report["context_length"], 2048);
+    assert_eq!(report["model_context_length"], 131072);
+
+    let sent = t.mock.requests_to(Method::POST, &ollama("/api/chat"));
+    assert_eq!(sent[0].json()["stream"], true);
+    assert!(
+        sent[0].json().get("think").is_none(),
+        "no thinking capability"
+    );
+    assert!(sent[0].json().get("conversation_id").is_none());
+}
+
+#[tokio::test]
+async fn chat_enables_native_thinking_when_the_model_supports_it() {
+    let t = TestApp::new().with_ollama();
+    let chunks = t.events("ollama-chat-chunk");
+    let thinking = t.events("ollama-chat-thinking");
+
+    crate::chat_ollama(
+        t.handle(),
+        t.state(),
+        chat_request("qwen3:8b", "Pick a number"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(joined(&chunks.all(), "/message/content"), "42");
+    let thinking = thinking.all();
+    assert_eq!(joined(&thinking, "/content"), "The user wants a number.");
+    assert!(thinking.iter().all(|event| event["model"] == "qwen3:8b"));
+    assert!(chunks
+        .all()
+        .iter()
+        .all(|c| c["message"]["thinking"].is_null()));
+
+    let sent = t.mock.requests_to(Method::POST, &ollama("/api/chat"));
+    assert_eq!(sent[0].json()["think"], true);
+    assert_eq!(
+        t.mock.requests_to(Method::POST, &ollama("/api/show")).len(),
+        1,
+        "context sizing and the thinking check share one lookup"
+    );
+}
+
+#[tokio::test]
+async fn chat_reports_missing_models_and_server_errors() {
+    let t = TestApp::new().with_ollama();
+
+    let err = crate::chat_ollama(t.handle(), t.state(), chat_request("missing:latest", "Hi"))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        crate::error::ZamaError::ModelNotFound {
+            model: "missing:latest".to_string()
+        }
+    );
+
+    t.mock.on(
+        Method::POST,
+        &ollama("/api/chat"),
+        Reply::error(500, r#"{"error":"llama runner process has terminated"}"#),
+    );
+    let err = crate::chat_ollama(t.handle(), t.state(), chat_request("llama3.2:latest", "Hi"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "http");
+    assert!(err
+        .to_string()
+        .contains("llama runner process has terminated"));
+}
+
+#[tokio::test]
+async fn chat_times_out_when_the_stream_stalls() {
+    let t = TestApp::new().with_ollama();
+    let settings = crate::http::NetworkSettings {
+        read_timeout_secs: 1,
+        max_retries: 0,
+        ..crate::http::NetworkSettings::default()
+    };
+    crate::http::set_network_settings(t.handle(), t.state(), settings).unwrap();
+    t.mock.on(
+        Method::POST,
+        &ollama("/api/chat"),
+        Reply::ndjson(include_str!("fixtures/ollama/chat_llama3.2.ndjson"))
+            .delay(std::time::Duration::from_millis(1500)),
+    );
+
+    let err = crate::chat_ollama(t.handle(), t.state(), chat_request("llama3.2:latest", "Hi"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "timeout", "{}", err);
+    assert!(err.retryable());
+}
+
+#[tokio::test]
+async fn chat_rejects_invalid_requests_without_calling_ollama() {
+    let t = TestApp::new().with_ollama();
+
+    let err = crate::chat_ollama(t.handle(), t.state(), chat_request("bad model!", "Hi"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let mut empty = chat_request("llama3.2:latest", "");
+    empty.messages.clear();
+    let err = crate::chat_ollama(t.handle(), t.state(), empty)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    assert!(t.mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn chat_applies_presets_before_sending() {
+    let t = TestApp::new().with_ollama();
+    crate::presets::set_global_preset(
+        t.handle(),
+        ParameterSet {
+            system: Some("Answer briefly.".to_string()),
+            options: json!({ "temperature": 0.2 }).as_object().unwrap().clone(),
+        },
+    )
+    .await
+    .unwrap();
+
+    crate::chat_ollama(t.handle(), t.state(), chat_request("llama3.2:latest", "Hi"))
+        .await
+        .unwrap();
+
+    let sent = t.mock.requests_to(Method::POST, &ollama("/api/chat"))[0].json();
+    assert_eq!(sent["options"]["temperature"], 0.2);
+    assert_eq!(sent["messages"][0]["role"], "system");
+    assert_eq!(sent["messages"][0]["content"], "Answer briefly.");
+}
+
+#[tokio::test]
+async fn plan_context_uses_the_modelfile_window_and_trims_old_turns() {
+    let t = TestApp::new().with_ollama();
+
+    let report = crate::context::plan_context(
+        t.handle(),
+        t.state(),
+        "qwen3:8b".to_string(),
+        vec![message("user", "Hello")],
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.context_length, 8192);
+    assert_eq!(report.model_context_length, Some(40960));
+    assert!(report.dropped.is_empty());
+
+    // 100 tokens per message against a 300 token window with 100 reserved for the reply
+    crate::context::set_context_settings(
+        t.handle(),
+        ContextSettings {
+            enabled: true,
+            strategy: ContextStrategy::PinSystem,
+            reserve_tokens: 100,
+        },
+    )
+    .unwrap();
+    let long = "x".repeat(384);
+    let messages = vec![
+        message("system", "Be helpful."),
+        message("user", &long),
+        message("assistant", &long),
+        message("user", &long),
+    ];
+    let report = crate::context::plan_context(
+        t.handle(),
+        t.state(),
+        "qwen3:8b".to_string(),
+        messages,
+        json!({ "num_ctx": 300 }).as_object().cloned(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.context_length, 300);
+    assert_eq!(
+        report.dropped.iter().map(|d| d.index).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert!(!report.overflow);
+
+    let err = crate::context::plan_context(
+        t.handle(),
+        t.state(),
+        "bad model!".to_string(),
+        Vec::new(),
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn plan_context_summarizes_dropped_turns() {
+    let t = TestApp::new().with_ollama();
+    crate::context::set_context_settings(
+        t.handle(),
+        ContextSettings {
+            enabled: true,
+            strategy: ContextStrategy::Summarize,
+            reserve_tokens: 100,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        crate::context::get_context_settings(t.handle()).strategy,
+        ContextStrategy::Summarize
+    );
+
+    let long = "y".repeat(384);
+    let report = crate::context::plan_context(
+        t.handle(),
+        t.state(),
+        "llama3.2:latest".to_string(),
+        vec![message("user", &long), message("user", &long)],
+        json!({ "num_ctx": 250 }).as_object().cloned(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.dropped.len(), 1);
+    assert_eq!(
+        report.summary.as_deref(),
+        Some("Earlier, the user greeted the assistant.")
+    );
+}
+
+#[tokio::test]
+async fn presets_resolve_across_every_level() {
+    let t = TestApp::new().with_ollama();
+    let options = |value: Value| value.as_object().unwrap().clone();
+
+    crate::presets::set_global_preset(
+        t.handle(),
+        ParameterSet {
+            system: None,
+            options: options(json!({ "temperature": 0.7, "top_k": 40 })),
+        },
+    )
+    .await
+    .unwrap();
+    let presets = crate::presets::set_model_preset(
+        t.handle(),
+        "qwen3".to_string(),
+        Some(ParameterSet {
+            system: Some("Think step by step.".to_string()),
+            options: options(json!({ "temperature": 0.5 })),
+        }),
+    )
+    .await
+    .unwrap();
+    assert!(presets.models.contains_key("qwen3"));
+    assert_eq!(
+        crate::presets::get_presets(t.handle()).await.unwrap(),
+        presets
+    );
+
+    let conversation =
+        crate::conversations::create_conversation(t.state(), "qwen3:8b".to_string(), None)
+            .await
+            .unwrap();
+    let saved = crate::presets::set_conversation_parameters(
+        t.state(),
+        conversation.id.clone(),
+        Some(ParameterSet {
+            system: None,
+            options: options(json!({ "temperature": 0.3 })),
+        }),
+    )
+    .await
+    .unwrap();
+    assert!(saved.is_some());
+
+    let resolved = crate::presets::resolve_options(
+        t.handle(),
+        t.state(),
+        "qwen3:8b".to_string(),
+        Some(conversation.id.clone()),
+        Some(options(json!({ "seed": 1 }))),
+    )
+    .await
+    .unwrap();
+    let source = |key: &str| {
+        resolved
+            .values
+            .iter()
+            .find(|v| v.key == key)
+            .unwrap_or_else(|| panic!("{} is resolved", key))
+            .source
+    };
+    assert_eq!(resolved.options["temperature"], 0.3);
+    assert_eq!(source("temperature"), OptionSource::Conversation);
+    assert_eq!(source("seed"), OptionSource::Request);
+    assert_eq!(source("top_k"), OptionSource::Global);
+    assert_eq!(source("system"), OptionSource::Model);
+    assert_eq!(source("num_ctx"), OptionSource::Modelfile);
+
+    let cleared = crate::presets::set_model_preset(t.handle(), "qwen3".to_string(), None)
+        .await
+        .unwrap();
+    assert!(cleared.models.is_empty());
+}
@@ -0,0 +1,418 @@
+use axum::http::Method;
+
+use super::mock::ollama;
+use super::TestApp;
+use crate::conversations::{
+    self, ConversationStore, ConversationView, LegacyConversation, LegacyMessage, Role,
+};
+use crate::stats::GenerationStats;
+use crate::titles::{self, TitleSettings};
+
+fn ids(view: &ConversationView) -> Vec<String> {
+    view.messages.iter().map(|m| m.message.id.clone()).collect()
+}
+
+fn stats(completion_tokens: u64, tokens_per_second: f64) -> GenerationStats {
+    GenerationStats {
+        prompt_tokens: Some(31),
+        completion_tokens: Some(completion_tokens),
+        tokens_per_second: Some(tokens_per_second),
+        done_reason: Some("stop".to_string()),
+        ..GenerationStats::default()
+    }
+}
+
+// A conversation with one completed exchange, titled in the background
+async fn exchange(t: &TestApp) -> ConversationView {
+    let titled = t.events("conversation-titled");
+    let view = conversations::create_conversation(t.state(), "llama3.2:latest".to_string(), None)
+        .await
+        .unwrap();
+    let view = conversations::append_message(
+        t.state(),
+        view.id,
+        Role::User,
+        "How does the borrow checker work?".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    let view = conversations::append_message(
+        t.state(),
+        view.id,
+        Role::Assistant,
+        String::new(),
+        Some("llama3.2:latest".to_string()),
+    )
+    .await
+    .unwrap();
+    let reply_id = ids(&view)[1].clone();
+    let view = conversations::update_message(
+        t.handle(),
+        t.state(),
+        view.id,
+        reply_id,
+        "It tracks ownership at compile time.".to_string(),
+        Some(stats(16, 43.5)),
+        Some("Explain ownership.".to_string()),
+    )
+    .await
+    .unwrap();
+    let events = titled.wait_for(1).await;
+    assert_eq!(events.len(), 1, "titled once after the first reply");
+    view
+}
+
+#[tokio::test]
+async fn first_reply_titles_and_summarizes_the_conversation() {
+    let t = TestApp::new().with_ollama();
+    let view = exchange(&t).await;
+
+    let saved = conversations::get_conversation(t.state(), view.id.clone())
+        .await
+        .unwrap();
+    assert_eq!(saved.title, "Rust ownership basics");
+    assert_eq!(
+        saved.summary.as_deref(),
+        Some("The user asked how Rust's borrow checker works.")
+    );
+    let reply = &saved.messages[1].message;
+    assert_eq!(reply.thinking.as_deref(), Some("Explain ownership."));
+    assert_eq!(reply.stats.as_ref().unwrap().completion_tokens, Some(16));
+
+    // The titling request used the conversation's own model and a JSON schema
+    let request = t.mock.requests_to(Method::POST, &ollama("/api/chat"))[0].json();
+    assert_eq!(request["model"], "llama3.2:latest");
+    assert_eq!(request["stream"], false);
+    assert!(request["format"]["properties"]["title"].is_object());
+}
+
+#[tokio::test]
+async fn generate_title_replaces_a_user_title_on_request() {
+    let t = TestApp::new().with_ollama();
+    let view = exchange(&t).await;
+
+    let renamed =
+        conversations::rename_conversation(t.state(), view.id.clone(), "  Mine  ".to_string())
+            .await
+            .unwrap();
+    assert_eq!(renamed.title, "Mine");
+    let err = conversations::rename_conversation(t.state(), view.id.clone(), " ".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let titled = t.events("conversation-titled");
+    titles::generate_title(t.handle(), t.state(), view.id.clone())
+        .await
+        .unwrap();
+    let events = titled.wait_for(1).await;
+    assert_eq!(events[0]["title"], "Rust ownership basics");
+
+    let err = titles::generate_title(t.handle(), t.state(), "missing".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn title_settings_round_trip_and_can_disable_titling() {
+    let t = TestApp::new().with_ollama();
+    assert_eq!(
+        titles::get_title_settings(t.handle()),
+        TitleSettings::default()
+    );
+
+    let err = titles::set_title_settings(
+        t.handle(),
+        TitleSettings {
+            enabled: true,
+            model: Some("not a model".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let disabled = TitleSettings {
+        enabled: false,
+        model: Some("qwen3:8b".to_string()),
+    };
+    titles::set_title_settings(t.handle(), disabled.clone()).unwrap();
+    assert_eq!(titles::get_title_settings(t.handle()), disabled);
+
+    let view = conversations::create_conversation(t.state(), "llama3.2:latest".to_string(), None)
+        .await
+        .unwrap();
+    let view =
+        conversations::append_message(t.state(), view.id, Role::User, "Hi".to_string(), None)
+            .await
+            .unwrap();
+    let view = conversations::append_message(
+        t.state(),
+        view.id,
+        Role::Assistant,
+        "Hello!".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    let reply_id = ids(&view)[1].clone();
+    conversations::update_message(
+        t.handle(),
+        t.state(),
+        view.id,
+        reply_id,
+        "Hello there!".to_string(),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    assert!(t
+        .mock
+        .requests_to(Method::POST, &ollama("/api/chat"))
+        .is_empty());
+}
+
+#[tokio::test]
+async fn edits_and_regenerations_become_switchable_branches() {
+    let t = TestApp::new().with_ollama();
+    let view = exchange(&t).await;
+    let [question, answer] = [ids(&view)[0].clone(), ids(&view)[1].clone()];
+
+    let edited = conversations::edit_message(
+        t.state(),
+        view.id.clone(),
+        question.clone(),
+        "What is a lifetime?".to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(edited.messages.len(), 1);
+    assert_eq!(edited.messages[0].message.content, "What is a lifetime?");
+    assert_eq!(edited.messages[0].sibling_count, 2);
+
+    let branches = conversations::list_branches(t.state(), view.id.clone(), question.clone())
+        .await
+        .unwrap();
+    assert_eq!(branches.len(), 2);
+    assert!(!branches[0].active && branches[1].active);
+    assert_eq!(branches[0].reply_count, 1);
+
+    let switched = conversations::switch_branch(t.state(), view.id.clone(), question)
+        .await
+        .unwrap();
+    assert_eq!(ids(&switched), vec![ids(&view)[0].clone(), answer.clone()]);
+
+    let err =
+        conversations::regenerate_message(t.state(), view.id.clone(), ids(&view)[0].clone(), None)
+            .await
+            .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+    let regenerated =
+        conversations::regenerate_message(t.state(), view.id.clone(), answer.clone(), None)
+            .await
+            .unwrap();
+    let new_reply = &regenerated.messages[1];
+    assert_eq!(new_reply.message.content, "");
+    assert_eq!(new_reply.message.model.as_deref(), Some("llama3.2:latest"));
+    assert_eq!(new_reply.sibling_index, 1);
+
+    let err = conversations::switch_branch(t.state(), view.id.clone(), "msg_missing".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn parent_cycles_in_a_corrupted_file_end_the_path() {
+    let t = TestApp::new().with_ollama();
+    let view = exchange(&t).await;
+    let [question, answer] = [ids(&view)[0].clone(), ids(&view)[1].clone()];
+    let id = view.id.clone();
+    t.state::<ConversationStore>()
+        .run(move |store| {
+            store.update(&id, |conversation| {
+                conversation.message_mut(&question)?.parent_id = Some(answer);
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+
+    let view = conversations::get_conversation(t.state(), view.id.clone())
+        .await
+        .unwrap();
+    assert_eq!(view.messages.len(), 2);
+    let switched = conversations::switch_branch(t.state(), view.id.clone(), ids(&view)[0].clone())
+        .await
+        .unwrap();
+    assert_eq!(switched.messages.len(), 2);
+}
+
+#[tokio::test]
+async fn forks_copy_the_path_and_keep_their_origin() {
+    let t = TestApp::new().with_ollama();
+    let view = exchange(&t).await;
+    let question = ids(&view)[0].clone();
+
+    let fork = conversations::fork_conversation(t.state(), view.id.clone(), Some(question.clone()))
+        .await
+        .unwrap();
+    assert_ne!(fork.id, view.id);
+    assert_eq!(fork.title, "Rust ownership basics (fork)");
+    assert_eq!(ids(&fork), vec![question.clone()]);
+    let origin = fork.forked_from.unwrap();
+    assert_eq!(
+        (origin.conversation_id, origin.message_id),
+        (view.id.clone(), question)
+    );
+
+    let whole = conversations::fork_conversation(t.state(), view.id.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(whole.messages.len(), 2);
+
+    let empty = conversations::create_conversation(t.state(), "llama3.2:latest".to_string(), None)
+        .await
+        .unwrap();
+    let err = conversations::fork_conversation(t.state(), empty.id, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn conversations_are_listed_deleted_and_cleared() {
+    let t = TestApp::new().with_ollama();
+    let first = conversations::create_conversation(t.state(), "llama3.2:latest".to_string(), None)
+        .await
+        .unwrap();
+    let second = conversations::create_conversation(
+        t.state(),
+        "qwen3:8b".to_string(),
+        Some("Numbers".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(second.title, "Numbers");
+    assert_eq!(
+        conversations::list_conversations(t.state())
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    conversations::delete_conversation(t.state(), first.id.clone())
+        .await
+        .unwrap();
+    let listed = conversations::list_conversations(t.state()).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, second.id);
+    let err = conversations::get_conversation(t.state(), first.id)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    conversations::clear_conversations(t.state()).await.unwrap();
+    assert!(conversations::list_conversations(t.state())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn legacy_conversations_import_once() {
+    let t = TestApp::new();
+    let legacy = || {
+        vec![LegacyConversation {
+            id: "1712345678901".to_string(),
+            title: "Old chat".to_string(),
+            model: "llama3.2:latest".to_string(),
+            created_at: 1_712_345_678_901,
+            updated_at: 1_712_345_679_901,
+            messages: vec![
+                LegacyMessage {
+                    role: Role::User,
+                    content: "Hi".to_string(),
+                    timestamp: 1_712_345_678_901,
+                },
+                LegacyMessage {
+                    role: Role::Assistant,
+                    content: "Hello!".to_string(),
+                    timestamp: 1_712_345_679_901,
+                },
+            ],
+        }]
+    };
+
+    assert_eq!(
+        conversations::import_conversations(t.state(), legacy())
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        conversations::import_conversations(t.state(), legacy())
+            .await
+            .unwrap(),
+        0
+    );
+
+    let view = conversations::get_conversation(t.state(), "1712345678901".to_string())
+        .await
+        .unwrap();
+    assert_eq!(view.title, "Old chat");
+    assert_eq!(view.messages.len(), 2);
+    assert_eq!(
+        view.messages[1].message.model.as_deref(),
+        Some("llama3.2:latest")
+    );
+}
+
+#[tokio::test]
+async fn model_stats_aggregate_saved_replies() {
+    let t = TestApp::new().with_ollama();
+    let view = exchange(&t).await;
+    let answer = ids(&view)[1].clone();
+    let regenerated = conversations::regenerate_message(t.state(), view.id.clone(), answer, None)
+        .await
+        .unwrap();
+    let second = ids(&regenerated)[1].clone();
+    conversations::update_message(
+        t.handle(),
+        t.state(),
+        view.id.clone(),
+        second,
+        "Ownership moves values.".to_string(),
+        Some(stats(24, 56.5)),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let all = crate::stats::model_stats(t.state(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 1);
+    let llama = &all[0];
+    assert_eq!(llama.model, "llama3.2:latest");
+    assert_eq!(llama.messages, 2);
+    assert_eq!(llama.prompt_tokens, 62);
+    assert_eq!(llama.completion_tokens, 40);
+    assert_eq!(llama.avg_tokens_per_second, Some(50.0));
+    assert_eq!(llama.daily.len(), 1);
+
+    assert!(
+        crate::stats::model_stats(t.state(), Some("qwen3:8b".to_string()), None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(crate::stats::model_stats(t.state(), None, Some(u64::MAX))
+        .await
+        .unwrap()
+        .is_empty());
+}
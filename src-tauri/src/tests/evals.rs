@@ -0,0 +1,374 @@
+use axum::http::Method;
+use serde_json::Value;
+use std::fs;
+
+use super::mock::ollama;
+use super::TestApp;
+use crate::batch::{self, BatchRequest};
+use crate::evals::{self, CaseChangeKind};
+
+const SUITE: &str = r#"
+name: smoke
+models: [llama3.2:latest, qwen3:8b]
+judge_model: llama3.2:latest
+concurrency: 1
+cases:
+  - name: greeting
+    input: Hi
+    expect:
+      - contains: HELP
+      - not_contains: goodbye
+      - judge: Offers to help in a friendly way
+  - name: number
+    input: Pick a number
+    expect:
+      - regex: '^\d+$'
+"#;
+
+fn batch_request(model: &str, input_path: &std::path::Path) -> BatchRequest {
+    BatchRequest {
+        model: model.to_string(),
+        input_path: input_path.display().to_string(),
+        output_path: None,
+        template: Some("Tell me about {{topic}}".to_string()),
+        prompt_id: None,
+        prompt_version: None,
+        system: None,
+        options: None,
+        concurrency: Some(2),
+        run_id: Some("batch_test".to_string()),
+    }
+}
+
+// The complete lines of a batch output file
+fn output_lines(path: &str) -> Vec<Value> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[tokio::test]
+async fn eval_runs_score_every_model_and_store_the_report() {
+    let t = TestApp::new().with_ollama();
+    let progress = t.events("eval-progress");
+
+    let report = evals::run_eval(
+        t.handle(),
+        t.state(),
+        SUITE.to_string(),
+        None,
+        Some("eval_first".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.id, "eval_first");
+    assert!(report.baseline_id.is_none());
+    assert_eq!(report.results.len(), 4);
+
+    let outcome: Vec<_> = report
+        .results
+        .iter()
+        .map(|r| (r.model.as_str(), r.case.as_str(), r.passed))
+        .collect();
+    assert_eq!(
+        outcome,
+        [
+            ("llama3.2:latest", "greeting", true),
+            ("llama3.2:latest", "number", false),
+            ("qwen3:8b", "greeting", false),
+            ("qwen3:8b", "number", true),
+        ]
+    );
+    let judged = &report.results[0].assertions[2];
+    assert_eq!(judged.kind, "judge");
+    assert!((judged.score - 0.8).abs() < 1e-9);
+    assert_eq!(judged.detail.as_deref(), Some("Accurate and concise."));
+    assert_eq!(
+        report.results[1].assertions[0].detail.as_deref(),
+        Some("No match in the reply")
+    );
+    assert_eq!(report.models[1].model, "qwen3:8b");
+    assert_eq!((report.models[1].passed, report.models[1].failed), (1, 1));
+
+    // Only the greeting has a judge, and it asks for a structured verdict
+    let judge_requests: Vec<_> = t
+        .mock
+        .requests_to(Method::POST, &ollama("/api/chat"))
+        .into_iter()
+        .filter(|r| r.json()["stream"] == Value::Bool(false))
+        .collect();
+    assert_eq!(judge_requests.len(), 2);
+    assert!(judge_requests
+        .iter()
+        .all(|r| r.json()["model"] == "llama3.2:latest"));
+    assert!(judge_requests[0].json()["format"]["properties"]["score"].is_object());
+
+    let events = progress.all();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[3]["completed"], 4);
+    assert_eq!(events[3]["total"], 4);
+
+    let stored = evals::get_eval_report(t.handle(), "eval_first".to_string())
+        .await
+        .unwrap();
+    assert_eq!(stored.results.len(), 4);
+}
+
+#[tokio::test]
+async fn eval_reports_are_listed_diffed_and_deleted() {
+    let t = TestApp::new().with_ollama();
+    evals::run_eval(
+        t.handle(),
+        t.state(),
+        SUITE.to_string(),
+        Some(vec!["llama3.2:latest".to_string()]),
+        Some("eval_base".to_string()),
+    )
+    .await
+    .unwrap();
+    let err = evals::diff_eval_reports(t.handle(), "eval_base".to_string(), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input", "no earlier run");
+
+    let sent = t.mock.requests().len();
+    let err = evals::run_eval(
+        t.handle(),
+        t.state(),
+        SUITE.to_string(),
+        Some(vec!["llama3.2:latest".to_string()]),
+        Some("eval_base".to_string()),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "invalid_input", "reused run id");
+    assert_eq!(t.mock.requests().len(), sent);
+
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let relaxed = SUITE.replace(r"'^\d+$'", r"'\w+'");
+    let head = evals::run_eval(
+        t.handle(),
+        t.state(),
+        relaxed,
+        Some(vec!["llama3.2:latest".to_string()]),
+        Some("eval_head".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(head.baseline_id.as_deref(), Some("eval_base"));
+
+    let ids: Vec<_> = evals::list_eval_reports(t.handle(), None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(ids, ["eval_head", "eval_base"]);
+    assert!(
+        evals::list_eval_reports(t.handle(), Some("other".to_string()))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let diff = evals::diff_eval_reports(t.handle(), "eval_head".to_string(), None)
+        .await
+        .unwrap();
+    assert!(!diff.same_suite);
+    assert_eq!((diff.fixes, diff.regressions), (1, 0));
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.changes[0].case, "number");
+    assert_eq!(diff.changes[0].kind, CaseChangeKind::Fixed);
+    assert!((diff.models[0].delta.unwrap() - 0.5).abs() < 1e-9);
+
+    evals::delete_eval_report(t.handle(), "eval_base".to_string())
+        .await
+        .unwrap();
+    evals::delete_eval_report(t.handle(), "eval_base".to_string())
+        .await
+        .unwrap();
+    let err = evals::get_eval_report(t.handle(), "eval_base".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+    let err = evals::get_eval_report(t.handle(), "../updater".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn eval_suites_are_validated_before_anything_runs() {
+    let t = TestApp::new().with_ollama();
+    for suite in [
+        "name: [unclosed",
+        "name: empty\ncases: []",
+        "name: ' '\ncases:\n  - input: Hi",
+        "name: no input\nmodels: [qwen3:8b]\ncases:\n  - expect: [contains: x]",
+        "name: bad regex\nmodels: [qwen3:8b]\ncases:\n  - input: Hi\n    expect:\n      - regex: '('",
+        "name: no models\ncases:\n  - input: Hi",
+        "name: bad model\nmodels: ['bad model!']\ncases:\n  - input: Hi",
+    ] {
+        let err = evals::run_eval(t.handle(), t.state(), suite.to_string(), None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input", "{}", suite);
+    }
+    for run_id in ["", "eval-1", "../eval"] {
+        let err = evals::run_eval(
+            t.handle(),
+            t.state(),
+            SUITE.to_string(),
+            None,
+            Some(run_id.to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), "invalid_input", "{}", run_id);
+    }
+    assert!(t.mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn eval_cases_record_model_failures() {
+    let t = TestApp::new().with_ollama();
+    let report = evals::run_eval(
+        t.handle(),
+        t.state(),
+        SUITE.to_string(),
+        Some(vec!["missing:latest".to_string()]),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(report.id.starts_with("eval"));
+    assert!(report
+        .results
+        .iter()
+        .all(|r| r.error.is_some() && r.score == 0.0));
+    assert_eq!(report.models[0].errors, 2);
+}
+
+#[tokio::test]
+async fn batch_runs_every_row_and_resumes() {
+    let t = TestApp::new().with_ollama();
+    let progress = t.events("batch-progress");
+    let input = t.dir.path().join("topics.jsonl");
+    fs::write(
+        &input,
+        "{\"topic\": \"ownership\"}\n\n{\"topic\": \"lifetimes\"}\n{\"topic\": \"traits\", \"level\": 2}\n",
+    )
+    .unwrap();
+
+    let summary = batch::run_batch(
+        t.handle(),
+        t.state(),
+        batch_request("llama3.2:latest", &input),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        (
+            summary.total,
+            summary.succeeded,
+            summary.failed,
+            summary.skipped
+        ),
+        (3, 3, 0, 0)
+    );
+    assert!(summary.output_path.ends_with("topics.results.jsonl"));
+    let mut lines = output_lines(&summary.output_path);
+    lines.sort_by_key(|line| line["index"].as_u64());
+    assert_eq!(lines[0]["prompt"], "Tell me about ownership");
+    assert_eq!(lines[2]["input"]["level"], "2");
+    assert!(lines[0]["output"]
+        .as_str()
+        .unwrap()
+        .contains("How can I help?"));
+    assert_eq!(lines[0]["stats"]["completion_tokens"], 16);
+
+    let events = progress.all();
+    assert_eq!(events.len(), 4, "one at the start and one per row");
+    assert_eq!(events[0]["completed"], 0);
+    assert_eq!(events[3]["completed"], 3);
+    assert!(events.iter().all(|e| e["run_id"] == "batch_test"));
+
+    // A crash left half a line; edited and new rows run again, the rest are skipped
+    let mut output = fs::read_to_string(&summary.output_path).unwrap();
+    output.push_str("{\"index\": 3, \"input_h");
+    fs::write(&summary.output_path, output).unwrap();
+    fs::write(
+        &input,
+        "{\"topic\": \"ownership\"}\n{\"topic\": \"borrowing\"}\n{\"topic\": \"traits\", \"level\": 2}\n{\"topic\": \"macros\"}\n",
+    )
+    .unwrap();
+    let chats = t.mock.requests_to(Method::POST, &ollama("/api/chat")).len();
+    let summary = batch::run_batch(
+        t.handle(),
+        t.state(),
+        batch_request("llama3.2:latest", &input),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        (summary.total, summary.succeeded, summary.skipped),
+        (4, 2, 2)
+    );
+    assert_eq!(
+        t.mock.requests_to(Method::POST, &ollama("/api/chat")).len() - chats,
+        2
+    );
+    assert_eq!(output_lines(&summary.output_path).len(), 5);
+}
+
+#[tokio::test]
+async fn batch_records_failed_rows_and_rejects_bad_requests() {
+    let t = TestApp::new().with_ollama();
+    let input = t.dir.path().join("topics.csv");
+    fs::write(&input, "topic,level\nownership,1\n\"traits, generics\",2\n").unwrap();
+
+    let summary = batch::run_batch(
+        t.handle(),
+        t.state(),
+        batch_request("missing:latest", &input),
+    )
+    .await
+    .unwrap();
+    assert_eq!((summary.total, summary.failed), (2, 2));
+    let lines = output_lines(&summary.output_path);
+    assert!(lines
+        .iter()
+        .all(|l| l["error"]["code"] == "model_not_found"));
+    assert!(lines
+        .iter()
+        .any(|l| l["prompt"] == "Tell me about traits, generics"));
+
+    let requests = t.mock.requests().len();
+    let mut both = batch_request("llama3.2:latest", &input);
+    both.prompt_id = Some("prompt_1".to_string());
+    let mut unknown_column = batch_request("llama3.2:latest", &input);
+    unknown_column.template = Some("{{subject}}".to_string());
+    let mut same_file = batch_request("llama3.2:latest", &input);
+    same_file.output_path = Some(input.display().to_string());
+    let mut text_input = batch_request("llama3.2:latest", &input);
+    text_input.input_path = t.dir.path().join("topics.txt").display().to_string();
+    let mut missing_prompt = batch_request("llama3.2:latest", &input);
+    missing_prompt.template = None;
+    missing_prompt.prompt_id = Some("prompt_missing".to_string());
+    for request in [
+        both,
+        unknown_column,
+        same_file,
+        text_input,
+        missing_prompt,
+        batch_request("bad model!", &input),
+    ] {
+        let err = batch::run_batch(t.handle(), t.state(), request)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input");
+    }
+    assert_eq!(t.mock.requests().len(), requests);
+}
@@ -0,0 +1,301 @@
+use axum::http::Method;
+use serde_json::json;
+
+use super::mock::{gemini, Reply};
+use super::{message, TestApp};
+use crate::credentials::{self, Provider};
+use crate::{GeminiContent, GeminiPart};
+
+const GENERATE: &str = "/models/gemini-2.5-flash:generateContent";
+const STREAM: &str = "/models/gemini-2.5-flash:streamGenerateContent";
+
+fn contents(text: &str) -> Vec<GeminiContent> {
+    vec![GeminiContent {
+        role: "user".to_string(),
+        parts: vec![GeminiPart {
+            text: text.to_string(),
+        }],
+    }]
+}
+
+async fn send(t: &TestApp, api_key: &str) -> Result<String, crate::error::ZamaError> {
+    crate::send_gemini_chat(
+        t.handle(),
+        t.state(),
+        api_key.to_string(),
+        "gemini-2.5-flash".to_string(),
+        contents("What does the borrow checker do?"),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn gemini_chat_returns_the_first_candidate() {
+    let t = TestApp::new();
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::json(include_str!("fixtures/gemini/generate_content.json")),
+    );
+
+    let reply = send(&t, "AIza-test").await.unwrap();
+    assert_eq!(
+        reply,
+        "Rust's borrow checker enforces ownership rules at compile time."
+    );
+    let request = &t.mock.requests()[0];
+    assert!(!request.query.contains_key("key"), "kept out of the URL");
+    assert_eq!(request.header("x-goog-api-key"), Some("AIza-test"));
+    assert_eq!(
+        request.json(),
+        json!({ "contents": [{ "role": "user", "parts": [{ "text": "What does the borrow checker do?" }] }] })
+    );
+
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::value(&json!({ "candidates": [] })),
+    );
+    assert_eq!(
+        send(&t, "AIza-test").await.unwrap(),
+        "No response from Gemini."
+    );
+}
+
+#[tokio::test]
+async fn gemini_chat_falls_back_to_the_stored_key() {
+    let t = TestApp::new();
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::json(include_str!("fixtures/gemini/generate_content.json")),
+    );
+
+    let err = send(&t, " ").await.unwrap_err();
+    assert_eq!(err.code(), "provider_auth");
+    assert!(t.mock.requests().is_empty());
+
+    let configured = |t: &TestApp| {
+        credentials::get_provider_status(t.handle())
+            .into_iter()
+            .map(|s| (s.provider, s.configured))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        configured(&t),
+        [(Provider::Ollama, true), (Provider::Gemini, false)]
+    );
+
+    credentials::set_provider_key(
+        t.handle(),
+        Provider::Gemini,
+        Some(" AIza-stored ".to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        configured(&t),
+        [(Provider::Ollama, true), (Provider::Gemini, true)]
+    );
+    send(&t, "").await.unwrap();
+    assert_eq!(
+        t.mock.requests()[0].header("x-goog-api-key"),
+        Some("AIza-stored")
+    );
+
+    credentials::set_provider_key(t.handle(), Provider::Gemini, None).unwrap();
+    assert_eq!(
+        configured(&t),
+        [(Provider::Ollama, true), (Provider::Gemini, false)]
+    );
+    let err = credentials::set_provider_key(t.handle(), Provider::Ollama, Some("x".to_string()))
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn gemini_errors_map_to_error_codes() {
+    let t = TestApp::new();
+
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::error(400, include_str!("fixtures/gemini/api_key_invalid.json")),
+    );
+    let err = send(&t, "AIza-wrong").await.unwrap_err();
+    assert_eq!(err.code(), "provider_auth");
+    assert!(!err.to_string().contains("AIza-wrong"));
+
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::error(429, include_str!("fixtures/gemini/resource_exhausted.json"))
+            .header("retry-after", "12"),
+    );
+    let err = send(&t, "AIza-test").await.unwrap_err();
+    assert_eq!(
+        err,
+        crate::error::ZamaError::RateLimited {
+            provider: crate::GEMINI_SERVICE.to_string(),
+            retry_after: Some(12)
+        }
+    );
+
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::error(400, r#"{"error":{"code":400,"status":"INVALID_ARGUMENT"}}"#),
+    );
+    let err = send(&t, "AIza-test").await.unwrap_err();
+    assert!(matches!(
+        err,
+        crate::error::ZamaError::Http { status: 400, .. }
+    ));
+
+    t.mock.on(
+        Method::POST,
+        &gemini(GENERATE),
+        Reply::json("{\"candidates\": ["),
+    );
+    let err = send(&t, "AIza-test").await.unwrap_err();
+    assert_eq!(err.code(), "parse");
+}
+
+#[tokio::test]
+async fn compare_streams_every_model_side_by_side() {
+    let t = TestApp::new().with_ollama();
+    let chunks = t.events("compare-chunk");
+    let results = t.events("compare-result");
+    t.mock.on(
+        Method::POST,
+        &gemini(STREAM),
+        Reply::sse(include_str!("fixtures/gemini/stream_generate_content.sse")),
+    );
+    credentials::set_provider_key(t.handle(), Provider::Gemini, Some("AIza-test".to_string()))
+        .unwrap();
+
+    let compared = crate::compare::compare_chat(
+        t.handle(),
+        t.state(),
+        vec![
+            "llama3.2:latest".to_string(),
+            "gemini/gemini-2.5-flash".to_string(),
+        ],
+        vec![
+            message("system", "Be brief."),
+            message("user", "What does the borrow checker do?"),
+        ],
+        json!({ "temperature": 0.1, "num_predict": 64 })
+            .as_object()
+            .cloned(),
+        Some("compare_test".to_string()),
+    )
+    .await
+    .unwrap();
+
+    let [llama, flash] = [&compared[0], &compared[1]];
+    assert!(llama.error.is_none() && flash.error.is_none());
+    assert_eq!(
+        llama.content, "<think>Greeting, keep it short.</think>Hello! How can I help?",
+        "the truncated line is skipped"
+    );
+    assert_eq!(llama.prompt_tokens, Some(31));
+    assert_eq!(llama.completion_tokens, Some(16));
+    assert_eq!(flash.content, "Rust's borrow checker enforces ownership.");
+    assert_eq!(flash.prompt_tokens, Some(9));
+    assert_eq!(flash.completion_tokens, Some(7));
+    assert_eq!(flash.model, "gemini/gemini-2.5-flash");
+
+    let request = &t.mock.requests_to(Method::POST, &gemini(STREAM))[0];
+    assert_eq!(request.query["alt"], "sse");
+    assert!(!request.query.contains_key("key"));
+    assert_eq!(request.header("x-goog-api-key"), Some("AIza-test"));
+    let body = request.json();
+    assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be brief.");
+    assert_eq!(body["contents"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        body["generationConfig"],
+        json!({ "temperature": 0.1, "maxOutputTokens": 64 })
+    );
+
+    let chunks = chunks.all();
+    assert!(chunks.iter().all(|c| c["run_id"] == "compare_test"));
+    assert_eq!(
+        chunks
+            .iter()
+            .filter(|c| c["model"] == "gemini/gemini-2.5-flash")
+            .count(),
+        2
+    );
+    assert_eq!(results.all().len(), 2);
+}
+
+#[tokio::test]
+async fn compare_reports_failures_per_model() {
+    let t = TestApp::new().with_ollama();
+
+    let compared = crate::compare::compare_chat(
+        t.handle(),
+        t.state(),
+        vec![
+            "qwen3:8b".to_string(),
+            "missing:latest".to_string(),
+            "gemini/gemini-2.5-pro".to_string(),
+        ],
+        vec![message("user", "Pick a number")],
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(compared[0].run_id.starts_with("compare"));
+    assert!(compared[0].error.is_none());
+    assert_eq!(
+        compared[1].error.as_ref().unwrap().code(),
+        "model_not_found"
+    );
+    assert_eq!(compared[2].error.as_ref().unwrap().code(), "provider_auth");
+    assert!(t
+        .mock
+        .requests_to(
+            Method::POST,
+            &gemini("/models/gemini-2.5-pro:streamGenerateContent")
+        )
+        .is_empty());
+
+    // A rejected key is an auth error here too, not a generic 400
+    t.mock.on(
+        Method::POST,
+        &gemini("/models/gemini-2.5-pro:streamGenerateContent"),
+        Reply::error(400, include_str!("fixtures/gemini/api_key_invalid.json")),
+    );
+    credentials::set_provider_key(t.handle(), Provider::Gemini, Some("AIza-bad".to_string()))
+        .unwrap();
+    let compared = crate::compare::compare_chat(
+        t.handle(),
+        t.state(),
+        vec!["gemini/gemini-2.5-pro".to_string()],
+        vec![message("user", "Pick a number")],
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(compared[0].error.as_ref().unwrap().code(), "provider_auth");
+
+    let model = |name: &str| name.to_string();
+    for (models, messages) in [
+        (Vec::new(), vec![message("user", "Hi")]),
+        (vec![model("qwen3:8b"); 2], vec![message("user", "Hi")]),
+        (
+            (0..9).map(|i| format!("model{}", i)).collect(),
+            vec![message("user", "Hi")],
+        ),
+        (vec![model("qwen3:8b")], Vec::new()),
+        (vec![model("bad model!")], vec![message("user", "Hi")]),
+    ] {
+        let err = crate::compare::compare_chat(t.handle(), t.state(), models, messages, None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input");
+    }
+}
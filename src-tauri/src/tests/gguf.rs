@@ -0,0 +1,260 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use super::{TempDir, TestApp};
+use crate::gguf::{GgufReader, GgufValue};
+
+// Builds synthetic GGUF headers: metadata entries, then tensor infos, without tensor data
+pub(super) struct GgufWriter {
+    version: u32,
+    metadata: Vec<u8>,
+    kv_count: u64,
+    tensors: Vec<u8>,
+    tensor_count: u64,
+}
+
+impl GgufWriter {
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            metadata: Vec::new(),
+            kv_count: 0,
+            tensors: Vec::new(),
+            tensor_count: 0,
+        }
+    }
+
+    // GGUF v1 used 32-bit lengths and counts
+    fn count(&self, out: &mut Vec<u8>, count: u64) {
+        if self.version == 1 {
+            out.extend((count as u32).to_le_bytes());
+        } else {
+            out.extend(count.to_le_bytes());
+        }
+    }
+
+    fn string(&self, out: &mut Vec<u8>, value: &str) {
+        self.count(out, value.len() as u64);
+        out.extend(value.as_bytes());
+    }
+
+    // A metadata entry with an already encoded value
+    pub fn raw(mut self, key: &str, value_type: u32, value: &[u8]) -> Self {
+        let mut entry = Vec::new();
+        self.string(&mut entry, key);
+        entry.extend(value_type.to_le_bytes());
+        entry.extend(value);
+        self.metadata.extend(entry);
+        self.kv_count += 1;
+        self
+    }
+
+    pub fn text(self, key: &str, value: &str) -> Self {
+        let mut encoded = Vec::new();
+        self.string(&mut encoded, value);
+        self.raw(key, 8, &encoded)
+    }
+
+    pub fn uint(self, key: &str, value: u32) -> Self {
+        self.raw(key, 4, &value.to_le_bytes())
+    }
+
+    pub fn strings(self, key: &str, values: &[&str]) -> Self {
+        let mut encoded = 8u32.to_le_bytes().to_vec();
+        self.count(&mut encoded, values.len() as u64);
+        for value in values {
+            self.string(&mut encoded, value);
+        }
+        self.raw(key, 9, &encoded)
+    }
+
+    pub fn tensor(mut self, name: &str, shape: &[u64], ggml_type: u32, offset: u64) -> Self {
+        let mut info = Vec::new();
+        self.string(&mut info, name);
+        info.extend((shape.len() as u32).to_le_bytes());
+        for dim in shape {
+            self.count(&mut info, *dim);
+        }
+        info.extend(ggml_type.to_le_bytes());
+        info.extend(offset.to_le_bytes());
+        self.tensors.extend(info);
+        self.tensor_count += 1;
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let mut out = b"GGUF".to_vec();
+        out.extend(self.version.to_le_bytes());
+        self.count(&mut out, self.tensor_count);
+        self.count(&mut out, self.kv_count);
+        out.extend(&self.metadata);
+        out.extend(&self.tensors);
+        out
+    }
+
+    pub fn write(&self, path: &Path) {
+        std::fs::write(path, self.build()).unwrap();
+    }
+}
+
+// An array of `depth` nested arrays around a single u32
+fn nested_array(depth: usize) -> Vec<u8> {
+    let mut value = Vec::new();
+    for _ in 1..depth {
+        value.extend(9u32.to_le_bytes());
+        value.extend(1u64.to_le_bytes());
+    }
+    value.extend(4u32.to_le_bytes());
+    value.extend(1u64.to_le_bytes());
+    value.extend(7u32.to_le_bytes());
+    value
+}
+
+fn read(bytes: Vec<u8>) -> Result<crate::gguf::GgufHeader, String> {
+    GgufReader::new(Cursor::new(bytes)).read_header()
+}
+
+#[test]
+fn reads_every_metadata_type() {
+    let bytes = GgufWriter::new(3)
+        .raw("u8", 0, &[200])
+        .raw("i8", 1, &[0xff])
+        .raw("u16", 2, &500u16.to_le_bytes())
+        .raw("i16", 3, &(-500i16).to_le_bytes())
+        .uint("u32", 70_000)
+        .raw("i32", 5, &(-70_000i32).to_le_bytes())
+        .raw("f32", 6, &0.5f32.to_le_bytes())
+        .raw("bool", 7, &[1])
+        .text("string", "llama")
+        .raw("u64", 10, &(1u64 << 40).to_le_bytes())
+        .raw("i64", 11, &(-1i64 << 40).to_le_bytes())
+        .raw("f64", 12, &1e-6f64.to_le_bytes())
+        .build();
+
+    let header = read(bytes).unwrap();
+    assert_eq!(header.version, 3);
+    assert_eq!(header.tensor_count, 0);
+    let value = |key: &str| header.get(key).unwrap().clone();
+    assert_eq!(value("u8"), GgufValue::UInt(200));
+    assert_eq!(value("i8"), GgufValue::Int(-1));
+    assert_eq!(value("u16"), GgufValue::UInt(500));
+    assert_eq!(value("i16"), GgufValue::Int(-500));
+    assert_eq!(value("u32"), GgufValue::UInt(70_000));
+    assert_eq!(value("i32"), GgufValue::Int(-70_000));
+    assert_eq!(value("f32"), GgufValue::Float(0.5));
+    assert_eq!(value("bool"), GgufValue::Bool(true));
+    assert_eq!(value("string"), GgufValue::String("llama".to_string()));
+    assert_eq!(value("u64"), GgufValue::UInt(1 << 40));
+    assert_eq!(value("i64"), GgufValue::Int(-1 << 40));
+    assert_eq!(value("f64"), GgufValue::Float(1e-6));
+}
+
+#[test]
+fn keeps_only_the_start_of_long_arrays() {
+    let tokens: Vec<String> = (0..100).map(|i| format!("tok{}", i)).collect();
+    let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    let header = read(
+        GgufWriter::new(3)
+            .strings("tokenizer.ggml.tokens", &tokens)
+            .text("general.architecture", "llama")
+            .build(),
+    )
+    .unwrap();
+
+    let GgufValue::Array(array) = header.get("tokenizer.ggml.tokens").unwrap() else {
+        panic!("tokens should be an array");
+    };
+    assert_eq!(array.len, 100);
+    assert_eq!(array.values.len(), 64);
+    assert_eq!(array.values[63].as_str(), Some("tok63"));
+    assert_eq!(header.vocab_size(), Some(100));
+    assert_eq!(header.architecture(), Some("llama"), "read past the array");
+}
+
+#[test]
+fn reads_v1_headers_with_32_bit_counts() {
+    let mut reader = GgufReader::new(Cursor::new(
+        GgufWriter::new(1)
+            .text("general.architecture", "llama")
+            .uint("llama.context_length", 2048)
+            .tensor("output.weight", &[16, 8], 0, 0)
+            .build(),
+    ));
+    let header = reader.read_header().unwrap();
+    assert_eq!(header.version, 1);
+    assert_eq!(header.context_length(), Some(2048));
+
+    let tensors = reader.read_tensor_infos(header.tensor_count).unwrap();
+    assert_eq!(tensors[0].shape, [16, 8]);
+    assert_eq!(tensors[0].elements, 128);
+    assert_eq!(tensors[0].size_bytes, Some(128 * 4));
+}
+
+#[test]
+fn rejects_corrupt_headers() {
+    let mut big_endian = GgufWriter::new(3).build();
+    big_endian[4..8].copy_from_slice(&3u32.to_be_bytes());
+    let mut truncated = GgufWriter::new(3)
+        .text("general.architecture", "llama")
+        .build();
+    truncated.truncate(truncated.len() - 2);
+    let mut huge_string = GgufWriter::new(3).build();
+    huge_string[16..24].copy_from_slice(&1u64.to_le_bytes());
+    huge_string.extend(u64::MAX.to_le_bytes());
+
+    for (bytes, expected) in [
+        (b"GGML\x03\x00\x00\x00".to_vec(), "bad magic"),
+        (big_endian, "Unsupported GGUF version"),
+        (truncated, "Failed to read"),
+        (huge_string, "too long"),
+        (
+            GgufWriter::new(3).raw("odd", 13, &[0; 8]).build(),
+            "Unknown GGUF metadata value type 13",
+        ),
+        (
+            GgufWriter::new(3)
+                .raw("nested", 9, &nested_array(5))
+                .build(),
+            "nested more than 4 deep",
+        ),
+    ] {
+        let err = read(bytes).unwrap_err();
+        assert!(
+            err.contains(expected),
+            "{} should mention {}",
+            err,
+            expected
+        );
+    }
+
+    let header = read(
+        GgufWriter::new(3)
+            .raw("nested", 9, &nested_array(4))
+            .build(),
+    )
+    .unwrap();
+    assert!(matches!(header.get("nested"), Some(GgufValue::Array(_))));
+
+    let mut reader = GgufReader::new(Cursor::new(
+        GgufWriter::new(3).tensor("x", &[1; 9], 0, 0).build(),
+    ));
+    let header = reader.read_header().unwrap();
+    let err = reader.read_tensor_infos(header.tensor_count).unwrap_err();
+    assert!(err.contains("9 dimensions"));
+}
+
+#[tokio::test]
+async fn deeply_nested_arrays_are_parse_errors() {
+    let t = TestApp::new();
+    let dir = TempDir::new();
+    let path = dir.path().join("nested.gguf");
+    // Deep enough to overflow the stack without the cap
+    GgufWriter::new(3)
+        .raw("nested", 9, &nested_array(100_000))
+        .write(&path);
+
+    let err = crate::inspect_gguf(t.state(), Some(path.display().to_string()), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "parse");
+}
@@ -0,0 +1,285 @@
+use axum::body::{Body, Bytes};
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use futures::StreamExt;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::http::Endpoints;
+
+// An in-process stand-in for Ollama, Hugging Face, ollamadb.dev and Gemini. Each service gets its
+// own path prefix on one local port, and every request is recorded for assertions.
+
+const OLLAMA: &str = "/ollama";
+const OLLAMADB: &str = "/ollamadb";
+const HUGGING_FACE: &str = "/hf";
+const GEMINI: &str = "/gemini/v1beta";
+
+// Route paths on the mock, eg. `ollama("/api/chat")`
+pub fn ollama(path: &str) -> String {
+    format!("{}{}", OLLAMA, path)
+}
+
+pub fn ollamadb(path: &str) -> String {
+    format!("{}{}", OLLAMADB, path)
+}
+
+pub fn hugging_face(path: &str) -> String {
+    format!("{}{}", HUGGING_FACE, path)
+}
+
+pub fn gemini(path: &str) -> String {
+    format!("{}{}", GEMINI, path)
+}
+
+// A request as the mock saw it
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: Method,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl Recorded {
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or(Value::Null)
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+}
+
+// A canned response. The body goes out in the given chunks, so a line or event can be split
+// across reads the way a real stream splits them.
+#[derive(Debug, Clone)]
+pub struct Reply {
+    status: StatusCode,
+    headers: Vec<(String, String)>,
+    chunks: Vec<String>,
+    delay: Duration, // Before each chunk
+}
+
+impl Reply {
+    pub fn text(body: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::OK,
+            headers: Vec::new(),
+            chunks: vec![body.into()],
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn json(body: impl Into<String>) -> Self {
+        Self::text(body).header("content-type", "application/json")
+    }
+
+    pub fn value(body: &Value) -> Self {
+        Self::json(body.to_string())
+    }
+
+    // One chunk per line, like Ollama's streaming endpoints
+    pub fn ndjson(body: &str) -> Self {
+        Self::chunked(body.split_inclusive('\n')).header("content-type", "application/x-ndjson")
+    }
+
+    // One chunk per event, like Gemini's `alt=sse`
+    pub fn sse(body: &str) -> Self {
+        Self::chunked(body.split_inclusive("\n\n")).header("content-type", "text/event-stream")
+    }
+
+    pub fn chunked<S: Into<String>>(chunks: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            chunks: chunks.into_iter().map(Into::into).collect(),
+            ..Self::text("")
+        }
+    }
+
+    pub fn error(status: u16, body: impl Into<String>) -> Self {
+        Self::json(body).status(status)
+    }
+
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = StatusCode::from_u16(status).unwrap();
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn into_response(self) -> Response {
+        let delay = self.delay;
+        let chunks = futures::stream::iter(self.chunks).then(move |chunk| async move {
+            tokio::time::sleep(delay).await;
+            Ok::<_, Infallible>(Bytes::from(chunk))
+        });
+        let mut response = Response::new(Body::from_stream(chunks));
+        *response.status_mut() = self.status;
+        for (name, value) in self.headers {
+            response.headers_mut().insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(&value).unwrap(),
+            );
+        }
+        response
+    }
+}
+
+type Handler = Arc<dyn Fn(&Recorded) -> Reply + Send + Sync>;
+
+#[derive(Default)]
+struct Routes {
+    handlers: HashMap<(Method, String), Handler>,
+    requests: Vec<Recorded>,
+}
+
+pub struct MockServer {
+    base_url: String,
+    routes: Arc<Mutex<Routes>>,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    // Serves on its own thread so it outlives whichever runtime the code under test uses
+    pub fn start() -> Self {
+        let routes = Arc::new(Mutex::new(Routes::default()));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        let (shutdown, stopped) = tokio::sync::oneshot::channel::<()>();
+
+        let shared = routes.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                let app = Router::new().fallback(move |request: Request| {
+                    let routes = shared.clone();
+                    async move { respond(&routes, request).await }
+                });
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(async {
+                        let _ = stopped.await;
+                    })
+                    .await
+                    .unwrap();
+            });
+        });
+
+        Self {
+            base_url,
+            routes,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints {
+            ollama: self.url(OLLAMA),
+            ollamadb: self.url(OLLAMADB),
+            hugging_face: self.url(HUGGING_FACE),
+            gemini: self.url(GEMINI),
+        }
+    }
+
+    // Absolute URL of `path` on the mock, eg. for release manifests and artifacts
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    // Answers every request to `path` with `reply`, replacing an earlier mock for it
+    pub fn on(&self, method: Method, path: &str, reply: Reply) {
+        self.on_request(method, path, move |_| reply.clone());
+    }
+
+    // Answers the requests to `path` with `replies` in turn, then keeps repeating the last one
+    pub fn on_sequence(&self, method: Method, path: &str, replies: Vec<Reply>) {
+        let served = Mutex::new(0);
+        self.on_request(method, path, move |_| {
+            let mut served = served.lock().unwrap();
+            let reply = replies[(*served).min(replies.len() - 1)].clone();
+            *served += 1;
+            reply
+        });
+    }
+
+    pub fn on_request(
+        &self,
+        method: Method,
+        path: &str,
+        handler: impl Fn(&Recorded) -> Reply + Send + Sync + 'static,
+    ) {
+        self.routes
+            .lock()
+            .unwrap()
+            .handlers
+            .insert((method, path.to_string()), Arc::new(handler));
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.routes.lock().unwrap().requests.clone()
+    }
+
+    pub fn requests_to(&self, method: Method, path: &str) -> Vec<Recorded> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path == path)
+            .collect()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+async fn respond(routes: &Mutex<Routes>, request: Request) -> Response {
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map(|b| String::from_utf8_lossy(&b).into_owned())
+        .unwrap_or_default();
+    let query = reqwest::Url::parse(&format!("http://mock{}", parts.uri))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default();
+    let recorded = Recorded {
+        method: parts.method.clone(),
+        path: parts.uri.path().to_string(),
+        query,
+        headers: parts.headers,
+        body,
+    };
+
+    let handler = {
+        let mut routes = routes.lock().unwrap();
+        routes.requests.push(recorded.clone());
+        routes
+            .handlers
+            .get(&(recorded.method.clone(), recorded.path.clone()))
+            .cloned()
+    };
+    match handler {
+        Some(handler) => handler(&recorded).into_response(),
+        None => Reply::error(404, r#"{"error":"no mock for this route"}"#).into_response(),
+    }
+}
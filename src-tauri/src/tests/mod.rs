@@ -0,0 +1,221 @@
+// Headless integration tests. Commands run against a mock Tauri runtime whose app directories live
+// in a temporary folder, and every upstream service is replaced by `mock::MockServer` replaying
+// recorded responses from `fixtures/`.
+
+mod mock;
+
+mod catalog;
+mod chat;
+mod conversations;
+mod evals;
+mod gemini;
+mod gguf;
+mod models;
+mod prompts;
+mod server;
+mod settings;
+mod updater;
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+use tauri::utils::config::AppDirectoriesOverride;
+use tauri::{App, AppHandle, Listener, Manager};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::http::{HttpClient, HttpState, NetworkSettings};
+use crate::logging::LoggingState;
+use mock::{MockServer, Reply};
+
+// A directory removed again when the test ends
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "zama-test-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+// Tests share one process and so can't each install a global subscriber. The returned layer is
+// never installed, but keeping it alive lets the state's filter accept reloads.
+fn logging_state(log_dir: PathBuf) -> (LoggingState, reload::Layer<EnvFilter, Registry>) {
+    let (layer, filter) = reload::Layer::new(EnvFilter::new("info"));
+    let (_, guard) = tracing_appender::non_blocking(std::io::sink());
+    (LoggingState::new(log_dir, filter, guard), layer)
+}
+
+// The app with the state `run()` manages, pointed at a fresh mock server
+pub struct TestApp {
+    pub app: App<MockRuntime>,
+    pub mock: MockServer,
+    pub dir: TempDir,
+    _log_filter: reload::Layer<EnvFilter, Registry>,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        let mock = MockServer::start();
+        let dir = TempDir::new();
+
+        let mut context = mock_context(noop_assets());
+        context.config_mut().app.app_directories_override =
+            Some(AppDirectoriesOverride::Root(dir.path().to_path_buf()));
+
+        let settings = NetworkSettings {
+            max_retries: 0, // Error statuses reach the caller as recorded
+            ..NetworkSettings::default()
+        };
+        let client = HttpClient::new(&settings)
+            .unwrap()
+            .with_endpoints(mock.endpoints());
+        let (logging, log_filter) = logging_state(dir.path().join("logs"));
+        let app = mock_builder()
+            .manage(HttpState::new(client))
+            .manage(crate::updater::UpdaterState::default())
+            .manage(crate::server::ServerState::default())
+            .manage(logging)
+            .build(context)
+            .unwrap();
+        app.manage(crate::conversations::init(app.handle()).unwrap());
+        app.manage(crate::prompts::init(app.handle()).unwrap());
+
+        Self {
+            app,
+            mock,
+            dir,
+            _log_filter: log_filter,
+        }
+    }
+
+    pub fn handle(&self) -> AppHandle<MockRuntime> {
+        self.app.handle().clone()
+    }
+
+    pub fn state<T: Send + Sync + 'static>(&self) -> tauri::State<'_, T> {
+        self.app.state::<T>()
+    }
+
+    // Records the payloads of every `event` emitted from now on
+    pub fn events(&self, event: &str) -> Events {
+        let events = Events::default();
+        let received = events.0.clone();
+        self.app.listen_any(event, move |event| {
+            let payload = serde_json::from_str(event.payload()).unwrap_or(Value::Null);
+            received.lock().unwrap().push(payload);
+        });
+        events
+    }
+
+    // A running Ollama with `llama3.2:latest` and `qwen3:8b` pulled
+    pub fn with_ollama(self) -> Self {
+        use axum::http::Method;
+        use mock::ollama;
+
+        self.mock
+            .on(Method::GET, &ollama(""), Reply::text("Ollama is running"));
+        self.mock.on(
+            Method::GET,
+            &ollama("/api/version"),
+            Reply::json(include_str!("fixtures/ollama/version.json")),
+        );
+        self.mock.on(
+            Method::GET,
+            &ollama("/api/tags"),
+            Reply::json(include_str!("fixtures/ollama/tags.json")),
+        );
+        self.mock.on_request(
+            Method::POST,
+            &ollama("/api/show"),
+            |request| match request.json()["name"].as_str() {
+                Some("llama3.2:latest") => {
+                    Reply::json(include_str!("fixtures/ollama/show_llama3.2.json"))
+                }
+                Some("qwen3:8b") => Reply::json(include_str!("fixtures/ollama/show_qwen3.json")),
+                _ => Reply::error(404, include_str!("fixtures/ollama/not_found.json")),
+            },
+        );
+        self.mock
+            .on_request(Method::POST, &ollama("/api/chat"), |request| {
+                let body = request.json();
+                let model = body["model"].as_str().unwrap_or_default().to_string();
+                if body["stream"] == Value::Bool(false) {
+                    // Background jobs: titles, summaries and judging ask for one JSON reply
+                    return Reply::value(&serde_json::json!({
+                        "model": model,
+                        "created_at": "2025-10-14T09:12:01.118235Z",
+                        "message": { "role": "assistant", "content": complete(&body) },
+                        "done": true,
+                    }));
+                }
+                match model.as_str() {
+                    "llama3.2:latest" => {
+                        Reply::ndjson(include_str!("fixtures/ollama/chat_llama3.2.ndjson"))
+                    }
+                    "qwen3:8b" => Reply::ndjson(include_str!("fixtures/ollama/chat_qwen3.ndjson")),
+                    _ => Reply::error(404, include_str!("fixtures/ollama/not_found.json")),
+                }
+            });
+        self
+    }
+}
+
+// What the mock answers to a non-streaming chat, by the shape of reply it asks for
+fn complete(body: &Value) -> String {
+    let properties = &body["format"]["properties"];
+    if properties.get("title").is_some() {
+        r#"{"title":"Rust ownership basics","summary":"The user asked how Rust's borrow checker works."}"#
+            .to_string()
+    } else if properties.get("score").is_some() {
+        r#"{"score":8,"reason":"Accurate and concise."}"#.to_string()
+    } else {
+        "Earlier, the user greeted the assistant.".to_string()
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Events(Arc<Mutex<Vec<Value>>>);
+
+impl Events {
+    pub fn all(&self) -> Vec<Value> {
+        self.0.lock().unwrap().clone()
+    }
+
+    // Waits for at least `count` events, for work that finishes in the background
+    pub async fn wait_for(&self, count: usize) -> Vec<Value> {
+        for _ in 0..200 {
+            if self.0.lock().unwrap().len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+        self.all()
+    }
+}
+
+pub fn message(role: &str, content: &str) -> crate::ollama::Message {
+    crate::ollama::Message {
+        role: role.to_string(),
+        content: content.to_string(),
+        thinking: None,
+    }
+}
@@ -0,0 +1,479 @@
+use axum::http::Method;
+use serde_json::json;
+use std::path::Path;
+
+use super::gguf::GgufWriter;
+use super::mock::{ollama, Reply};
+use super::{TempDir, TestApp};
+use crate::error::ZamaError;
+use crate::http::{Endpoints, HttpClient, NetworkSettings};
+use crate::memory::{MemoryEstimateRequest, MemoryFit};
+use crate::ollama::{DeleteModelRequest, ShowModelRequest};
+
+// A tiny but well-formed GGUF v3 file: llama metadata and two tensors, without tensor data
+fn write_gguf(path: &Path) {
+    GgufWriter::new(3)
+        .text("general.architecture", "llama")
+        .text("general.name", "Tiny Llama")
+        .uint("general.file_type", 15)
+        .uint("llama.context_length", 4096)
+        .uint("llama.block_count", 2)
+        .uint("llama.embedding_length", 64)
+        .uint("llama.attention.head_count", 4)
+        .uint("llama.attention.head_count_kv", 2)
+        .text("tokenizer.chat_template", "{{ .Prompt }}")
+        .tensor("token_embd.weight", &[32, 32], 0, 0)
+        .tensor("blk.0.attn_q.weight", &[64, 64], 1, 4096)
+        .write(path);
+}
+
+// Serves `tiny:latest` with its weights at `blob`, like Ollama's generated Modelfile
+fn serve_tiny_model(t: &TestApp, blob: &Path) {
+    let show = json!({
+        "modelfile": format!("# Modelfile generated by \"ollama show\"\nFROM {}\n", blob.display()),
+        "details": {
+            "format": "gguf",
+            "family": "llama",
+            "parameter_size": "5.1K",
+            "quantization_level": "Q4_K_M"
+        },
+        "model_info": { "general.architecture": "llama" },
+        "capabilities": ["completion"]
+    });
+    t.mock
+        .on(Method::POST, &ollama("/api/show"), Reply::value(&show));
+}
+
+#[tokio::test]
+async fn lists_shows_and_deletes_models() {
+    let t = TestApp::new().with_ollama();
+
+    let listed = crate::list_ollama_models(t.state()).await.unwrap();
+    let names: Vec<_> = listed.models.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names[..2], ["llama3.2:latest", "qwen3:8b"]);
+    assert_eq!(listed.models[0].details.quantization_level, "Q4_K_M");
+
+    let show = crate::show_ollama_model(
+        t.state(),
+        ShowModelRequest {
+            name: "qwen3:8b".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(show.model, "qwen3:8b", "filled in from the request");
+    assert!(show.capabilities.contains(&"thinking".to_string()));
+    assert!(show.parameters.contains("num_ctx"));
+    assert_eq!(show.model_info["qwen3.context_length"], 40960);
+
+    let err = crate::show_ollama_model(
+        t.state(),
+        ShowModelRequest {
+            name: "missing:latest".to_string(),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "model_not_found");
+
+    t.mock
+        .on(Method::DELETE, &ollama("/api/delete"), Reply::text(""));
+    let deleted = crate::delete_ollama_model(
+        t.state(),
+        DeleteModelRequest {
+            name: "qwen3:8b".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(deleted, "Model deleted successfully");
+    let request = &t.mock.requests_to(Method::DELETE, &ollama("/api/delete"))[0];
+    assert_eq!(request.json()["name"], "qwen3:8b");
+
+    t.mock.on(
+        Method::DELETE,
+        &ollama("/api/delete"),
+        Reply::error(404, include_str!("fixtures/ollama/not_found.json")),
+    );
+    let err = crate::delete_ollama_model(
+        t.state(),
+        DeleteModelRequest {
+            name: "missing:latest".to_string(),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ZamaError::ModelNotFound {
+            model: "missing:latest".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn error_statuses_map_to_error_codes() {
+    let t = TestApp::new();
+    let tags = ollama("/api/tags");
+
+    t.mock.on(
+        Method::GET,
+        &tags,
+        Reply::error(429, r#"{"error":"too many requests"}"#).header("retry-after", "7"),
+    );
+    let err = crate::list_ollama_models(t.state()).await.unwrap_err();
+    assert_eq!(
+        err,
+        ZamaError::RateLimited {
+            provider: crate::ollama::OLLAMA_SERVICE.to_string(),
+            retry_after: Some(7)
+        }
+    );
+    assert!(err.retryable());
+
+    t.mock.on(
+        Method::GET,
+        &tags,
+        Reply::error(401, r#"{"error":"unauthorized"}"#),
+    );
+    let err = crate::list_ollama_models(t.state()).await.unwrap_err();
+    assert_eq!(err.code(), "provider_auth");
+
+    t.mock.on(
+        Method::GET,
+        &tags,
+        Reply::error(503, "upstream unavailable"),
+    );
+    let err = crate::list_ollama_models(t.state()).await.unwrap_err();
+    assert_eq!(
+        err,
+        ZamaError::Http {
+            status: 503,
+            body: "upstream unavailable".to_string()
+        }
+    );
+
+    t.mock
+        .on(Method::GET, &tags, Reply::json(r#"{"models": [{"name": "#));
+    let err = crate::list_ollama_models(t.state()).await.unwrap_err();
+    assert_eq!(err.code(), "parse");
+
+    let serialized = serde_json::to_value(&err).unwrap();
+    assert_eq!(serialized["code"], "parse");
+    assert_eq!(serialized["retryable"], false);
+}
+
+#[tokio::test]
+async fn idempotent_requests_retry_transient_statuses() {
+    let t = TestApp::new();
+    t.mock.on_sequence(
+        Method::GET,
+        &ollama("/api/tags"),
+        vec![
+            Reply::error(503, "loading").header("retry-after", "0"),
+            Reply::json(include_str!("fixtures/ollama/tags.json")),
+        ],
+    );
+    let client = HttpClient::new(&NetworkSettings::default())
+        .unwrap()
+        .with_endpoints(t.mock.endpoints());
+
+    let listed = crate::ollama::list_models(&client).await.unwrap();
+    assert!(!listed.models.is_empty());
+    assert_eq!(
+        t.mock.requests_to(Method::GET, &ollama("/api/tags")).len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn unreachable_ollama_is_reported_as_connection_refused() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let settings = NetworkSettings {
+        max_retries: 0,
+        ..NetworkSettings::default()
+    };
+    let client = HttpClient::new(&settings)
+        .unwrap()
+        .with_endpoints(Endpoints {
+            ollama: format!("http://127.0.0.1:{}", port),
+            ..Endpoints::default()
+        });
+
+    let err = crate::ollama::list_models(&client).await.unwrap_err();
+    assert_eq!(err.code(), "connection_refused");
+    assert!(err.retryable());
+    assert!(!crate::ollama::is_running(&client).await);
+}
+
+#[tokio::test]
+async fn reports_status_and_config_of_a_running_ollama() {
+    let t = TestApp::new().with_ollama();
+    let status = crate::check_ollama_status(t.state()).await.unwrap();
+    assert_eq!(status, "Ollama server is already running.");
+
+    t.mock.on(
+        Method::GET,
+        &ollama("/api/config"),
+        Reply::value(&json!({ "ollama_url": "http://127.0.0.1:11434" })),
+    );
+    let config = crate::get_ollama_config(t.state()).await.unwrap();
+    assert_eq!(config.ollama_url, "http://127.0.0.1:11434");
+
+    let version = crate::ollama::version(&t.state::<crate::http::HttpState>().client())
+        .await
+        .unwrap();
+    assert_eq!(version, "0.12.5");
+}
+
+#[tokio::test]
+async fn pull_model_rejects_invalid_names() {
+    let err = crate::pull_model("llama3.2; rm -rf /".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn inspects_gguf_files_by_path_and_by_model() {
+    let t = TestApp::new();
+    let dir = TempDir::new();
+    let path = dir.path().join("tiny.gguf");
+    write_gguf(&path);
+
+    let inspection = crate::inspect_gguf(t.state(), Some(path.display().to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(inspection.version, 3);
+    assert_eq!(inspection.name.as_deref(), Some("Tiny Llama"));
+    assert_eq!(inspection.architecture.as_deref(), Some("llama"));
+    assert_eq!(inspection.file_type.as_deref(), Some("Q4_K_M"));
+    assert_eq!(inspection.context_length, Some(4096));
+    assert_eq!(inspection.head_count_kv, Some(2));
+    assert_eq!(inspection.parameter_count, 32 * 32 + 64 * 64);
+    assert_eq!(inspection.chat_template.as_deref(), Some("{{ .Prompt }}"));
+    let types: Vec<_> = inspection
+        .tensor_types
+        .iter()
+        .map(|t| t.ggml_type.as_str())
+        .collect();
+    assert_eq!(types, ["F16", "F32"], "largest first");
+
+    serve_tiny_model(&t, &path);
+    let by_model = crate::inspect_gguf(t.state(), None, Some("tiny:latest".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(by_model.path, inspection.path);
+
+    let err = crate::inspect_gguf(t.state(), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let not_gguf = dir.path().join("notes.gguf");
+    std::fs::write(&not_gguf, "just text").unwrap();
+    let err = crate::inspect_gguf(t.state(), Some(not_gguf.display().to_string()), None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "parse");
+}
+
+#[tokio::test]
+async fn estimates_memory_from_sizes_and_installed_models() {
+    let t = TestApp::new();
+
+    let estimate = crate::estimate_memory(
+        t.state(),
+        MemoryEstimateRequest {
+            parameter_size: Some("8.2B".to_string()),
+            quantization_level: Some("Q4_K_M".to_string()),
+            num_ctx: Some(4096),
+            gpu_memory_bytes: Some(1024),
+            ..MemoryEstimateRequest::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(estimate.parameter_count, Some(8_200_000_000));
+    assert_eq!(estimate.num_ctx, 4096);
+    assert!(!estimate.kv_cache_exact);
+    assert_eq!(
+        estimate.total_bytes,
+        estimate.weights_bytes + estimate.kv_cache_bytes + estimate.overhead_bytes
+    );
+    assert!(!matches!(estimate.fit, MemoryFit::Fit), "1 KiB of VRAM");
+
+    let dir = TempDir::new();
+    let path = dir.path().join("tiny.gguf");
+    write_gguf(&path);
+    serve_tiny_model(&t, &path);
+    let estimate = crate::estimate_memory(
+        t.state(),
+        MemoryEstimateRequest {
+            model: Some("tiny:latest".to_string()),
+            ..MemoryEstimateRequest::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert!(estimate.kv_cache_exact, "shape read from the GGUF");
+    assert_eq!(estimate.parameter_count, Some(32 * 32 + 64 * 64));
+    assert_eq!(
+        estimate.weights_bytes,
+        std::fs::metadata(&path).unwrap().len()
+    );
+
+    let err = crate::estimate_memory(
+        t.state(),
+        MemoryEstimateRequest {
+            quantization_level: Some("Q99".to_string()),
+            ..MemoryEstimateRequest::default()
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+}
+
+#[tokio::test]
+async fn imports_gguf_files_uploading_only_missing_blobs() {
+    let t = TestApp::new();
+    let progress = t.events("gguf-import-progress");
+    let dir = TempDir::new();
+    let path = dir.path().join("tiny.gguf");
+    write_gguf(&path);
+    let digest = format!(
+        "sha256:{}",
+        crate::gguf_import::sha256_file(&path, |_| {}).unwrap()
+    );
+    let blob = ollama(&format!("/api/blobs/{}", digest));
+
+    // Missing until uploaded
+    t.mock.on_sequence(
+        Method::HEAD,
+        &blob,
+        vec![Reply::error(404, ""), Reply::text("")],
+    );
+    t.mock.on(Method::POST, &blob, Reply::text("").status(201));
+    t.mock.on(
+        Method::POST,
+        &ollama("/api/create"),
+        Reply::value(&json!({ "status": "success" })),
+    );
+
+    let result = crate::import_gguf(
+        t.handle(),
+        path.display().to_string(),
+        "tiny:latest".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(result.uploaded);
+    assert_eq!(result.digest, digest);
+    assert_eq!(result.architecture.as_deref(), Some("llama"));
+    assert_eq!(result.quantization.as_deref(), Some("Q4_K_M"));
+    assert_eq!(
+        result.modelfile,
+        "FROM ./tiny.gguf\nTEMPLATE \"\"\"{{ .Prompt }}\"\"\"\nPARAMETER num_ctx 4096\n"
+    );
+
+    let uploaded = &t.mock.requests_to(Method::POST, &blob)[0];
+    assert_eq!(uploaded.body.len() as u64, result.size);
+    let create = t.mock.requests_to(Method::POST, &ollama("/api/create"))[0].json();
+    // The GGUF's own template and trained window are what Ollama is asked to use
+    assert_eq!(
+        create,
+        json!({
+            "model": "tiny:latest",
+            "files": { "tiny.gguf": digest },
+            "template": "{{ .Prompt }}",
+            "parameters": { "num_ctx": 4096 },
+            "stream": false
+        })
+    );
+    let stages: Vec<_> = progress
+        .all()
+        .iter()
+        .map(|p| p["stage"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        stages,
+        ["reading", "hashing", "uploading", "creating", "done"]
+    );
+
+    // Present already: no upload, and the template reaches Ollama
+    let again = crate::import_gguf(
+        t.handle(),
+        path.display().to_string(),
+        "tiny:chat".to_string(),
+        Some("[INST] {{ .Prompt }} [/INST]".to_string()),
+    )
+    .await
+    .unwrap();
+    assert!(!again.uploaded);
+    assert_eq!(t.mock.requests_to(Method::POST, &blob).len(), 1);
+    let create = t.mock.requests_to(Method::POST, &ollama("/api/create"))[1].json();
+    assert_eq!(create["template"], "[INST] {{ .Prompt }} [/INST]");
+    assert!(again
+        .modelfile
+        .contains("TEMPLATE \"\"\"[INST] {{ .Prompt }} [/INST]\"\"\""));
+}
+
+#[tokio::test]
+async fn gguf_import_fails_when_ollama_loses_the_upload() {
+    let t = TestApp::new();
+    let dir = TempDir::new();
+    let path = dir.path().join("tiny.gguf");
+    write_gguf(&path);
+    let digest = format!(
+        "sha256:{}",
+        crate::gguf_import::sha256_file(&path, |_| {}).unwrap()
+    );
+    let blob = ollama(&format!("/api/blobs/{}", digest));
+    t.mock.on(Method::HEAD, &blob, Reply::error(404, ""));
+    t.mock.on(Method::POST, &blob, Reply::text("").status(201));
+
+    let err = crate::import_gguf(
+        t.handle(),
+        path.display().to_string(),
+        "tiny:latest".to_string(),
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "integrity");
+    assert!(t
+        .mock
+        .requests_to(Method::POST, &ollama("/api/create"))
+        .is_empty());
+
+    for (path, name) in [
+        ("relative/tiny.gguf", "tiny:latest"),
+        ("/tmp/zama-missing.gguf", "tiny:latest"),
+        ("/tmp/weights.bin", "tiny:latest"),
+        (path.to_str().unwrap(), "bad name!"),
+    ] {
+        let err = crate::import_gguf(t.handle(), path.to_string(), name.to_string(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input", "{} as {}", path, name);
+    }
+    let err = crate::import_gguf(
+        t.handle(),
+        path.display().to_string(),
+        "tiny:latest".to_string(),
+        Some("{{ .Prompt }}\"\"\"".to_string()),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.code(),
+        "invalid_input",
+        "can't be quoted in a Modelfile"
+    );
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use super::TestApp;
+use crate::prompts::{self, PromptFormat, PromptInput, PromptKind};
+
+fn input(id: Option<String>, content: &str) -> PromptInput {
+    PromptInput {
+        id,
+        name: "Reviewer".to_string(),
+        kind: PromptKind::System,
+        description: None,
+        tags: vec!["Code".to_string()],
+        content: content.to_string(),
+        note: None,
+    }
+}
+
+#[tokio::test]
+async fn prompts_keep_versions_and_survive_an_export_round_trip() {
+    let t = TestApp::new();
+    let saved = prompts::save_prompt(t.state(), input(None, "Review {{language}} code"))
+        .await
+        .unwrap();
+    let saved = prompts::save_prompt(
+        t.state(),
+        input(Some(saved.prompt.id), "Review {{language}} code tersely"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(saved.version, 2);
+    assert_eq!(saved.variables, ["language"]);
+
+    let vars = HashMap::from([("language".to_string(), "Rust".to_string())]);
+    let rendered = prompts::render_prompt(
+        t.state(),
+        saved.prompt.id.clone(),
+        Some(vars.clone()),
+        Some(1),
+    )
+    .await
+    .unwrap();
+    assert_eq!(rendered.content, "Review Rust code");
+    let err = prompts::render_prompt(t.state(), saved.prompt.id.clone(), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let listed = prompts::list_prompts(t.state(), Some("code".to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+
+    let exported = prompts::export_prompts(t.state(), PromptFormat::Yaml, None)
+        .await
+        .unwrap();
+    prompts::delete_prompt(t.state(), saved.prompt.id.clone())
+        .await
+        .unwrap();
+    assert!(prompts::get_prompt(t.state(), saved.prompt.id.clone())
+        .await
+        .is_err());
+
+    let imported = prompts::import_prompts(t.state(), exported).await.unwrap();
+    assert_eq!(imported.created, 1);
+    let restored = prompts::list_prompts(t.state(), None, None).await.unwrap();
+    assert_eq!(restored[0].prompt.name, "Reviewer");
+    assert_eq!(restored[0].prompt.versions.len(), 2);
+}
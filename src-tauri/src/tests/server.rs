@@ -0,0 +1,281 @@
+use axum::http::Method;
+use serde_json::{json, Value};
+
+use super::mock::{gemini, ollama, Reply};
+use super::TestApp;
+use crate::credentials::{self, Provider};
+use crate::server::{self, ServerStatus};
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn client() -> reqwest::Client {
+    reqwest::Client::builder().no_proxy().build().unwrap()
+}
+
+async fn enable(t: &TestApp) -> (ServerStatus, String) {
+    let status = server::set_server_settings(t.handle(), Some(true), Some(free_port()))
+        .await
+        .unwrap();
+    let base_url = status.base_url.clone().unwrap();
+    (status, base_url)
+}
+
+#[tokio::test]
+async fn server_starts_with_a_token_and_stops_again() {
+    let t = TestApp::new();
+    let status = server::get_server_status(t.handle()).await.unwrap();
+    assert!(!status.enabled && !status.running);
+    assert!(status.token.is_none());
+
+    let err = server::set_server_settings(t.handle(), Some(true), Some(80))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    // A taken port leaves the server off, also after a restart
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = taken.local_addr().unwrap().port();
+    let err = server::set_server_settings(t.handle(), Some(true), Some(port))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "io");
+    let status = server::get_server_status(t.handle()).await.unwrap();
+    assert!(!status.enabled && !status.running);
+    assert_eq!(status.port, port);
+    drop(taken);
+
+    let (status, base_url) = enable(&t).await;
+    assert!(status.enabled && status.running);
+    let token = status.token.unwrap();
+    assert!(token.starts_with("zama-") && token.len() == 69);
+    assert_eq!(base_url, format!("http://127.0.0.1:{}/v1", status.port));
+
+    let stopped = server::set_server_settings(t.handle(), Some(false), None)
+        .await
+        .unwrap();
+    assert!(!stopped.running);
+    assert_eq!(
+        stopped.token.as_deref(),
+        Some(token.as_str()),
+        "kept for next time"
+    );
+    assert!(client()
+        .get(format!("{}/models", base_url))
+        .send()
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn server_requires_the_current_token() {
+    let t = TestApp::new().with_ollama();
+    let (status, base_url) = enable(&t).await;
+    let old = status.token.unwrap();
+
+    let res = client()
+        .get(format!("{}/models", base_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 401);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "invalid_api_key");
+
+    // Checked before the body is read, so malformed bodies don't reveal anything either
+    for path in ["/chat/completions", "/embeddings"] {
+        let res = client()
+            .post(format!("{}{}", base_url, path))
+            .header("content-type", "text/plain")
+            .body("not json")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 401, "{}", path);
+    }
+
+    let res = client()
+        .get(format!("{}/models", base_url))
+        .bearer_auth(&old)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+
+    let status = server::regenerate_server_token(t.handle()).await.unwrap();
+    let new = status.token.unwrap();
+    assert_ne!(new, old);
+    let base_url = status.base_url.unwrap();
+    for (token, expected) in [(old, 401), (new, 200)] {
+        let res = client()
+            .get(format!("{}/models", base_url))
+            .bearer_auth(token)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), expected);
+    }
+}
+
+#[tokio::test]
+async fn server_lists_models_of_every_configured_provider() {
+    let t = TestApp::new();
+    t.mock.on(
+        Method::GET,
+        &ollama("/v1/models"),
+        Reply::json(include_str!("fixtures/ollama/openai_models.json")),
+    );
+    t.mock.on(
+        Method::GET,
+        &gemini("/openai/models"),
+        Reply::json(include_str!("fixtures/gemini/openai_models.json")),
+    );
+    let (status, base_url) = enable(&t).await;
+    let token = status.token.unwrap();
+    let list = || async {
+        client()
+            .get(format!("{}/models", base_url))
+            .bearer_auth(&token)
+            .send()
+            .await
+            .unwrap()
+            .json::<Value>()
+            .await
+            .unwrap()
+    };
+
+    let models = list().await;
+    let ids: Vec<_> = models["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, ["llama3.2:latest", "qwen3:8b"], "no Gemini key yet");
+    assert_eq!(models["data"][0]["owned_by"], "ollama");
+
+    credentials::set_provider_key(t.handle(), Provider::Gemini, Some("AIza-test".to_string()))
+        .unwrap();
+    let models = list().await;
+    let gemini_models: Vec<_> = models["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|m| m["owned_by"] == "gemini")
+        .map(|m| m["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        gemini_models,
+        ["gemini/gemini-2.5-flash", "gemini/gemini-2.5-pro"]
+    );
+    let request = &t.mock.requests_to(Method::GET, &gemini("/openai/models"))[0];
+    assert_eq!(request.header("authorization"), Some("Bearer AIza-test"));
+
+    // A provider that is down is left out
+    t.mock.on(
+        Method::GET,
+        &ollama("/v1/models"),
+        Reply::error(500, r#"{"error":"boom"}"#),
+    );
+    let models = list().await;
+    assert_eq!(models["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn server_forwards_chat_and_embeddings_to_the_owning_provider() {
+    let t = TestApp::new();
+    t.mock.on(
+        Method::POST,
+        &ollama("/v1/chat/completions"),
+        Reply::sse(include_str!("fixtures/ollama/openai_chat_completion.sse")),
+    );
+    t.mock.on(
+        Method::POST,
+        &gemini("/openai/embeddings"),
+        Reply::value(&json!({
+            "object": "list",
+            "data": [{ "object": "embedding", "index": 0, "embedding": [0.1, 0.2] }],
+            "model": "text-embedding-004"
+        })),
+    );
+    let (status, base_url) = enable(&t).await;
+    let token = status.token.unwrap();
+
+    let res = client()
+        .post(format!("{}/chat/completions", base_url))
+        .bearer_auth(&token)
+        .json(&json!({
+            "model": "ollama/llama3.2:latest",
+            "stream": true,
+            "messages": [{ "role": "user", "content": "Hi" }]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers()["content-type"], "text/event-stream");
+    assert_eq!(
+        res.text().await.unwrap(),
+        include_str!("fixtures/ollama/openai_chat_completion.sse")
+    );
+    let forwarded = &t
+        .mock
+        .requests_to(Method::POST, &ollama("/v1/chat/completions"))[0];
+    assert_eq!(forwarded.json()["model"], "llama3.2:latest");
+    assert_eq!(forwarded.header("authorization"), None);
+
+    let embed = || async {
+        client()
+            .post(format!("{}/embeddings", base_url))
+            .bearer_auth(&token)
+            .json(&json!({ "model": "gemini/text-embedding-004", "input": "Hi" }))
+            .send()
+            .await
+            .unwrap()
+    };
+    let res = embed().await;
+    assert_eq!(res.status(), 424, "no Gemini key configured");
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "provider_auth");
+
+    credentials::set_provider_key(t.handle(), Provider::Gemini, Some("AIza-test".to_string()))
+        .unwrap();
+    let res = embed().await;
+    assert_eq!(res.status(), 200);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["data"][0]["embedding"], json!([0.1, 0.2]));
+    let forwarded = &t
+        .mock
+        .requests_to(Method::POST, &gemini("/openai/embeddings"))[0];
+    assert_eq!(forwarded.json()["model"], "text-embedding-004");
+    assert_eq!(forwarded.header("authorization"), Some("Bearer AIza-test"));
+
+    // Upstream errors pass through with their status
+    t.mock.on(
+        Method::POST,
+        &ollama("/v1/chat/completions"),
+        Reply::error(404, r#"{"error":{"message":"model \"nope\" not found"}}"#),
+    );
+    let res = client()
+        .post(format!("{}/chat/completions", base_url))
+        .bearer_auth(&token)
+        .json(&json!({ "model": "nope", "messages": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 404);
+
+    let res = client()
+        .post(format!("{}/chat/completions", base_url))
+        .bearer_auth(&token)
+        .json(&json!({ "messages": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 400);
+}
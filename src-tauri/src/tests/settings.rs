@@ -0,0 +1,195 @@
+use axum::http::Method;
+use std::collections::BTreeMap;
+
+use super::mock::ollama;
+use super::TestApp;
+use crate::http::{self, HttpState, NetworkSettings};
+use crate::logging::{self, LogSettings, LoggingState};
+use crate::paths::{self, AppDirs};
+
+#[tokio::test]
+async fn network_settings_are_validated_saved_and_applied() {
+    let t = TestApp::new().with_ollama();
+    assert_eq!(
+        http::get_network_settings(t.handle()),
+        NetworkSettings::default()
+    );
+
+    for invalid in [
+        NetworkSettings {
+            connect_timeout_secs: 0,
+            ..NetworkSettings::default()
+        },
+        NetworkSettings {
+            max_retries: 11,
+            ..NetworkSettings::default()
+        },
+        NetworkSettings {
+            proxy_url: Some("not a url".to_string()),
+            ..NetworkSettings::default()
+        },
+        NetworkSettings {
+            ca_cert_path: Some("/nonexistent/ca.pem".to_string()),
+            ..NetworkSettings::default()
+        },
+    ] {
+        let err = http::set_network_settings(t.handle(), t.state(), invalid).unwrap_err();
+        assert_eq!(err.code(), "invalid_input");
+    }
+    assert_eq!(
+        http::get_network_settings(t.handle()),
+        NetworkSettings::default()
+    );
+
+    let settings = NetworkSettings {
+        read_timeout_secs: 60,
+        max_retries: 1,
+        ..NetworkSettings::default()
+    };
+    http::set_network_settings(t.handle(), t.state(), settings.clone()).unwrap();
+    assert_eq!(http::get_network_settings(t.handle()), settings);
+
+    // The replaced client keeps talking to the same endpoints
+    let client = t.state::<HttpState>().client();
+    assert_eq!(client.endpoints(), &t.mock.endpoints());
+    crate::list_ollama_models(t.state()).await.unwrap();
+    let request = &t.mock.requests_to(Method::GET, &ollama("/api/tags"))[0];
+    assert_eq!(
+        request.header("user-agent"),
+        Some(http::user_agent().as_str())
+    );
+}
+
+#[tokio::test]
+async fn log_settings_are_validated_and_saved() {
+    let t = TestApp::new();
+    assert_eq!(
+        logging::get_log_settings(t.handle()),
+        LogSettings::default()
+    );
+
+    let invalid = LogSettings {
+        filters: BTreeMap::from([("zama_lib::http".to_string(), "loud".to_string())]),
+        ..LogSettings::default()
+    };
+    let err = logging::set_log_settings(t.handle(), t.state(), invalid).unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let settings = LogSettings {
+        level: "debug".to_string(),
+        filters: BTreeMap::from([("zama_lib::updater".to_string(), "trace".to_string())]),
+        redact_content: true,
+    };
+    logging::set_log_settings(t.handle(), t.state(), settings.clone()).unwrap();
+    assert_eq!(logging::get_log_settings(t.handle()), settings);
+}
+
+#[tokio::test]
+async fn recent_logs_and_diagnostics_read_the_log_directory() {
+    let t = TestApp::new();
+    let log_dir = t.state::<LoggingState>().log_dir.clone();
+    std::fs::create_dir_all(&log_dir).unwrap();
+    std::fs::write(
+        log_dir.join("zama.2026-10-17.log"),
+        "2026-10-17T08:00:00.000000Z  INFO zama_lib: Starting Zama\n",
+    )
+    .unwrap();
+    std::fs::write(
+        log_dir.join("zama.2026-10-18.log"),
+        "2026-10-18T09:12:44.123456Z  WARN zama_lib::catalog: Serving cached catalog after fetch failed\n\
+         2026-10-18T09:12:45.000000Z DEBUG zama_lib::http: GET https://generativelanguage.googleapis.com/v1beta/models?key=AIzaSyDUMMYDUMMYDUMMYDUMMYDUMMYDUMMY12345\n\
+         2026-10-18T09:12:46.000000Z ERROR zama_lib::ollama: Failed to parse chat response chunk\n\
+         caused by: EOF while parsing a string\n",
+    )
+    .unwrap();
+
+    let all = logging::get_recent_logs(t.state(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 5);
+    assert_eq!(all[0].message, "Starting Zama");
+    assert_eq!(all[4].message, "caused by: EOF while parsing a string");
+
+    let warnings = logging::get_recent_logs(t.state(), None, Some("warn".to_string()))
+        .await
+        .unwrap();
+    let messages: Vec<_> = warnings.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Serving cached catalog after fetch failed",
+            "Failed to parse chat response chunk",
+            "caused by: EOF while parsing a string",
+        ]
+    );
+    assert_eq!(warnings[0].target.as_deref(), Some("zama_lib::catalog"));
+
+    let last = logging::get_recent_logs(t.state(), Some(1), None)
+        .await
+        .unwrap();
+    assert_eq!(last.len(), 1);
+
+    let bundle = logging::export_diagnostics(t.handle()).await.unwrap();
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(&bundle).unwrap()).unwrap();
+    let mut names: Vec<_> = zip.file_names().map(str::to_string).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "app_info.json",
+            "logs/zama.2026-10-17.log",
+            "logs/zama.2026-10-18.log",
+            "system.json",
+        ]
+    );
+    let mut log = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("logs/zama.2026-10-18.log").unwrap(),
+        &mut log,
+    )
+    .unwrap();
+    assert!(log.contains("key=[REDACTED]"));
+    assert!(!log.contains("AIzaSyDUMMY"));
+}
+
+#[test]
+fn app_info_reports_the_build() {
+    let t = TestApp::new();
+    let info = crate::app_info(t.handle());
+    assert_eq!(info.version, crate::app_info::VERSION);
+    assert_eq!(info.os, std::env::consts::OS);
+    assert!(!info.target.is_empty());
+}
+
+#[test]
+fn json_files_are_replaced_atomically_and_fall_back_when_unreadable() {
+    let dir = super::TempDir::new();
+    let paths = AppDirs::in_dir(dir.path());
+    let path = dir.path().join("nested/settings.json");
+    assert_eq!(paths::load_json::<LogSettings>(&path).unwrap(), None);
+
+    let settings = LogSettings {
+        level: "debug".to_string(),
+        ..LogSettings::default()
+    };
+    paths::write_json_atomic(&path, &settings).unwrap();
+    assert_eq!(paths::load_json(&path).unwrap(), Some(settings.clone()));
+    assert!(!path.with_extension("json.tmp").exists());
+
+    paths::save_settings(&paths, "logging.json", &settings).unwrap();
+    assert_eq!(
+        paths::load_settings::<LogSettings>(&paths, "logging.json"),
+        settings
+    );
+    std::fs::write(paths.config.join("logging.json"), "{ not json").unwrap();
+    assert_eq!(
+        paths::load_json::<LogSettings>(&paths.config.join("logging.json"))
+            .unwrap_err()
+            .code(),
+        "parse"
+    );
+    assert_eq!(
+        paths::load_settings::<LogSettings>(&paths, "logging.json"),
+        LogSettings::default()
+    );
+}
@@ -0,0 +1,495 @@
+use axum::http::Method;
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey};
+use semver::Version;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+
+use super::mock::Reply;
+use super::{TempDir, TestApp};
+use crate::http::HttpState;
+use crate::updater::{self, ArtifactKind, ReleaseArtifact, UpdateChannel, UpdaterSettings};
+
+const ARTIFACT: &[u8] = b"#!/bin/sh\necho zama 0.2.0\n";
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn public_key() -> String {
+    base64::engine::general_purpose::STANDARD.encode(signing_key().verifying_key().as_bytes())
+}
+
+fn sign(message: &str) -> String {
+    base64::engine::general_purpose::STANDARD
+        .encode(signing_key().sign(message.as_bytes()).to_bytes())
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// A binary artifact for this platform signed with the test key
+fn artifact(t: &TestApp, version: &str) -> ReleaseArtifact {
+    t.mock.on(
+        Method::GET,
+        "/releases/zama",
+        Reply::text(String::from_utf8_lossy(ARTIFACT)),
+    );
+    let sha256 = sha256(ARTIFACT);
+    ReleaseArtifact {
+        url: t.mock.url("/releases/zama"),
+        signature: sign(&updater::signed_message(
+            version,
+            &updater::platform_key(),
+            &sha256,
+        )),
+        sha256,
+        size: ARTIFACT.len() as u64,
+        kind: ArtifactKind::Binary,
+    }
+}
+
+#[test]
+fn versions_compare_by_semver_precedence() {
+    assert!(updater::is_update_available("0.1.4", "0.1.5"));
+    assert!(updater::is_update_available("0.1.4", "0.10.0"));
+    assert!(updater::is_update_available("0.2.0-beta.1", "0.2.0-beta.2"));
+    assert!(updater::is_update_available("0.2.0-rc.1", "0.2.0"));
+    assert!(!updater::is_update_available("0.1.4", "0.1.4"));
+    assert!(!updater::is_update_available("0.1.5", "0.1.4"));
+    assert!(!updater::is_update_available("0.2.0", "0.2.0-rc.1"));
+    assert!(!updater::is_update_available("0.1.4", "v0.1.5"));
+    assert!(!updater::is_update_available("latest", "0.1.5"));
+}
+
+#[test]
+fn channels_only_take_their_own_pre_releases() {
+    let version = |v: &str| Version::parse(v).unwrap();
+    for channel in [
+        UpdateChannel::Stable,
+        UpdateChannel::Beta,
+        UpdateChannel::Nightly,
+    ] {
+        assert!(channel.accepts(&version("0.2.0")));
+    }
+    assert!(!UpdateChannel::Stable.accepts(&version("0.2.0-beta.1")));
+    assert!(UpdateChannel::Beta.accepts(&version("0.2.0-beta.1")));
+    assert!(UpdateChannel::Beta.accepts(&version("0.2.0-rc.2")));
+    assert!(!UpdateChannel::Beta.accepts(&version("0.2.0-nightly.20261018")));
+    assert!(UpdateChannel::Nightly.accepts(&version("0.2.0-nightly.20261018")));
+
+    assert!(!updater::is_update_for_channel(
+        UpdateChannel::Stable,
+        "0.1.4",
+        "0.2.0-rc.1"
+    ));
+    assert!(updater::is_update_for_channel(
+        UpdateChannel::Beta,
+        "0.1.4",
+        "0.2.0-rc.1"
+    ));
+    assert!(!updater::is_update_for_channel(
+        UpdateChannel::Beta,
+        "0.2.0",
+        "0.2.0-rc.1"
+    ));
+    assert!(!updater::is_update_for_channel(
+        UpdateChannel::Nightly,
+        "0.1.4",
+        "next"
+    ));
+}
+
+#[test]
+fn signatures_cover_version_platform_and_hash() {
+    let hash = sha256(ARTIFACT);
+    let message = updater::signed_message("0.2.0", "linux-x86_64", &hash.to_uppercase());
+    assert_eq!(
+        message,
+        format!("zama-update\n0.2.0\nlinux-x86_64\n{}", hash)
+    );
+    let signature = sign(&message);
+    updater::verify_signature(&public_key(), &message, &signature).unwrap();
+
+    for replayed in [
+        updater::signed_message("0.2.1", "linux-x86_64", &hash),
+        updater::signed_message("0.2.0", "windows-x86_64", &hash),
+        updater::signed_message("0.2.0", "linux-x86_64", &sha256(b"tampered")),
+    ] {
+        assert!(updater::verify_signature(&public_key(), &replayed, &signature).is_err());
+    }
+    assert!(updater::verify_signature("not base64!", &message, &signature).is_err());
+    assert!(updater::verify_signature(&public_key(), &message, "c2hvcnQ=").is_err());
+}
+
+#[tokio::test]
+async fn downloads_are_checked_before_they_are_kept() {
+    let t = TestApp::new();
+    let client = t.state::<HttpState>().client();
+    let dest = t.dir.path().join("updates/zama-0.2.0.download");
+    let platform = updater::platform_key();
+
+    let good = artifact(&t, "0.2.0");
+    let mut progress = Vec::new();
+    updater::download_and_verify(
+        &client,
+        &good,
+        "0.2.0",
+        &platform,
+        &public_key(),
+        &dest,
+        |done, total| progress.push((done, total)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(fs::read(&dest).unwrap(), ARTIFACT);
+    assert_eq!(
+        progress.last(),
+        Some(&(ARTIFACT.len() as u64, Some(ARTIFACT.len() as u64)))
+    );
+
+    // A signature for another release is refused before anything is downloaded
+    let requests = t.mock.requests().len();
+    let err = updater::download_and_verify(
+        &client,
+        &good,
+        "0.2.1",
+        &platform,
+        &public_key(),
+        &dest,
+        |_, _| {},
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "integrity");
+    assert_eq!(t.mock.requests().len(), requests);
+
+    // A correctly signed hash that doesn't match the bytes served
+    let hash = sha256(b"something else");
+    let mismatched = ReleaseArtifact {
+        signature: sign(&updater::signed_message("0.2.0", &platform, &hash)),
+        sha256: hash,
+        ..good.clone()
+    };
+    let err = updater::download_and_verify(
+        &client,
+        &mismatched,
+        "0.2.0",
+        &platform,
+        &public_key(),
+        &dest,
+        |_, _| {},
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "integrity");
+    assert!(!dest.exists(), "the rejected download is removed");
+
+    let oversized = ReleaseArtifact {
+        size: 4,
+        ..good.clone()
+    };
+    let err = updater::download_and_verify(
+        &client,
+        &oversized,
+        "0.2.0",
+        &platform,
+        &public_key(),
+        &dest,
+        |_, _| {},
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "integrity");
+    assert!(!dest.exists());
+
+    // However large the manifest claims the artifact is, nothing past the cap is fetched
+    let requests = t.mock.requests().len();
+    let huge = ReleaseArtifact {
+        size: u64::MAX,
+        ..good.clone()
+    };
+    let err = updater::download_and_verify(
+        &client,
+        &huge,
+        "0.2.0",
+        &platform,
+        &public_key(),
+        &dest,
+        |_, _| {},
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "integrity");
+    assert_eq!(t.mock.requests().len(), requests);
+
+    t.mock.on(
+        Method::GET,
+        "/releases/zama",
+        Reply::error(404, "Not Found"),
+    );
+    let err = updater::download_and_verify(
+        &client,
+        &good,
+        "0.2.0",
+        &platform,
+        &public_key(),
+        &dest,
+        |_, _| {},
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.code(), "http");
+}
+
+#[test]
+fn binaries_are_staged_and_swapped_in() {
+    let dir = TempDir::new();
+    let target = dir.path().join("zama");
+    let download = dir.path().join("zama-0.2.0.download");
+    fs::write(&target, "old").unwrap();
+    fs::write(&download, ARTIFACT).unwrap();
+
+    let staged = updater::stage_artifact(&download, ArtifactKind::Binary, &target).unwrap();
+    assert_eq!(staged, dir.path().join("zama.zama-new"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(
+            fs::metadata(&staged).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    updater::replace_with_rollback(&target, &staged).unwrap();
+    assert_eq!(fs::read(&target).unwrap(), ARTIFACT);
+    assert!(!staged.exists());
+    assert!(!dir.path().join("zama.zama-backup").exists());
+
+    // A staged file that has gone missing leaves the current install in place
+    let err = updater::replace_with_rollback(&target, &staged).unwrap_err();
+    assert!(err.contains("rolled back"), "{}", err);
+    assert_eq!(fs::read(&target).unwrap(), ARTIFACT);
+}
+
+#[test]
+fn app_bundles_are_unpacked_from_the_archive() {
+    let dir = TempDir::new();
+    let target = dir.path().join("Zama.app");
+    fs::create_dir_all(target.join("Contents/MacOS")).unwrap();
+    fs::write(target.join("Contents/MacOS/zama"), "old").unwrap();
+
+    let archive = dir.path().join("zama.tar.gz");
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        fs::File::create(&archive).unwrap(),
+        flate2::Compression::fast(),
+    ));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(ARTIFACT.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "Zama.app/Contents/MacOS/zama", ARTIFACT)
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let staged = updater::stage_artifact(&archive, ArtifactKind::AppBundle, &target).unwrap();
+    updater::replace_with_rollback(&target, &staged).unwrap();
+    assert_eq!(
+        fs::read(target.join("Contents/MacOS/zama")).unwrap(),
+        ARTIFACT
+    );
+    assert!(!dir.path().join("Zama.app.zama-new-unpack").exists());
+
+    // An archive without a bundle is refused
+    let empty = dir.path().join("empty.tar.gz");
+    let builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        fs::File::create(&empty).unwrap(),
+        flate2::Compression::fast(),
+    ));
+    builder.into_inner().unwrap().finish().unwrap();
+    assert!(updater::stage_artifact(&empty, ArtifactKind::AppBundle, &target).is_err());
+}
+
+#[test]
+fn update_settings_are_validated_and_saved() {
+    let t = TestApp::new();
+    let settings = updater::get_update_settings(t.handle());
+    assert_eq!(settings.channel, UpdateChannel::Stable);
+    assert!(settings.auto_update && settings.manifest_url.is_none());
+    assert_eq!(
+        updater::manifest_url(&settings),
+        "https://raw.githubusercontent.com/myferr/zama/main/pkg/stable.json"
+    );
+
+    for url in [
+        "http://updates.example.com/{channel}.json",
+        "http://localhost.example.com/{channel}.json",
+        "http://localhost@updates.example.com/{channel}.json",
+        "file:///etc/{channel}.json",
+        "updates.example.com/{channel}.json",
+    ] {
+        let err = updater::set_update_settings(t.handle(), None, None, Some(url.to_string()))
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_input", "{}", url);
+    }
+    updater::set_update_settings(
+        t.handle(),
+        None,
+        None,
+        Some("http://localhost:8080/{channel}.json".to_string()),
+    )
+    .unwrap();
+
+    let settings = updater::set_update_settings(
+        t.handle(),
+        Some(UpdateChannel::Beta),
+        Some(false),
+        Some(" https://updates.example.com/{channel}.json ".to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        updater::manifest_url(&settings),
+        "https://updates.example.com/beta.json"
+    );
+    let saved = updater::get_update_settings(t.handle());
+    assert_eq!(saved.channel, UpdateChannel::Beta);
+    assert!(!saved.auto_update);
+
+    let settings =
+        updater::set_update_settings(t.handle(), None, None, Some(String::new())).unwrap();
+    assert!(settings.manifest_url.is_none());
+    assert_eq!(settings.channel, UpdateChannel::Beta);
+
+    // A hand-edited settings file isn't trusted either
+    let edited = UpdaterSettings {
+        manifest_url: Some("http://updates.example.com/{channel}.json".to_string()),
+        ..settings
+    };
+    assert_eq!(
+        updater::manifest_url(&edited),
+        "https://raw.githubusercontent.com/myferr/zama/main/pkg/beta.json"
+    );
+}
+
+#[tokio::test]
+async fn checks_report_skipped_and_deferred_updates() {
+    let t = TestApp::new();
+    let artifact = artifact(&t, "0.2.0");
+    t.mock.on(
+        Method::GET,
+        "/pkg/stable.json",
+        Reply::value(&json!({
+            "version": "0.2.0",
+            "notes": "Faster model pulls",
+            "pub_date": "2026-10-01T00:00:00Z",
+            "platforms": { (updater::platform_key()): artifact }
+        })),
+    );
+    t.mock.on(
+        Method::GET,
+        "/pkg/beta.json",
+        Reply::value(&json!({ "version": "0.3.0-beta.1", "platforms": {} })),
+    );
+    let manifest_url = t.mock.url("/pkg/{channel}.json");
+    updater::set_update_settings(t.handle(), None, None, Some(manifest_url)).unwrap();
+
+    let check = updater::check_for_updates(t.handle()).await.unwrap();
+    assert_eq!(check.current_version, crate::app_info::VERSION);
+    let update = check.update.unwrap();
+    assert_eq!(update.version, "0.2.0");
+    assert_eq!(update.notes.as_deref(), Some("Faster model pulls"));
+    assert!(!check.skipped && check.remind_after.is_none());
+
+    assert_eq!(
+        updater::skip_update(t.handle(), "soon".to_string())
+            .unwrap_err()
+            .code(),
+        "invalid_input"
+    );
+    updater::skip_update(t.handle(), "0.2.0".to_string()).unwrap();
+    assert!(
+        updater::check_for_updates(t.handle())
+            .await
+            .unwrap()
+            .skipped
+    );
+
+    let until = updater::remind_update_later(t.handle(), Some(10_000)).unwrap();
+    let check = updater::check_for_updates(t.handle()).await.unwrap();
+    assert_eq!(check.remind_after, Some(until));
+    assert!(check.skipped, "the skip still stands");
+    let now = until - 30 * 24 * 60 * 60;
+    let until = updater::remind_update_later(t.handle(), Some(0)).unwrap();
+    assert!(
+        until >= now + 60 * 60 && until < now + 2 * 60 * 60,
+        "clamped to an hour"
+    );
+
+    // A beta release without a build for this platform is not offered
+    updater::set_update_settings(t.handle(), Some(UpdateChannel::Beta), None, None).unwrap();
+    let check = updater::check_for_updates(t.handle()).await.unwrap();
+    assert!(check.update.is_none());
+    assert_eq!(check.channel, UpdateChannel::Beta);
+}
+
+#[tokio::test]
+async fn installs_need_a_matching_pending_update_and_a_trusted_key() {
+    let t = TestApp::new();
+    let err = updater::install_update(t.handle(), t.state(), "0.2.0".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "invalid_input");
+
+    let artifact = artifact(&t, "0.2.0");
+    t.mock.on(
+        Method::GET,
+        "/pkg/stable.json",
+        Reply::value(
+            &json!({ "version": "0.2.0", "platforms": { (updater::platform_key()): artifact } }),
+        ),
+    );
+    let manifest_url = t
+        .mock
+        .url("/pkg/stable.json")
+        .replace("127.0.0.1", "localhost");
+    updater::set_update_settings(t.handle(), None, None, Some(manifest_url)).unwrap();
+    updater::check_for_updates(t.handle()).await.unwrap();
+
+    let err = updater::install_update(t.handle(), t.state(), "0.1.9".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.code(),
+        "invalid_input",
+        "only the version the user agreed to"
+    );
+
+    if option_env!("ZAMA_UPDATER_PUBLIC_KEY").is_none() {
+        let err = updater::install_update(t.handle(), t.state(), "0.2.0".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "integrity");
+        assert_eq!(t.mock.requests_to(Method::GET, "/releases/zama").len(), 0);
+    }
+
+    t.mock.on(
+        Method::GET,
+        "/pkg/stable.json",
+        Reply::error(503, "Service Unavailable"),
+    );
+    let err = updater::check_for_updates(t.handle()).await.unwrap_err();
+    assert_eq!(err.code(), "http");
+    t.mock.on(
+        Method::GET,
+        "/pkg/stable.json",
+        Reply::json("{\"version\":"),
+    );
+    let err = updater::check_for_updates(t.handle()).await.unwrap_err();
+    assert_eq!(err.code(), "parse");
+}
@@ -0,0 +1,82 @@
+use serde::Serialize;
+
+use crate::ollama::ShowModelResponse;
+
+const OPEN_TAG: &str = "<think>";
+const CLOSE_TAG: &str = "</think>";
+
+// Ollama lists `thinking` among a model's capabilities when it accepts the `think` flag
+const THINKING_CAPABILITY: &str = "thinking";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ThinkingChunk {
+    pub model: String,
+    pub content: String,
+}
+
+// Whether the model behind `show` supports Ollama's native thinking channel
+pub fn supports_thinking(show: &ShowModelResponse) -> bool {
+    show.capabilities.iter().any(|c| c == THINKING_CAPABILITY)
+}
+
+// Splits streamed text into answer and thinking on `<think>` tags, for models that predate the
+// native channel. A tag cut across two chunks is held back until the next chunk completes it.
+#[derive(Debug, Default)]
+pub struct ThinkTagParser {
+    in_think: bool,
+    pending: String, // Tail that may be the start of a tag
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Split {
+    pub answer: String,
+    pub thinking: String,
+}
+
+impl ThinkTagParser {
+    pub fn push(&mut self, text: &str) -> Split {
+        let mut buffer = std::mem::take(&mut self.pending);
+        buffer.push_str(text);
+        let mut split = Split::default();
+
+        loop {
+            let tag = if self.in_think { CLOSE_TAG } else { OPEN_TAG };
+            let out = if self.in_think {
+                &mut split.thinking
+            } else {
+                &mut split.answer
+            };
+            if let Some(pos) = buffer.find(tag) {
+                out.push_str(&buffer[..pos]);
+                buffer.drain(..pos + tag.len());
+                self.in_think = !self.in_think;
+                continue;
+            }
+            // Keep back the longest suffix that could still grow into the tag
+            let keep = (1..tag.len())
+                .rev()
+                .find(|&n| buffer.ends_with(&tag[..n]))
+                .unwrap_or(0);
+            let emit = buffer.len() - keep;
+            out.push_str(&buffer[..emit]);
+            self.pending = buffer[emit..].to_string();
+            return split;
+        }
+    }
+
+    // Flushes a held-back partial tag once the stream has ended
+    pub fn finish(&mut self) -> Split {
+        let rest = std::mem::take(&mut self.pending);
+        if self.in_think {
+            Split {
+                answer: String::new(),
+                thinking: rest,
+            }
+        } else {
+            Split {
+                answer: rest,
+                thinking: String::new(),
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::conversations::{Conversation, ConversationStore, Role};
+use crate::credentials::Provider;
+use crate::error::ZamaError;
+use crate::ollama::{self, Message};
+use crate::{http, paths, server};
+
+const SETTINGS_FILE: &str = "titles.json";
+const MAX_TITLE_CHARS: usize = 60;
+
+// Enough of the conversation to name it; long pastes are cut so a small model stays fast
+const MAX_MESSAGES: usize = 4;
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+const TITLE_PROMPT: &str = "You name chat conversations. Reply with JSON: `title` is 3 to 6 \
+words describing the topic, without quotes or trailing punctuation; `summary` is one short \
+paragraph on what was asked and answered.";
+
+// Conversations with a job running, so a double trigger doesn't ask the model twice
+static IN_FLIGHT: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct TitleSettings {
+    pub enabled: bool,
+    pub model: Option<String>, // Local model to use; None: the conversation's own Ollama model
+}
+
+impl Default for TitleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            model: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GeneratedTitle {
+    title: String,
+    summary: String,
+}
+
+fn load_settings<R: Runtime>(app_handle: &AppHandle<R>) -> TitleSettings {
+    paths::load_settings(app_handle, SETTINGS_FILE)
+}
+
+fn save_settings<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    settings: &TitleSettings,
+) -> Result<(), ZamaError> {
+    paths::save_settings(app_handle, SETTINGS_FILE, settings)
+}
+
+// The configured model, else the conversation's own when it runs on Ollama
+fn titling_model(settings: &TitleSettings, conversation: &Conversation) -> Option<String> {
+    if let Some(model) = settings.model.as_deref().filter(|m| !m.trim().is_empty()) {
+        return Some(model.trim().to_string());
+    }
+    match server::route_model(&conversation.model) {
+        (Provider::Ollama, name) if !name.is_empty() => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+fn transcript(conversation: &Conversation) -> String {
+    conversation
+        .active_path()
+        .into_iter()
+        .filter(|m| m.role != Role::System && !m.content.trim().is_empty())
+        .take(MAX_MESSAGES)
+        .map(|m| {
+            let role = if m.role == Role::User {
+                "User"
+            } else {
+                "Assistant"
+            };
+            let content: String = m.content.chars().take(MAX_MESSAGE_CHARS).collect();
+            format!("{}: {}", role, content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// Models like to wrap titles in quotes or end them with a period
+fn clean_title(title: &str) -> String {
+    let title = title
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(|c: char| c == '"' || c == '\'' || c == '`' || c == '*')
+        .trim_end_matches(['.', ':'])
+        .trim();
+    if title.chars().count() > MAX_TITLE_CHARS {
+        format!(
+            "{}...",
+            title
+                .chars()
+                .take(MAX_TITLE_CHARS)
+                .collect::<String>()
+                .trim_end()
+        )
+    } else {
+        title.to_string()
+    }
+}
+
+async fn generate<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    conversation_id: &str,
+    overwrite: bool,
+) -> Result<(), ZamaError> {
+    let settings = load_settings(app_handle);
+    let store = app_handle.state::<ConversationStore>();
+    let id = conversation_id.to_string();
+    let conversation = store.run(move |store| store.load(&id)).await?;
+    let Some(model) = titling_model(&settings, &conversation) else {
+        tracing::debug!(
+            conversation = conversation_id,
+            "No local model to title the conversation with"
+        );
+        return Ok(());
+    };
+    let transcript = transcript(&conversation);
+    if transcript.is_empty() {
+        return Ok(());
+    }
+
+    let prompt = [
+        Message {
+            role: "system".to_string(),
+            content: TITLE_PROMPT.to_string(),
+            thinking: None,
+        },
+        Message {
+            role: "user".to_string(),
+            content: transcript,
+            thinking: None,
+        },
+    ];
+    let format = json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string" },
+            "summary": { "type": "string" },
+        },
+        "required": ["title", "summary"],
+    });
+    let reply = ollama::complete(&http::client(app_handle), &model, &prompt, Some(format)).await?;
+    let generated: GeneratedTitle = serde_json::from_str(&reply)
+        .map_err(|e| ZamaError::Parse(format!("Invalid title response: {}", e)))?;
+    let title = clean_title(&generated.title);
+    let summary = generated.summary.trim().to_string();
+
+    let id = conversation_id.to_string();
+    let (conversation, _) = store
+        .run(move |store| {
+            store.update(&id, |conversation| {
+                if !title.is_empty() && (overwrite || !conversation.title_edited) {
+                    conversation.title = title;
+                    conversation.title_edited = false;
+                }
+                if !summary.is_empty() {
+                    conversation.summary = Some(summary);
+                }
+                Ok(())
+            })
+        })
+        .await?;
+    tracing::info!(
+        conversation = conversation_id,
+        model,
+        "Generated conversation title"
+    );
+    app_handle.emit("conversation-titled", conversation.view())?;
+    Ok(())
+}
+
+// Titles and summarizes `conversation_id` in the background; the result arrives as a
+// `conversation-titled` event. `overwrite` also replaces a title the user set.
+pub fn spawn<R: Runtime>(app_handle: AppHandle<R>, conversation_id: String, overwrite: bool) {
+    if !overwrite && !load_settings(&app_handle).enabled {
+        return;
+    }
+    if !IN_FLIGHT.lock().unwrap().insert(conversation_id.clone()) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = generate(&app_handle, &conversation_id, overwrite).await {
+            tracing::warn!(conversation = %conversation_id, error = %e, "Failed to generate conversation title");
+        }
+        IN_FLIGHT.lock().unwrap().remove(&conversation_id);
+    });
+}
+
+// Returns at once; the new title and summary follow as a `conversation-titled` event
+#[tauri::command]
+pub async fn generate_title<R: Runtime>(
+    app_handle: AppHandle<R>,
+    store: tauri::State<'_, ConversationStore>,
+    conversation_id: String,
+) -> Result<(), ZamaError> {
+    let id = conversation_id.clone();
+    store.run(move |store| store.load(&id)).await?;
+    spawn(app_handle, conversation_id, true);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_title_settings<R: Runtime>(app_handle: AppHandle<R>) -> TitleSettings {
+    load_settings(&app_handle)
+}
+
+#[tauri::command]
+pub fn set_title_settings<R: Runtime>(
+    app_handle: AppHandle<R>,
+    settings: TitleSettings,
+) -> Result<TitleSettings, ZamaError> {
+    if let Some(model) = settings.model.as_deref().filter(|m| !m.trim().is_empty()) {
+        ollama::validate_model_name(model.trim())?;
+    }
+    save_settings(&app_handle, &settings)?;
+    Ok(settings)
+}
@@ -1,60 +1,234 @@
+use crate::error::ZamaError;
+use crate::http::{self, HttpClient};
+use crate::paths;
+use base64::Engine;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use futures::StreamExt;
 use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
-
-// Function to get the current version from pkg/version.json
-pub fn get_current_version() -> Result<String, String> {
-    println!("Attempting to get current version from ../pkg/version.json...");
-    let version_path = PathBuf::from("../pkg/version.json");
-    let content = fs::read_to_string(&version_path)
-        .map_err(|e| format!("Failed to read version.json: {}", e))?;
-    let json: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse version.json: {}", e))?;
-    let version = json["version"]
-        .as_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Version not found in version.json".to_string())?;
-    println!("Successfully read current version: {}", version);
-    Ok(version)
-}
-
-// Function to fetch the latest version from GitHub
-pub async fn get_latest_version() -> Result<String, String> {
-    let url = "https://raw.githubusercontent.com/myferr/zama/main/pkg/version.json";
-    println!("Attempting to fetch latest version from GitHub: {}", url);
-    let response = reqwest::get(url)
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+// Release manifests, one per channel. Custom URLs may use the same `{channel}` placeholder.
+pub const DEFAULT_MANIFEST_URL: &str =
+    "https://raw.githubusercontent.com/myferr/zama/main/pkg/{channel}.json";
+
+const DEFAULT_REMIND_AFTER_HOURS: u64 = 24;
+
+// Base64 Ed25519 public key matching the release signing key. It is baked in at build time so
+// that nothing fetched at runtime can change which signatures are trusted.
+const UPDATER_PUBLIC_KEY: Option<&str> = option_env!("ZAMA_UPDATER_PUBLIC_KEY");
+
+const SETTINGS_FILE: &str = "updater.json";
+const UPDATE_SERVICE: &str = "the update server";
+const STAGING_DIR: &str = "updates";
+const BACKUP_SUFFIX: &str = "zama-backup";
+const NEW_SUFFIX: &str = "zama-new";
+// No release comes anywhere near this; it bounds what a bad manifest can make us write to disk
+const MAX_ARTIFACT_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+// --- Updater Schemas ---
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct UpdaterSettings {
+    pub manifest_url: Option<String>,
+    pub channel: UpdateChannel,
+    pub auto_update: bool, // Check on launch; when off, updates are only found through `check_for_updates`
+    pub skipped_version: Option<String>,
+    pub remind_after: Option<u64>, // Unix seconds before which launch checks stay quiet
+}
+
+impl Default for UpdaterSettings {
+    fn default() -> Self {
+        Self {
+            manifest_url: None,
+            channel: UpdateChannel::Stable,
+            auto_update: true,
+            skipped_version: None,
+            remind_after: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl UpdateChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+            UpdateChannel::Nightly => "nightly",
+        }
+    }
+
+    // Stable only takes plain releases, beta also takes `-beta`/`-rc` pre-releases and nightly
+    // takes anything
+    pub fn accepts(&self, version: &Version) -> bool {
+        if version.pre.is_empty() {
+            return true;
+        }
+        let pre = version.pre.as_str();
+        match self {
+            UpdateChannel::Stable => false,
+            UpdateChannel::Beta => pre.starts_with("beta") || pre.starts_with("rc"),
+            UpdateChannel::Nightly => true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+    pub platforms: HashMap<String, ReleaseArtifact>, // Keyed by `platform_key()`, eg. "darwin-aarch64"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReleaseArtifact {
+    pub url: String,
+    pub sha256: String,
+    pub signature: String, // Base64 Ed25519 signature over `signed_message()`
+    pub size: u64,         // Downloads stop as soon as they exceed it
+    pub kind: ArtifactKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Binary,    // A single executable or AppImage replacing the running one
+    AppBundle, // A `.tar.gz` containing `Zama.app`
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub version: String,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateCheck {
+    pub current_version: String,
+    pub channel: UpdateChannel,
+    pub update: Option<UpdateInfo>,
+    pub skipped: bool,             // The user chose to skip this version
+    pub remind_after: Option<u64>, // Set while "remind me later" is in effect
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateProgress {
+    pub version: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+// An update the user has been told about but hasn't agreed to yet
+#[derive(Debug, Clone)]
+struct PendingUpdate {
+    version: String,
+    artifact: ReleaseArtifact,
+}
+
+#[derive(Default)]
+pub struct UpdaterState {
+    pending: Mutex<Option<PendingUpdate>>,
+}
+
+// --- Settings ---
+
+pub fn load_settings<R: Runtime>(app_handle: &AppHandle<R>) -> UpdaterSettings {
+    paths::load_settings(app_handle, SETTINGS_FILE)
+}
+
+pub fn save_settings<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    settings: &UpdaterSettings,
+) -> Result<(), ZamaError> {
+    paths::save_settings(app_handle, SETTINGS_FILE, settings)
+}
+
+// The environment wins so that managed machines can point at an internal mirror. Neither it nor
+// a hand-edited settings file went through `set_update_settings`, so a URL that fails validation
+// is skipped in favor of the next one.
+pub fn manifest_url(settings: &UpdaterSettings) -> String {
+    let configured = [
+        (
+            "ZAMA_UPDATE_MANIFEST_URL",
+            std::env::var("ZAMA_UPDATE_MANIFEST_URL").ok(),
+        ),
+        ("settings", settings.manifest_url.clone()),
+    ];
+    configured
+        .into_iter()
+        .filter_map(|(source, url)| Some((source, url?.trim().to_string())))
+        .filter(|(_, url)| !url.is_empty())
+        .find(|(source, url)| {
+            validate_manifest_url(url)
+                .inspect_err(|e| tracing::warn!(source, %url, error = %e, "Ignoring manifest URL"))
+                .is_ok()
+        })
+        .map_or_else(|| DEFAULT_MANIFEST_URL.to_string(), |(_, url)| url)
+        .replace("{channel}", settings.channel.as_str())
+}
+
+// Manifests decide what gets installed, so only https is accepted, plus plain http to this
+// machine for testing a local release
+pub fn validate_manifest_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(&url.replace("{channel}", UpdateChannel::Stable.as_str()))
+        .map_err(|e| format!("Invalid manifest URL: {}", e))?;
+    match (parsed.scheme(), parsed.host_str()) {
+        ("https", Some(_)) => Ok(()),
+        ("http", Some("localhost" | "127.0.0.1")) => Ok(()),
+        _ => Err("Manifest URL must use https".to_string()),
+    }
+}
+
+// Version of the running build, embedded at compile time
+pub fn get_current_version() -> String {
+    crate::app_info::VERSION.to_string()
+}
+
+// --- Manifest ---
+
+pub async fn fetch_manifest(client: &HttpClient, url: &str) -> Result<ReleaseManifest, ZamaError> {
+    tracing::debug!(%url, "Fetching release manifest");
+    let response = client
+        .send_idempotent(client.get(url))
         .await
-        .map_err(|e| format!("Failed to fetch latest version: {}", e))?;
-    let content = response
-        .text()
+        .map_err(|e| ZamaError::request(UPDATE_SERVICE, e))?;
+    if !response.status().is_success() {
+        return Err(ZamaError::from_response(UPDATE_SERVICE, response, None).await);
+    }
+    response
+        .json::<ReleaseManifest>()
         .await
-        .map_err(|e| format!("Failed to read response text: {}", e))?;
-    let json: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse latest version JSON: {}", e))?;
-    let version = json["version"]
-        .as_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Version not found in latest version JSON".to_string())?;
-    println!("Successfully fetched latest version: {}", version);
-    Ok(version)
+        .map_err(|e| ZamaError::Parse(format!("Failed to parse release manifest: {}", e)))
 }
 
 // Function to compare versions using semantic versioning
 pub fn is_update_available(current_version: &str, latest_version: &str) -> bool {
-    println!(
-        "Comparing current version ({}) with latest version ({})",
-        current_version, latest_version
-    );
+    tracing::debug!(current_version, latest_version, "Comparing versions");
 
     // Parse versions using semver for proper comparison
     let current = match Version::parse(current_version) {
         Ok(v) => v,
         Err(e) => {
-            eprintln!(
-                "Failed to parse current version '{}': {}",
-                current_version, e
-            );
+            tracing::warn!(current_version, error = %e, "Failed to parse current version");
             return false;
         }
     };
@@ -62,163 +236,523 @@ pub fn is_update_available(current_version: &str, latest_version: &str) -> bool
     let latest = match Version::parse(latest_version) {
         Ok(v) => v,
         Err(e) => {
-            eprintln!("Failed to parse latest version '{}': {}", latest_version, e);
+            tracing::warn!(latest_version, error = %e, "Failed to parse latest version");
             return false;
         }
     };
 
     let update = latest > current;
-    if update {
-        println!("Update is available.");
-    } else {
-        println!("No update available.");
-    }
+    tracing::debug!(update, "Version comparison done");
     update
 }
 
-// Function to uninstall the current application (macOS specific)
-pub fn uninstall_app() -> Result<(), String> {
-    println!("Attempting to uninstall Zama.app...");
-    let app_name = "Zama.app";
-    let common_app_paths = vec![
-        PathBuf::from("/Applications").join(app_name),
-        PathBuf::from(format!(
-            "{}/Applications",
-            std::env::var("HOME").unwrap_or_default()
-        ))
-        .join(app_name),
-    ];
-
-    for app_path in common_app_paths {
-        if app_path.exists() {
-            println!("Found app at: {:?}", app_path);
-            // Move to Trash (macOS specific command) - using proper escaping
-            let app_path_str = app_path.to_string_lossy();
-            // Escape single quotes in the path to prevent AppleScript injection
-            let escaped_path = app_path_str.replace("'", "'\"'\"'");
-            let applescript_cmd = format!(
-                "tell app \"Finder\" to move POSIX file \"{}\" to trash",
-                escaped_path
+// Whether `latest_version` is an update for `channel`. Pre-releases are compared by semver
+// precedence, so `0.2.0-beta.2` beats `0.2.0-beta.1` and `0.2.0` beats both.
+pub fn is_update_for_channel(
+    channel: UpdateChannel,
+    current_version: &str,
+    latest_version: &str,
+) -> bool {
+    match Version::parse(latest_version) {
+        Ok(latest) if channel.accepts(&latest) => {
+            is_update_available(current_version, latest_version)
+        }
+        Ok(_) => {
+            tracing::info!(
+                latest_version,
+                channel = channel.as_str(),
+                "Ignoring release outside the update channel"
             );
-
-            let output = Command::new("osascript")
-                .arg("-e")
-                .arg(applescript_cmd)
-                .output()
-                .map_err(|e| format!("Failed to execute osascript for uninstall: {}", e))?;
-
-            if output.status.success() {
-                println!("Successfully moved {:?} to Trash.", app_path);
-                return Ok(());
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                eprintln!("Failed to move {:?} to Trash. Stderr: {}", app_path, stderr);
-                return Err(format!(
-                    "Failed to move {:?} to Trash: {}",
-                    app_path, stderr
-                ));
-            }
+            false
+        }
+        Err(e) => {
+            tracing::warn!(latest_version, error = %e, "Failed to parse latest version");
+            false
         }
     }
-    println!("Zama.app not found in common application directories. Skipping uninstall.");
-    Err("Zama.app not found in common application directories.".to_string())
 }
 
-// Function to install the latest application using secure download and verification
-pub async fn install_app() -> Result<(), String> {
-    println!("Attempting to install the latest version of Zama...");
-    let install_script_url =
-        "https://raw.githubusercontent.com/myferr/zama/main/scripts/install.sh";
+// Manifest key for the running build, eg. "darwin-aarch64", "linux-x86_64", "windows-x86_64"
+pub fn platform_key() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{}-{}", os, std::env::consts::ARCH)
+}
+
+// --- Verification ---
 
-    // Securely download the install script first
-    println!("Downloading install script from: {}", install_script_url);
-    let response = reqwest::get(install_script_url)
-        .await
-        .map_err(|e| format!("Failed to download install script: {}", e))?;
+// The signature covers the version and platform as well as the hash, so a validly signed
+// artifact can't be replayed as a different release or for a different OS
+pub fn signed_message(version: &str, platform: &str, sha256: &str) -> String {
+    format!(
+        "zama-update\n{}\n{}\n{}",
+        version,
+        platform,
+        sha256.to_ascii_lowercase()
+    )
+}
+
+pub fn verify_signature(public_key: &str, message: &str, signature: &str) -> Result<(), String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let key_bytes: [u8; 32] = engine
+        .decode(public_key.trim())
+        .map_err(|e| format!("Invalid updater public key: {}", e))?
+        .try_into()
+        .map_err(|_| "Updater public key must be 32 bytes".to_string())?;
+    let key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| format!("Invalid updater public key: {}", e))?;
+
+    let signature_bytes: [u8; 64] = engine
+        .decode(signature.trim())
+        .map_err(|e| format!("Invalid update signature: {}", e))?
+        .try_into()
+        .map_err(|_| "Update signature must be 64 bytes".to_string())?;
+    key.verify(message.as_bytes(), &Signature::from_bytes(&signature_bytes))
+        .map_err(|_| "Update signature verification failed".to_string())
+}
+
+// Downloads `artifact` into `dest`, hashing as it streams, and only keeps the file if both the
+// SHA-256 and the Ed25519 signature check out
+pub async fn download_and_verify(
+    client: &HttpClient,
+    artifact: &ReleaseArtifact,
+    version: &str,
+    platform: &str,
+    public_key: &str,
+    dest: &Path,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<(), ZamaError> {
+    // Check the signature over the advertised hash before downloading anything
+    verify_signature(
+        public_key,
+        &signed_message(version, platform, &artifact.sha256),
+        &artifact.signature,
+    )
+    .map_err(ZamaError::Integrity)?;
+    if artifact.size > MAX_ARTIFACT_BYTES {
+        return Err(ZamaError::Integrity(format!(
+            "Update is advertised at {} bytes, more than the {} allowed",
+            artifact.size, MAX_ARTIFACT_BYTES
+        )));
+    }
 
+    let response = client
+        .send_idempotent(client.get(&artifact.url))
+        .await
+        .map_err(|e| ZamaError::request(UPDATE_SERVICE, e))?;
     if !response.status().is_success() {
-        return Err(format!(
-            "Failed to download install script: HTTP {}",
-            response.status()
+        return Err(ZamaError::from_response(UPDATE_SERVICE, response, None).await);
+    }
+    if response
+        .content_length()
+        .is_some_and(|length| length > artifact.size)
+    {
+        return Err(ZamaError::Integrity(
+            "Update download is larger than advertised".to_string(),
         ));
     }
+    let total = Some(artifact.size);
 
-    let script_content = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read install script: {}", e))?;
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| ZamaError::Io(format!("Failed to create update staging dir: {}", e)))?;
+    }
+    let mut file = fs::File::create(dest)
+        .map_err(|e| ZamaError::Io(format!("Failed to create update file: {}", e)))?;
+    let mut hasher = Sha256::new();
+    let mut downloaded = 0u64;
+    let mut stream = response.bytes_stream();
 
-    // Basic validation - ensure it's actually a shell script
-    if !script_content.starts_with("#!/") {
-        return Err("Install script does not appear to be a valid shell script".to_string());
+    let result: Result<(), ZamaError> = async {
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| ZamaError::request(UPDATE_SERVICE, e))?;
+            downloaded += chunk.len() as u64;
+            if downloaded > artifact.size {
+                return Err(ZamaError::Integrity(
+                    "Update download is larger than advertised".to_string(),
+                ));
+            }
+            hasher.update(&chunk);
+            file.write_all(&chunk)
+                .map_err(|e| ZamaError::Io(format!("Failed to write update file: {}", e)))?;
+            on_progress(downloaded, total);
+        }
+        file.flush()
+            .map_err(|e| ZamaError::Io(format!("Failed to write update file: {}", e)))?;
+
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        if !digest.eq_ignore_ascii_case(artifact.sha256.trim()) {
+            return Err(ZamaError::Integrity(format!(
+                "Update checksum mismatch: expected {}, got {}",
+                artifact.sha256, digest
+            )));
+        }
+        Ok(())
     }
+    .await;
 
-    // Write script to a temporary file
-    let temp_dir = std::env::temp_dir();
-    let script_path = temp_dir.join("zama_install.sh");
+    if result.is_err() {
+        let _ = fs::remove_file(dest);
+    }
+    result
+}
 
-    std::fs::write(&script_path, &script_content)
-        .map_err(|e| format!("Failed to write install script to temp file: {}", e))?;
+// --- Installation ---
 
-    // Make script executable
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(&script_path)
-            .map_err(|e| format!("Failed to get script permissions: {}", e))?
-            .permissions();
-        perms.set_mode(0o755);
-        std::fs::set_permissions(&script_path, perms)
-            .map_err(|e| format!("Failed to set script permissions: {}", e))?;
-    }
-
-    // Execute the script from the local file
-    println!("Executing install script...");
-    let output = Command::new("bash")
-        .arg(&script_path)
-        .output()
-        .map_err(|e| format!("Failed to execute install script: {}", e))?;
-
-    // Clean up temp file
-    let _ = std::fs::remove_file(&script_path);
-
-    if output.status.success() {
-        println!("Installation script executed successfully.");
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("Installation script failed. Stderr: {}", stderr);
-        Err(format!("Installation script failed: {}", stderr))
-    }
-}
-
-// Main update function
-pub async fn check_and_update() {
-    println!("Checking for updates...");
-    match get_current_version() {
-        Ok(current_version) => {
-            println!("Current version: {}", current_version);
-            match get_latest_version().await {
-                Ok(latest_version) => {
-                    println!("Latest version available: {}", latest_version);
-                    if is_update_available(&current_version, &latest_version) {
-                        println!("Update available! Initiating update process...");
-                        if let Err(e) = uninstall_app() {
-                            eprintln!("Uninstall failed: {}", e);
-                            // Continue with install even if uninstall fails, as it might be a fresh install or app not found
-                        }
-                        if let Err(e) = install_app().await {
-                            eprintln!("Install failed: {}", e);
-                            return;
-                        }
-                        println!("Update completed successfully. Please restart the application.");
-                    } else {
-                        println!("You are already running the latest version.");
-                    }
+// What gets replaced: the `.app` bundle on macOS, the AppImage on Linux, otherwise the executable
+pub fn install_target(kind: ArtifactKind) -> Result<PathBuf, String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate the running executable: {}", e))?;
+    match kind {
+        ArtifactKind::AppBundle => exe
+            .ancestors()
+            .find(|p| p.extension().is_some_and(|ext| ext == "app"))
+            .map(Path::to_path_buf)
+            .ok_or_else(|| "The running app is not inside an .app bundle".to_string()),
+        ArtifactKind::Binary => Ok(std::env::var_os("APPIMAGE")
+            .map(PathBuf::from)
+            .unwrap_or(exe)),
+    }
+}
+
+fn sibling_path(target: &Path, suffix: &str) -> Result<PathBuf, String> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| format!("Invalid install path {}", target.display()))?;
+    Ok(target.with_file_name(format!("{}.{}", name.to_string_lossy(), suffix)))
+}
+
+// Unpacks or copies the verified download next to `target`, so the final swap is a same-filesystem
+// rename rather than a copy that could be interrupted halfway
+pub fn stage_artifact(
+    download: &Path,
+    kind: ArtifactKind,
+    target: &Path,
+) -> Result<PathBuf, String> {
+    let staged = sibling_path(target, NEW_SUFFIX)?;
+    remove_path(&staged)?;
+
+    match kind {
+        ArtifactKind::Binary => {
+            fs::copy(download, &staged).map_err(|e| format!("Failed to stage update: {}", e))?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+                    .map_err(|e| format!("Failed to set update permissions: {}", e))?;
+            }
+            Ok(staged)
+        }
+        ArtifactKind::AppBundle => {
+            let unpack_dir = sibling_path(target, &format!("{}-unpack", NEW_SUFFIX))?;
+            remove_path(&unpack_dir)?;
+            let archive = fs::File::open(download)
+                .map_err(|e| format!("Failed to open update archive: {}", e))?;
+            // `unpack` refuses entries that would escape the destination directory
+            tar::Archive::new(flate2::read::GzDecoder::new(archive))
+                .unpack(&unpack_dir)
+                .map_err(|e| format!("Failed to unpack update archive: {}", e))?;
+
+            let bundle = fs::read_dir(&unpack_dir)
+                .map_err(|e| format!("Failed to read unpacked update: {}", e))?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .find(|p| p.is_dir() && p.extension().is_some_and(|ext| ext == "app"))
+                .ok_or_else(|| "Update archive does not contain an .app bundle".to_string())?;
+            fs::rename(&bundle, &staged).map_err(|e| format!("Failed to stage update: {}", e))?;
+            remove_path(&unpack_dir)?;
+            Ok(staged)
+        }
+    }
+}
+
+// Swaps `staged` into `target`, keeping the previous install as a backup until the swap has
+// succeeded and putting it back if anything goes wrong
+pub fn replace_with_rollback(target: &Path, staged: &Path) -> Result<(), String> {
+    let backup = sibling_path(target, BACKUP_SUFFIX)?;
+    remove_path(&backup)?;
+
+    fs::rename(target, &backup)
+        .map_err(|e| format!("Failed to move the current install aside: {}", e))?;
+
+    if let Err(e) = fs::rename(staged, target) {
+        return match fs::rename(&backup, target) {
+            Ok(()) => Err(format!("Failed to install update, rolled back: {}", e)),
+            Err(rollback) => Err(format!(
+                "Failed to install update ({}) and to restore {} from {} ({})",
+                e,
+                target.display(),
+                backup.display(),
+                rollback
+            )),
+        };
+    }
+
+    // Windows keeps the running executable locked, so the backup may have to wait for next launch
+    if let Err(e) = remove_path(&backup) {
+        tracing::warn!(path = %backup.display(), error = %e, "Leaving previous install in place");
+    }
+    Ok(())
+}
+
+// Removes backups a previous update couldn't delete while the old version was still running
+pub fn cleanup_previous_install() {
+    for kind in [ArtifactKind::Binary, ArtifactKind::AppBundle] {
+        if let Ok(backup) = install_target(kind).and_then(|t| sibling_path(&t, BACKUP_SUFFIX)) {
+            if backup.exists() {
+                if let Err(e) = remove_path(&backup) {
+                    tracing::warn!(path = %backup.display(), error = %e, "Failed to remove previous install");
                 }
-                Err(e) => eprintln!("Failed to get latest version: {}", e),
             }
         }
-        Err(e) => eprintln!("Failed to get current version: {}", e),
     }
 }
+
+fn remove_path(path: &Path) -> Result<(), String> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        return Ok(());
+    };
+    result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+// --- Commands ---
+
+// Fetches the manifest for the configured channel and records any applicable update as pending,
+// without downloading or installing anything
+async fn find_update<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    settings: &UpdaterSettings,
+) -> Result<UpdateCheck, ZamaError> {
+    let current_version = get_current_version();
+    let manifest = fetch_manifest(&http::client(app_handle), &manifest_url(settings)).await?;
+
+    let mut check = UpdateCheck {
+        current_version: current_version.clone(),
+        channel: settings.channel,
+        update: None,
+        skipped: false,
+        remind_after: settings.remind_after.filter(|until| *until > unix_now()),
+    };
+    if !is_update_for_channel(settings.channel, &current_version, &manifest.version) {
+        return Ok(check);
+    }
+    let Some(artifact) = manifest.platforms.get(&platform_key()).cloned() else {
+        tracing::info!(
+            version = %manifest.version,
+            platform = %platform_key(),
+            "Release has no build for this platform, skipping"
+        );
+        return Ok(check);
+    };
+
+    let state = app_handle.state::<UpdaterState>();
+    *state.pending.lock().unwrap() = Some(PendingUpdate {
+        version: manifest.version.clone(),
+        artifact,
+    });
+
+    check.skipped = settings.skipped_version.as_deref() == Some(manifest.version.as_str());
+    check.update = Some(UpdateInfo {
+        current_version,
+        version: manifest.version,
+        notes: manifest.notes,
+        pub_date: manifest.pub_date,
+    });
+    Ok(check)
+}
+
+// Looks for an update on launch and, if there is one, asks the frontend for consent through an
+// `update-available` event. Nothing is downloaded until `install_update` is invoked, and nothing
+// is checked at all when auto-update is off.
+pub async fn check_and_update<R: Runtime>(app_handle: AppHandle<R>) {
+    cleanup_previous_install();
+
+    let settings = load_settings(&app_handle);
+    if !settings.auto_update {
+        tracing::info!("Automatic update checks are turned off");
+        return;
+    }
+    tracing::info!(channel = settings.channel.as_str(), "Checking for updates");
+
+    let check = match find_update(&app_handle, &settings).await {
+        Ok(check) => check,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to check for updates");
+            return;
+        }
+    };
+    let Some(info) = check.update else {
+        tracing::info!("Already running the latest version");
+        return;
+    };
+    if check.skipped {
+        tracing::info!(version = %info.version, "Skipping version as requested");
+        return;
+    }
+    if check.remind_after.is_some() {
+        tracing::info!(version = %info.version, "Update deferred, not reminding yet");
+        return;
+    }
+
+    if let Err(e) = app_handle.emit("update-available", info) {
+        tracing::error!(error = %e, "Failed to emit update-available event");
+    }
+}
+
+// Manual check from the settings screen; reports the update and its changelog but leaves the
+// decision to the user
+#[tauri::command]
+pub async fn check_for_updates<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<UpdateCheck, ZamaError> {
+    let settings = load_settings(&app_handle);
+    find_update(&app_handle, &settings).await
+}
+
+#[tauri::command]
+pub fn get_update_settings<R: Runtime>(app_handle: AppHandle<R>) -> UpdaterSettings {
+    load_settings(&app_handle)
+}
+
+#[tauri::command]
+pub fn set_update_settings<R: Runtime>(
+    app_handle: AppHandle<R>,
+    channel: Option<UpdateChannel>,
+    auto_update: Option<bool>,
+    manifest_url: Option<String>,
+) -> Result<UpdaterSettings, ZamaError> {
+    let mut settings = load_settings(&app_handle);
+    if let Some(channel) = channel {
+        settings.channel = channel;
+    }
+    if let Some(auto_update) = auto_update {
+        settings.auto_update = auto_update;
+    }
+    if let Some(url) = manifest_url {
+        let url = url.trim();
+        if url.is_empty() {
+            settings.manifest_url = None;
+        } else {
+            validate_manifest_url(url).map_err(ZamaError::InvalidInput)?;
+            settings.manifest_url = Some(url.to_string());
+        }
+    }
+    save_settings(&app_handle, &settings)?;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn skip_update<R: Runtime>(app_handle: AppHandle<R>, version: String) -> Result<(), ZamaError> {
+    Version::parse(&version)
+        .map_err(|e| ZamaError::InvalidInput(format!("Invalid version '{}': {}", version, e)))?;
+    let mut settings = load_settings(&app_handle);
+    settings.skipped_version = Some(version);
+    settings.remind_after = None;
+    save_settings(&app_handle, &settings)
+}
+
+#[tauri::command]
+pub fn remind_update_later<R: Runtime>(
+    app_handle: AppHandle<R>,
+    hours: Option<u64>,
+) -> Result<u64, ZamaError> {
+    let hours = hours
+        .unwrap_or(DEFAULT_REMIND_AFTER_HOURS)
+        .clamp(1, 24 * 30);
+    let mut settings = load_settings(&app_handle);
+    let until = unix_now() + hours * 60 * 60;
+    settings.remind_after = Some(until);
+    save_settings(&app_handle, &settings)?;
+    Ok(until)
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// Installs the update announced through `update-available`; `version` must match what the user
+// agreed to so a manifest change in between can't swap in a different release
+#[tauri::command]
+pub async fn install_update<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: tauri::State<'_, UpdaterState>,
+    version: String,
+) -> Result<(), ZamaError> {
+    let pending = state
+        .pending
+        .lock()
+        .unwrap()
+        .clone()
+        .filter(|p| p.version == version)
+        .ok_or_else(|| {
+            ZamaError::InvalidInput(format!("No pending update for version {}", version))
+        })?;
+    let public_key = UPDATER_PUBLIC_KEY.ok_or_else(|| {
+        ZamaError::Integrity(
+            "This build has no updater public key, updates are disabled".to_string(),
+        )
+    })?;
+
+    let staging_dir = app_handle.path().app_data_dir()?.join(STAGING_DIR);
+    let download = staging_dir.join(format!("zama-{}.download", pending.version));
+    let platform = platform_key();
+
+    let progress_handle = app_handle.clone();
+    let progress_version = pending.version.clone();
+    download_and_verify(
+        &http::client(&app_handle),
+        &pending.artifact,
+        &pending.version,
+        &platform,
+        public_key,
+        &download,
+        move |downloaded, total| {
+            let progress = UpdateProgress {
+                version: progress_version.clone(),
+                downloaded,
+                total,
+            };
+            let _ = progress_handle.emit("update-progress", progress);
+        },
+    )
+    .await?;
+
+    let kind = pending.artifact.kind;
+    let install_result = tokio::task::spawn_blocking(move || {
+        let target = install_target(kind)?;
+        let staged = stage_artifact(&download, kind, &target)?;
+        let result = replace_with_rollback(&target, &staged);
+        if result.is_err() {
+            let _ = remove_path(&staged);
+        }
+        let _ = fs::remove_file(&download);
+        result
+    })
+    .await?;
+    install_result.map_err(ZamaError::Io)?;
+
+    *state.pending.lock().unwrap() = None;
+    tracing::info!(%version, "Update installed, restart to apply");
+    if let Err(e) = app_handle.emit("update-installed", &version) {
+        tracing::error!(error = %e, "Failed to emit update-installed event");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn restart_app<R: Runtime>(app_handle: AppHandle<R>) {
+    app_handle.restart();
+}
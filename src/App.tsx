@@ -14,18 +14,41 @@ import ChatPage from "@/pages/ChatPage";
 import ModelsPage from "@/pages/ModelsPage";
 import HistoryPage from "@/pages/HistoryPage";
 import { ChatHistoryProvider } from "@/contexts/ChatHistoryContext";
+import UpdatePrompt from "@/components/UpdatePrompt";
+import PromptPicker from "@/components/PromptPicker";
 
 import { OllamaClientClass } from "$/lib/client";
-import type { OllamaModel } from "$/lib/schemas/client.schema";
+import { PresetsClientClass } from "$/lib/presets-client";
+import type {
+  ContextSettings,
+  ContextStrategy,
+  OllamaModel,
+} from "$/lib/schemas/client.schema";
 
 const OllamaClient = new OllamaClientClass();
+const PresetsClient = new PresetsClientClass();
 import LibraryPage from "@/pages/LibraryPage";
 import CloudLLMsPage from "@/pages/CloudLLMsPage";
+import ComparePage from "@/pages/ComparePage";
+import PromptsPage from "@/pages/PromptsPage";
+import BatchPage from "@/pages/BatchPage";
+import EvalsPage from "@/pages/EvalsPage";
+import LogsPage from "@/pages/LogsPage";
 
 import { VscLibrary } from "react-icons/vsc";
 import { SiRobotframework } from "react-icons/si";
 import { MdChatBubbleOutline } from "react-icons/md";
-import { History, Sun, Moon, Cloud } from "lucide-react";
+import {
+  History,
+  Sun,
+  Moon,
+  Cloud,
+  Columns3,
+  NotebookPen,
+  ListChecks,
+  FlaskConical,
+  ScrollText,
+} from "lucide-react";
 
 interface PageConfig {
   id: string;
@@ -43,6 +66,12 @@ const pageConfigs: PageConfig[] = [
     icon: <MdChatBubbleOutline />,
     component: (props) => <ChatPage {...props} />,
   },
+  {
+    id: "compare",
+    name: "Compare",
+    icon: <Columns3 />,
+    component: (props) => <ComparePage {...props} />,
+  },
   {
     id: "models",
     name: "Your Models",
@@ -55,6 +84,24 @@ const pageConfigs: PageConfig[] = [
     icon: <VscLibrary />,
     component: () => <LibraryPage />,
   },
+  {
+    id: "prompts",
+    name: "Prompts",
+    icon: <NotebookPen />,
+    component: () => <PromptsPage />,
+  },
+  {
+    id: "batch",
+    name: "Batch",
+    icon: <ListChecks />,
+    component: (props) => <BatchPage {...props} />,
+  },
+  {
+    id: "evals",
+    name: "Evaluations",
+    icon: <FlaskConical />,
+    component: () => <EvalsPage />,
+  },
   {
     id: "history",
     name: "History",
@@ -67,6 +114,12 @@ const pageConfigs: PageConfig[] = [
     icon: <Cloud />,
     component: () => <CloudLLMsPage />,
   },
+  {
+    id: "logs",
+    name: "Logs",
+    icon: <ScrollText />,
+    component: () => <LogsPage />,
+  },
 ];
 
 export default function App() {
@@ -98,6 +151,9 @@ export default function App() {
   const [contextLength, setContextLength] = useState<number | null>(null);
   const [temperature, setTemperature] = useState<number>(0.8); // Default temperature
   const [systemPrompt, setSystemPrompt] = useState<string>("");
+  const [presetsLoaded, setPresetsLoaded] = useState(false);
+  const [contextSettings, setContextSettings] =
+    useState<ContextSettings | null>(null);
   const [availableModels, setAvailableModels] = useState<OllamaModel[]>([]);
   const [selectedModel, setSelectedModel] = useState<string | null>(null);
 
@@ -120,6 +176,10 @@ export default function App() {
         try {
           const geminiConfig = JSON.parse(geminiConfigString);
           if (geminiConfig.apiKey && geminiConfig.defaultModel) {
+            // Keys saved before the backend stored its own copy
+            OllamaClient.setProviderKey("gemini", geminiConfig.apiKey).catch(
+              (error) => console.error("Failed to sync Gemini key:", error),
+            );
             // Add hardcoded Gemini models
             const geminiModels = [
               { name: "gemini-pro", modified_at: "", size: 0, digest: "" },
@@ -190,6 +250,66 @@ export default function App() {
     loadInitialData();
   }, [page]);
 
+  useEffect(() => {
+    OllamaClient.getContextSettings()
+      .then(setContextSettings)
+      .catch((error) =>
+        console.error("Failed to load context settings:", error),
+      );
+  }, []);
+
+  // The sidebar edits the global preset; chats resolve it in the backend below any model preset
+  useEffect(() => {
+    PresetsClient.get()
+      .then(({ global }) => {
+        const options = global.options ?? {};
+        if (typeof options.temperature === "number") {
+          setTemperature(options.temperature);
+        }
+        if (typeof options.num_ctx === "number") {
+          setContextLength(options.num_ctx);
+        }
+        setSystemPrompt(global.system ?? "");
+      })
+      .catch((error) => console.error("Failed to load presets:", error))
+      .finally(() => setPresetsLoaded(true));
+  }, []);
+
+  useEffect(() => {
+    if (!presetsLoaded) return;
+    const timer = setTimeout(() => {
+      PresetsClient.setGlobal(currentParameters()).catch((error) =>
+        console.error("Failed to save global preset:", error),
+      );
+    }, 500);
+    return () => clearTimeout(timer);
+  }, [presetsLoaded, temperature, contextLength, systemPrompt]);
+
+  const currentParameters = () => ({
+    system: systemPrompt || undefined,
+    options: { temperature, num_ctx: contextLength || undefined },
+  });
+
+  const saveModelPreset = async () => {
+    if (!selectedModel || selectedModel.startsWith("gemini")) return;
+    try {
+      await PresetsClient.setModel(selectedModel, currentParameters());
+    } catch (error) {
+      console.error("Failed to save model preset:", error);
+    }
+  };
+
+  const updateContextStrategy = async (strategy: ContextStrategy) => {
+    if (!contextSettings) return;
+    try {
+      setContextSettings(
+        await OllamaClient.setContextSettings({ ...contextSettings, strategy }),
+      );
+    } catch (error) {
+      console.error("Failed to save context settings:", error);
+    }
+  };
+
   const currentPageComponent = pageConfigs.find(
     (p) => p.id === page,
   )?.component;
@@ -210,6 +330,7 @@ export default function App() {
 
   return (
     <ChatHistoryProvider>
+      <UpdatePrompt />
       <div className={`flex h-screen bg-background`}>
         {/* Left Sidebar */}
         <aside className="w-56 bg-card p-4 border-r border-border">
@@ -283,8 +404,8 @@ export default function App() {
               })}
             </section>
 
-            {/* Right Sidebar - only show for chat page */}
-            {page === "chat" && (
+            {/* Right Sidebar - chat and compare share the model settings */}
+            {(page === "chat" || page === "compare") && (
               <aside className="w-72 bg-card p-4 border-l border-border">
                 <h2 className="text-lg font-semibold mb-2">
                   Model Configuration
@@ -302,6 +423,9 @@ export default function App() {
                       }
                       placeholder="Enter system prompt here..."
                     />
+                    <div className="mt-2">
+                      <PromptPicker kind="system" onApply={setSystemPrompt} />
+                    </div>
                   </div>
                   <div>
                     <label htmlFor="context-length" className="block mb-1">
@@ -316,6 +440,34 @@ export default function App() {
                       onValueChange={(value) => setContextLength(value[0])}
                     />
                   </div>
+                  {contextSettings && (
+                    <div>
+                      <label htmlFor="context-strategy" className="block mb-1">
+                        When the conversation is too long:
+                      </label>
+                      <Select
+                        value={contextSettings.strategy}
+                        onValueChange={(value: string) =>
+                          updateContextStrategy(value as ContextStrategy)
+                        }
+                      >
+                        <SelectTrigger id="context-strategy" className="w-full">
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                          <SelectItem value="pin_system">
+                            Drop oldest, keep system prompt
+                          </SelectItem>
+                          <SelectItem value="sliding_window">
+                            Drop oldest messages
+                          </SelectItem>
+                          <SelectItem value="summarize">
+                            Summarize older messages
+                          </SelectItem>
+                        </SelectContent>
+                      </Select>
+                    </div>
+                  )}
                   <div>
                     <label htmlFor="temperature" className="block mb-1">
                       Temperature: {temperature}
@@ -329,6 +481,16 @@ export default function App() {
                       onValueChange={(value) => setTemperature(value[0])}
                     />
                   </div>
+                  {selectedModel && !selectedModel.startsWith("gemini") && (
+                    <Button
+                      variant="outline"
+                      size="sm"
+                      className="w-full"
+                      onClick={saveModelPreset}
+                    >
+                      Save as default for {selectedModel}
+                    </Button>
+                  )}
                 </div>
               </aside>
             )}
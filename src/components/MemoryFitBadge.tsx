@@ -0,0 +1,51 @@
+import { useEffect, useState } from "preact/hooks";
+import { OllamaClientClass } from "$/lib/client";
+import type {
+  MemoryEstimate,
+  MemoryEstimateRequest,
+} from "$/lib/schemas/client.schema";
+
+const OllamaClient = new OllamaClientClass();
+
+const LABELS: Record<MemoryEstimate["fit"], [string, string]> = {
+  fit: ["Fits in memory", "bg-green-900 text-green-300"],
+  partial_offload: ["Partial GPU offload", "bg-yellow-900 text-yellow-300"],
+  wont_fit: ["Won't fit", "bg-red-900 text-red-300"],
+};
+
+interface MemoryFitBadgeProps {
+  request: MemoryEstimateRequest;
+}
+
+// Whether a model fits this machine; renders nothing until the estimate is in, or if it fails
+export default function MemoryFitBadge({ request }: MemoryFitBadgeProps) {
+  const [estimate, setEstimate] = useState<MemoryEstimate | null>(null);
+  const key = JSON.stringify(request);
+
+  useEffect(() => {
+    let cancelled = false;
+    OllamaClient.estimateMemory(request)
+      .then((result) => !cancelled && setEstimate(result))
+      .catch(() => !cancelled && setEstimate(null));
+    return () => {
+      cancelled = true;
+    };
+  }, [key]);
+
+  if (!estimate) {
+    return null;
+  }
+  const [label, colors] = LABELS[estimate.fit];
+  const gb = (estimate.total_bytes / (1000 * 1000 * 1000)).toFixed(1);
+  return (
+    <span
+      className={`${colors} text-xs px-2 py-0.5 rounded-full`}
+      title={[
+        `~${gb} GB at ${estimate.num_ctx} context`,
+        ...estimate.warnings,
+      ].join("\n")}
+    >
+      {label}
+    </span>
+  );
+}
@@ -0,0 +1,119 @@
+import { useEffect, useState } from "preact/hooks";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { PromptsClientClass } from "$/lib/prompts-client";
+import { errorMessage } from "$/lib/errors";
+import type { Prompt, PromptKind } from "$/lib/schemas/prompts.schema";
+
+const Prompts = new PromptsClientClass();
+
+interface PromptPickerProps {
+  kind: PromptKind;
+  onApply: (content: string) => void;
+  onError?: (message: string) => void;
+}
+
+// Picks a library prompt, asks for its `{{variables}}` and hands back the rendered text
+export default function PromptPicker({
+  kind,
+  onApply,
+  onError,
+}: PromptPickerProps) {
+  const [prompts, setPrompts] = useState<Prompt[]>([]);
+  const [selected, setSelected] = useState<Prompt | null>(null);
+  const [values, setValues] = useState<Record<string, string>>({});
+
+  const load = () => {
+    Prompts.list({ kind })
+      .then(setPrompts)
+      .catch((error) => console.error("Failed to load prompts:", error));
+  };
+
+  useEffect(load, [kind]);
+
+  const apply = async (prompt: Prompt, vars: Record<string, string>) => {
+    try {
+      const rendered = await Prompts.render(prompt.id, vars);
+      onApply(rendered.content);
+      setSelected(null);
+      setValues({});
+    } catch (error) {
+      onError?.(errorMessage(error));
+    }
+  };
+
+  const handleSelect = (id: string) => {
+    const prompt = prompts.find((p) => p.id === id);
+    if (!prompt) return;
+    if (prompt.variables.length === 0) {
+      apply(prompt, {});
+    } else {
+      setSelected(prompt);
+      setValues({});
+    }
+  };
+
+  if (prompts.length === 0) return null;
+
+  return (
+    <div className="space-y-2">
+      <Select
+        value={selected?.id ?? ""}
+        onValueChange={handleSelect}
+        onOpenChange={(open: boolean) => open && load()}
+      >
+        <SelectTrigger className="w-full" size="sm">
+          <SelectValue
+            placeholder={
+              kind === "system" ? "Use a library prompt" : "Use a template"
+            }
+          />
+        </SelectTrigger>
+        <SelectContent>
+          {prompts.map((prompt) => (
+            <SelectItem key={prompt.id} value={prompt.id}>
+              {prompt.name}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+      {selected && (
+        <div className="space-y-2 rounded-md border border-border p-2">
+          {selected.variables.map((name) => (
+            <Input
+              key={name}
+              value={values[name] ?? ""}
+              placeholder={name}
+              className="h-8"
+              onInput={(e) =>
+                setValues({
+                  ...values,
+                  [name]: (e.target as HTMLInputElement).value,
+                })
+              }
+            />
+          ))}
+          <div className="flex gap-2">
+            <Button size="sm" onClick={() => apply(selected, values)}>
+              Apply
+            </Button>
+            <Button
+              size="sm"
+              variant="ghost"
+              onClick={() => setSelected(null)}
+            >
+              Cancel
+            </Button>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
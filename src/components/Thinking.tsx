@@ -3,9 +3,10 @@ import { ChevronDown, ChevronRight } from "lucide-react";
 
 interface ThinkingProps {
   content: string;
+  active?: boolean; // Still streaming
 }
 
-export default function Thinking({ content }: ThinkingProps) {
+export default function Thinking({ content, active = false }: ThinkingProps) {
   const [isOpen, setIsOpen] = useState(false);
 
   return (
@@ -26,7 +27,11 @@ export default function Thinking({ content }: ThinkingProps) {
         ) : (
           <ChevronRight className="w-4 h-4 mr-2" />
         )}
-        <span className="font-semibold animate-pulse">Thinking...</span>
+        {active ? (
+          <span className="font-semibold animate-pulse">Thinking...</span>
+        ) : (
+          <span className="font-semibold">Thought process</span>
+        )}
       </button>
       {isOpen && (
         <div className="mt-2 p-2 border-t">
@@ -0,0 +1,105 @@
+import { useEffect, useState } from "preact/hooks";
+import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "$/lib/errors";
+import { listen } from "@tauri-apps/api/event";
+import { Button } from "@/components/ui/button";
+
+interface UpdateInfo {
+  current_version: string;
+  version: string;
+  notes: string | null;
+  pub_date: string | null;
+}
+
+interface UpdateProgress {
+  version: string;
+  downloaded: number;
+  total: number | null;
+}
+
+// Nothing is downloaded or replaced until the user clicks "Install"
+export default function UpdatePrompt() {
+  const [update, setUpdate] = useState<UpdateInfo | null>(null);
+  const [progress, setProgress] = useState<number | null>(null);
+  const [installed, setInstalled] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisteners = [
+      listen<UpdateInfo>("update-available", (event) =>
+        setUpdate(event.payload),
+      ),
+      listen<UpdateProgress>("update-progress", (event) => {
+        const { downloaded, total } = event.payload;
+        setProgress(total ? Math.round((downloaded / total) * 100) : null);
+      }),
+      listen<string>("update-installed", () => setInstalled(true)),
+    ];
+    return () => {
+      for (const unlisten of unlisteners) {
+        unlisten.then((fn) => fn());
+      }
+    };
+  }, []);
+
+  if (!update) {
+    return null;
+  }
+
+  const install = async () => {
+    setError(null);
+    setProgress(0);
+    try {
+      await invoke("install_update", { version: update.version });
+    } catch (err) {
+      setProgress(null);
+      setError(errorMessage(err));
+    }
+  };
+
+  const dismiss = (command: string, args: Record<string, unknown>) => {
+    setUpdate(null);
+    invoke(command, args).catch((err) =>
+      console.error(`Failed to ${command}:`, err),
+    );
+  };
+
+  return (
+    <div className="fixed bottom-4 right-4 z-50 w-80 rounded-lg border bg-card p-4 shadow-lg">
+      <p className="font-semibold">
+        Zama {update.version} is available (you have {update.current_version})
+      </p>
+      {update.notes && (
+        <p className="mt-1 max-h-32 overflow-y-auto whitespace-pre-wrap text-sm text-muted-foreground">
+          {update.notes}
+        </p>
+      )}
+      {error && <p className="mt-2 text-sm text-red-500">{error}</p>}
+      <div className="mt-3 flex justify-end gap-2">
+        {installed ? (
+          <Button onClick={() => invoke("restart_app")}>Restart now</Button>
+        ) : progress !== null ? (
+          <p className="text-sm">Installing... {progress}%</p>
+        ) : (
+          <>
+            <Button
+              variant="ghost"
+              onClick={() =>
+                dismiss("skip_update", { version: update.version })
+              }
+            >
+              Skip
+            </Button>
+            <Button
+              variant="ghost"
+              onClick={() => dismiss("remind_update_later", {})}
+            >
+              Later
+            </Button>
+            <Button onClick={install}>Install</Button>
+          </>
+        )}
+      </div>
+    </div>
+  );
+}
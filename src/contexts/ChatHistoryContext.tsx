@@ -1,34 +1,55 @@
 import { createContext } from "preact";
 import { useContext, useState, useEffect } from "preact/hooks";
+import { listen } from "@tauri-apps/api/event";
+import { HistoryClientClass } from "$/lib/history-client";
+import type { GenerationStats } from "$/lib/schemas/client.schema";
+import type {
+  Branch,
+  Conversation,
+  LegacyConversation,
+  Message,
+  MessageRole,
+} from "$/lib/schemas/history.schema";
 
-export interface Message {
-  role: "user" | "assistant" | "system";
-  content: string;
-  timestamp: number;
-}
-
-export interface Conversation {
-  id: string;
-  title: string;
-  messages: Message[];
-  model: string;
-  createdAt: number;
-  updatedAt: number;
-}
+export type { Branch, Conversation, Message };
 
 interface ChatHistoryContextType {
   conversations: Conversation[];
   currentConversationId: string | null;
   currentConversation: Conversation | null;
-  createNewConversation: (model: string) => string;
+  createNewConversation: (model: string) => Promise<Conversation>;
   switchToConversation: (conversationId: string) => void;
-  addMessageToConversation: (conversationId: string, message: Message) => void;
+  addMessageToConversation: (
+    conversationId: string,
+    role: MessageRole,
+    content: string,
+    model?: string,
+  ) => Promise<Conversation>;
   updateMessageInConversation: (
     conversationId: string,
-    messageIndex: number,
+    messageId: string,
+    content: string,
+    stats?: GenerationStats,
+    thinking?: string,
+  ) => Promise<void>;
+  editMessage: (
+    conversationId: string,
+    messageId: string,
     content: string,
-  ) => void;
+  ) => Promise<Conversation>;
+  regenerateMessage: (
+    conversationId: string,
+    messageId: string,
+    model?: string,
+  ) => Promise<Conversation>;
+  listBranches: (conversationId: string, messageId: string) => Promise<Branch[]>;
+  switchBranch: (conversationId: string, messageId: string) => Promise<void>;
+  forkConversation: (
+    conversationId: string,
+    messageId?: string,
+  ) => Promise<Conversation>;
   updateConversationTitle: (conversationId: string, title: string) => void;
+  generateTitle: (conversationId: string) => Promise<void>;
   deleteConversation: (conversationId: string) => void;
   clearAllConversations: () => void;
 }
@@ -37,16 +58,25 @@ const ChatHistoryContext = createContext<ChatHistoryContextType | undefined>(
   undefined,
 );
 
-const STORAGE_KEY = "zama_chat_history";
+// Where earlier versions kept the history; migrated to the backend on first launch
+const LEGACY_STORAGE_KEY = "zama_chat_history";
+
+const History = new HistoryClientClass();
 
-function generateConversationId(): string {
-  return `conv_${Date.now()}_${Math.random().toString(36).substr(2, 9)}`;
+async function migrateLegacyHistory() {
+  const saved = localStorage.getItem(LEGACY_STORAGE_KEY);
+  if (!saved) return;
+  const parsed = JSON.parse(saved) as LegacyConversation[];
+  await History.importLegacy(parsed);
+  localStorage.removeItem(LEGACY_STORAGE_KEY);
 }
 
-function generateTitleFromMessage(content: string): string {
-  // Create a title from the first user message, truncated to ~40 chars
-  const cleaned = content.trim().replace(/\s+/g, " ");
-  return cleaned.length > 40 ? `${cleaned.substring(0, 40)}...` : cleaned;
+function mostRecent(conversations: Conversation[]): Conversation | null {
+  return conversations.length > 0
+    ? conversations.reduce((prev, curr) =>
+        curr.updatedAt > prev.updatedAt ? curr : prev,
+      )
+    : null;
 }
 
 export function ChatHistoryProvider({
@@ -59,140 +89,157 @@ export function ChatHistoryProvider({
     string | null
   >(null);
 
-  // Load conversations from localStorage on mount
+  // Load conversations from the backend on mount
   useEffect(() => {
-    try {
-      const saved = localStorage.getItem(STORAGE_KEY);
-      if (saved) {
-        const parsed = JSON.parse(saved) as Conversation[];
-        setConversations(parsed);
-
+    const load = async () => {
+      try {
+        await migrateLegacyHistory();
+      } catch (error) {
+        console.error("Failed to migrate chat history:", error);
+      }
+      try {
+        const loaded = await History.list();
+        setConversations(loaded);
         // Set the most recent conversation as current
-        if (parsed.length > 0) {
-          const mostRecent = parsed.reduce((prev, curr) =>
-            curr.updatedAt > prev.updatedAt ? curr : prev,
-          );
-          setCurrentConversationId(mostRecent.id);
-        }
+        setCurrentConversationId(mostRecent(loaded)?.id ?? null);
+      } catch (error) {
+        console.error("Failed to load chat history:", error);
       }
-    } catch (error) {
-      console.error("Failed to load chat history:", error);
-    }
+    };
+    load();
   }, []);
 
-  // Save conversations to localStorage whenever they change
+  // Titles and summaries are written by a background job after the first exchange
   useEffect(() => {
-    try {
-      localStorage.setItem(STORAGE_KEY, JSON.stringify(conversations));
-    } catch (error) {
-      console.error("Failed to save chat history:", error);
-    }
-  }, [conversations]);
+    const unlisten = listen<Conversation>("conversation-titled", (event) => {
+      const titled = event.payload;
+      setConversations((prev) =>
+        prev.map((c) => (c.id === titled.id ? titled : c)),
+      );
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Replaces the cached copy with what the backend returned, adding new conversations first
+  const store = (conversation: Conversation) => {
+    setConversations((prev) =>
+      prev.some((c) => c.id === conversation.id)
+        ? prev.map((c) => (c.id === conversation.id ? conversation : c))
+        : [conversation, ...prev],
+    );
+    return conversation;
+  };
 
   const currentConversation =
     conversations.find((c) => c.id === currentConversationId) || null;
 
-  const createNewConversation = (model: string) => {
-    const newConversation: Conversation = {
-      id: generateConversationId(),
-      title: "New Conversation",
-      messages: [],
-      model,
-      createdAt: Date.now(),
-      updatedAt: Date.now(),
-    };
-
-    setConversations((prev) => [newConversation, ...prev]);
-    setCurrentConversationId(newConversation.id);
-    return newConversation.id;
+  const createNewConversation = async (model: string) => {
+    const conversation = store(await History.create(model));
+    setCurrentConversationId(conversation.id);
+    return conversation;
   };
 
   const switchToConversation = (conversationId: string) => {
     setCurrentConversationId(conversationId);
   };
 
-  const addMessageToConversation = (
+  const addMessageToConversation = async (
+    conversationId: string,
+    role: MessageRole,
+    content: string,
+    model?: string,
+  ) =>
+    store(await History.appendMessage(conversationId, role, content, model));
+
+  const updateMessageInConversation = async (
     conversationId: string,
-    message: Message,
+    messageId: string,
+    content: string,
+    stats?: GenerationStats,
+    thinking?: string,
   ) => {
-    setConversations((prev) =>
-      prev.map((conv) => {
-        if (conv.id === conversationId) {
-          const updatedConv = {
-            ...conv,
-            messages: [...conv.messages, message],
-            updatedAt: Date.now(),
-          };
-
-          // Auto-generate title from first user message
-          if (conv.title === "New Conversation" && message.role === "user") {
-            updatedConv.title = generateTitleFromMessage(message.content);
-          }
-
-          return updatedConv;
-        }
-        return conv;
-      }),
+    store(
+      await History.updateMessage(
+        conversationId,
+        messageId,
+        content,
+        stats,
+        thinking,
+      ),
     );
   };
 
-  const updateMessageInConversation = (
+  const editMessage = async (
     conversationId: string,
-    messageIndex: number,
+    messageId: string,
     content: string,
+  ) => store(await History.editMessage(conversationId, messageId, content));
+
+  const regenerateMessage = async (
+    conversationId: string,
+    messageId: string,
+    model?: string,
+  ) =>
+    store(await History.regenerateMessage(conversationId, messageId, model));
+
+  const listBranches = (conversationId: string, messageId: string) =>
+    History.listBranches(conversationId, messageId);
+
+  const switchBranch = async (conversationId: string, messageId: string) => {
+    store(await History.switchBranch(conversationId, messageId));
+  };
+
+  const forkConversation = async (
+    conversationId: string,
+    messageId?: string,
   ) => {
-    setConversations((prev) =>
-      prev.map((conv) => {
-        if (conv.id === conversationId) {
-          const updatedMessages = [...conv.messages];
-          if (updatedMessages[messageIndex]) {
-            updatedMessages[messageIndex] = {
-              ...updatedMessages[messageIndex],
-              content,
-            };
-          }
-          return {
-            ...conv,
-            messages: updatedMessages,
-            updatedAt: Date.now(),
-          };
-        }
-        return conv;
-      }),
-    );
+    const fork = store(await History.fork(conversationId, messageId));
+    setCurrentConversationId(fork.id);
+    return fork;
   };
 
-  const updateConversationTitle = (conversationId: string, title: string) => {
-    setConversations((prev) =>
-      prev.map((conv) =>
-        conv.id === conversationId
-          ? { ...conv, title, updatedAt: Date.now() }
-          : conv,
-      ),
-    );
+  const updateConversationTitle = async (
+    conversationId: string,
+    title: string,
+  ) => {
+    try {
+      store(await History.rename(conversationId, title));
+    } catch (error) {
+      console.error("Failed to rename conversation:", error);
+    }
   };
 
-  const deleteConversation = (conversationId: string) => {
+  const generateTitle = (conversationId: string) =>
+    History.generateTitle(conversationId);
+
+  const deleteConversation = async (conversationId: string) => {
+    try {
+      await History.delete(conversationId);
+    } catch (error) {
+      console.error("Failed to delete conversation:", error);
+      return;
+    }
     setConversations((prev) => {
       const filtered = prev.filter((c) => c.id !== conversationId);
 
       // If we deleted the current conversation, switch to the most recent remaining one
       if (currentConversationId === conversationId) {
-        if (filtered.length > 0) {
-          const mostRecent = filtered.reduce((prev, curr) =>
-            curr.updatedAt > prev.updatedAt ? curr : prev,
-          );
-          setCurrentConversationId(mostRecent.id);
-        } else {
-          setCurrentConversationId(null);
-        }
+        setCurrentConversationId(mostRecent(filtered)?.id ?? null);
       }
 
       return filtered;
     });
   };
 
-  const clearAllConversations = () => {
+  const clearAllConversations = async () => {
+    try {
+      await History.clear();
+    } catch (error) {
+      console.error("Failed to clear conversations:", error);
+      return;
+    }
     setConversations([]);
     setCurrentConversationId(null);
   };
@@ -207,7 +254,13 @@ export function ChatHistoryProvider({
         switchToConversation,
         addMessageToConversation,
         updateMessageInConversation,
+        editMessage,
+        regenerateMessage,
+        listBranches,
+        switchBranch,
+        forkConversation,
         updateConversationTitle,
+        generateTitle,
         deleteConversation,
         clearAllConversations,
       }}
@@ -0,0 +1,174 @@
+import { useEffect, useState } from "preact/hooks";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Textarea } from "@/components/ui/textarea";
+import { useToast } from "@/components/ui/toast";
+import { OllamaClientClass } from "$/lib/client";
+import { runBatch } from "$/lib/batch-client";
+import { errorMessage } from "$/lib/errors";
+import type { BatchProgress, BatchSummary } from "$/lib/schemas/batch.schema";
+import { ListChecks, Play } from "lucide-react";
+
+const OllamaClient = new OllamaClientClass();
+
+interface BatchPageProps {
+  systemPrompt: string;
+}
+
+export default function BatchPage({ systemPrompt }: BatchPageProps) {
+  const [models, setModels] = useState<string[]>([]);
+  const [model, setModel] = useState("");
+  const [inputPath, setInputPath] = useState("");
+  const [outputPath, setOutputPath] = useState("");
+  const [template, setTemplate] = useState("");
+  const [concurrency, setConcurrency] = useState(2);
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<BatchProgress | null>(null);
+  const [summary, setSummary] = useState<BatchSummary | null>(null);
+  const { showToast, ToastComponent } = useToast();
+
+  useEffect(() => {
+    OllamaClient.listModels()
+      .then((response) => {
+        const names = response.models.map((m) => m.name);
+        setModels(names);
+        setModel((current) => current || names[0] || "");
+      })
+      .catch((error) => console.error("Failed to load Ollama models:", error));
+  }, []);
+
+  const handleRun = async () => {
+    if (!model || !inputPath.trim() || !template.trim()) return;
+    setRunning(true);
+    setProgress(null);
+    setSummary(null);
+    try {
+      setSummary(
+        await runBatch(
+          {
+            model,
+            input_path: inputPath.trim(),
+            output_path: outputPath.trim() || undefined,
+            template,
+            system: systemPrompt || undefined,
+            concurrency,
+          },
+          setProgress,
+        ),
+      );
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const percent =
+    progress && progress.total > 0
+      ? Math.round((progress.completed / progress.total) * 100)
+      : 0;
+
+  return (
+    <div className="flex flex-col h-full gap-4 max-w-3xl">
+      <div className="flex items-center gap-2">
+        <ListChecks className="w-5 h-5" />
+        <h1 className="text-lg font-semibold">Batch Runs</h1>
+      </div>
+
+      <div className="grid grid-cols-[8rem_1fr] items-center gap-2 text-sm">
+        <label htmlFor="batch-model">Model</label>
+        <select
+          id="batch-model"
+          className="h-9 rounded-md border border-border bg-background px-2"
+          value={model}
+          onChange={(e) => setModel((e.target as HTMLSelectElement).value)}
+          disabled={running}
+        >
+          {models.map((name) => (
+            <option key={name} value={name}>
+              {name}
+            </option>
+          ))}
+        </select>
+        <label htmlFor="batch-input">Input file</label>
+        <Input
+          id="batch-input"
+          value={inputPath}
+          onInput={(e) => setInputPath((e.target as HTMLInputElement).value)}
+          placeholder="/path/to/dataset.csv or .jsonl"
+          disabled={running}
+        />
+        <label htmlFor="batch-output">Output file</label>
+        <Input
+          id="batch-output"
+          value={outputPath}
+          onInput={(e) => setOutputPath((e.target as HTMLInputElement).value)}
+          placeholder="Default: next to the input, .results.jsonl"
+          disabled={running}
+        />
+        <label htmlFor="batch-concurrency">Parallel requests</label>
+        <Input
+          id="batch-concurrency"
+          type="number"
+          min={1}
+          max={8}
+          value={concurrency}
+          onInput={(e) =>
+            setConcurrency(Number((e.target as HTMLInputElement).value) || 1)
+          }
+          disabled={running}
+        />
+      </div>
+
+      <Textarea
+        value={template}
+        onInput={(e) => setTemplate((e.target as HTMLTextAreaElement).value)}
+        placeholder="Prompt template, eg. Classify the sentiment of: {{text}}"
+        className="min-h-32"
+        disabled={running}
+      />
+
+      <div className="flex items-center gap-4">
+        <Button
+          onClick={handleRun}
+          disabled={running || !model || !inputPath.trim() || !template.trim()}
+        >
+          <Play className="w-4 h-4" />
+          {running ? "Running..." : "Run"}
+        </Button>
+        <span className="text-xs text-muted-foreground">
+          Running again with the same files resumes where the last run stopped.
+        </span>
+      </div>
+
+      {progress && (
+        <div className="space-y-1 text-sm">
+          <div className="h-2 rounded bg-muted overflow-hidden">
+            <div className="h-full bg-primary" style={{ width: `${percent}%` }} />
+          </div>
+          <div className="text-muted-foreground">
+            {progress.completed} / {progress.total} rows
+            {progress.skipped > 0 && ` · ${progress.skipped} from earlier runs`}
+            {progress.failed > 0 && ` · ${progress.failed} failed`}
+          </div>
+          {progress.error && (
+            <div className="text-destructive text-xs">
+              Row {progress.index}: {progress.error}
+            </div>
+          )}
+        </div>
+      )}
+
+      {summary && (
+        <div className="rounded-lg border border-border bg-card p-3 text-sm">
+          {summary.succeeded} succeeded, {summary.failed} failed,{" "}
+          {summary.skipped} skipped in{" "}
+          {(summary.duration_ms / 1000).toFixed(1)}s. Results:{" "}
+          <span className="font-mono">{summary.output_path}</span>
+        </div>
+      )}
+
+      {ToastComponent}
+    </div>
+  );
+}
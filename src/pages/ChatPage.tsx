@@ -2,17 +2,40 @@
 import { useEffect, useState, useRef } from "preact/hooks";
 import { Input } from "@/components/ui/input";
 import { Button } from "@/components/ui/button";
+import { Textarea } from "@/components/ui/textarea";
 import { useToast } from "@/components/ui/toast";
-import { useChatHistory, type Message } from "@/contexts/ChatHistoryContext";
+import {
+  useChatHistory,
+  type Conversation,
+  type Message,
+} from "@/contexts/ChatHistoryContext";
 import { OllamaClientClass, GeminiClientClass } from "$/lib/client";
-import type { ChatRequest, GeminiContent } from "$/lib/schemas/client.schema";
-import { SendHorizonal, Copy, Check, MessageCircle, Plus } from "lucide-react";
+import { errorMessage } from "$/lib/errors";
+import type {
+  ChatRequest,
+  ContextReport,
+  GeminiContent,
+  GenerationStats,
+} from "$/lib/schemas/client.schema";
+import {
+  SendHorizonal,
+  Copy,
+  Check,
+  MessageCircle,
+  Plus,
+  Pencil,
+  RefreshCw,
+  GitBranch,
+  ChevronLeft,
+  ChevronRight,
+} from "lucide-react";
 import ReactMarkdown from "react-markdown";
 import remarkGfm from "remark-gfm";
 import { RxGithubLogo } from "react-icons/rx";
 import { Light as SyntaxHighlighter } from "react-syntax-highlighter";
 import { docco } from "react-syntax-highlighter/dist/esm/styles/hljs";
 import Thinking from "@/components/Thinking";
+import PromptPicker from "@/components/PromptPicker";
 
 // Register languages for syntax highlighting
 import javascript from "react-syntax-highlighter/dist/esm/languages/hljs/javascript";
@@ -48,6 +71,19 @@ function unwrap<T>(v: MaybeSignal<T> | null | undefined): T | null {
   return v;
 }
 
+// Tells the user when older turns didn't fit the model's context window
+function contextNotice(report: ContextReport): string | null {
+  if (report.overflow) {
+    return `The latest message alone exceeds the ${report.context_length}-token context window; the model may not see all of it.`;
+  }
+  const count = report.dropped.length;
+  if (count === 0) return null;
+  const turns = `${count} earlier message${count === 1 ? "" : "s"}`;
+  return report.summary
+    ? `${turns} didn't fit the ${report.context_length}-token context window and were summarized.`
+    : `${turns} didn't fit the ${report.context_length}-token context window and were left out.`;
+}
+
 interface ChatPageProps {
   // allow string, null, or a signal-like { value: string | undefined }
   selectedModel: MaybeSignal<string | undefined> | string | null;
@@ -60,8 +96,6 @@ interface ChatPageProps {
 
 export default function ChatPage({
   selectedModel,
-  contextLength,
-  temperature,
   systemPrompt,
 }: ChatPageProps) {
   const [input, setInput] = useState("");
@@ -73,6 +107,10 @@ export default function ChatPage({
   const [isThinking, setIsThinking] = useState(false);
   const [streamingAssistantContent, setStreamingAssistantContent] =
     useState(""); // New state for streaming content
+  const [editingMessageId, setEditingMessageId] = useState<string | null>(
+    null,
+  );
+  const [editText, setEditText] = useState("");
   const containerRef = useRef<HTMLDivElement>(null);
   const { showToast, ToastComponent } = useToast();
 
@@ -82,6 +120,11 @@ export default function ChatPage({
     createNewConversation,
     addMessageToConversation,
     updateMessageInConversation,
+    editMessage,
+    regenerateMessage,
+    listBranches,
+    switchBranch,
+    forkConversation,
   } = useChatHistory();
 
   // Always work with a plain string here
@@ -94,160 +137,82 @@ export default function ChatPage({
     }
   }, [currentConversation?.messages, thinkingContent]);
 
-  const handleSubmit = async () => {
-    if (!input.trim()) return;
-    if (!modelName) {
-      showToast("Please select a model first.", "error");
-      return;
-    }
+  // Streams a reply to `history` into the assistant message `replyId`, which must be the last
+  // message on the conversation's active path
+  const streamReply = async (
+    conversationId: string,
+    replyId: string,
+    history: Message[],
+  ) => {
+    if (!modelName) return;
 
     // Determine service based on selected model
     const isGeminiModel = modelName.startsWith("gemini");
 
-    // Create new conversation if none exists or if model changed
-    let conversationId = currentConversationId;
-    let conversationToUse = currentConversation;
-    if (
-      !conversationId ||
-      (currentConversation && currentConversation.model !== modelName)
-    ) {
-      conversationId = createNewConversation(modelName);
-      conversationToUse = null; // New conversation, no previous messages
-    }
-
-    const userMessage: Message = {
-      role: "user",
-      content: input.trim(),
-      timestamp: Date.now(),
-    };
-
-    const userInput = input.trim();
-    setInput("");
     setLoading(true);
     setIsThinking(false);
     setThinkingContent("");
     setStreamingAssistantContent(""); // Initialize streaming content
 
-    // Calculate the position where the assistant message will be added
-    const currentMessageCount = conversationToUse?.messages.length || 0;
-    const assistantMessageIndex = currentMessageCount + 1;
-
-    // Add user message to conversation
-    addMessageToConversation(conversationId, userMessage);
-
     // Build message history for API call - include ALL previous messages for context
     const messagesToSend: Array<{
       role: "system" | "user" | "assistant";
       content: string;
     }> = [];
-    if (systemPrompt) {
+    // Ollama chats get their system prompt and options from the saved presets in the backend
+    if (systemPrompt && isGeminiModel) {
       messagesToSend.push({ role: "system", content: systemPrompt });
     }
-
-    // Add all messages from current conversation for full context (before adding the new user message)
-    if (conversationToUse) {
-      for (const msg of conversationToUse.messages) {
-        if (msg.role === "user" || msg.role === "assistant") {
-          messagesToSend.push({
-            role: msg.role,
-            content: msg.content,
-          });
-        }
+    for (const msg of history) {
+      if (msg.role === "user" || msg.role === "assistant") {
+        messagesToSend.push({
+          role: msg.role,
+          content: msg.content,
+        });
       }
     }
 
-    // Add the new user message to the API call
-    messagesToSend.push({ role: "user", content: userInput });
-
-    // Create placeholder assistant message
-    const assistantMessage: Message = {
-      role: "assistant",
-      content: "",
-      timestamp: Date.now(),
-    };
-    addMessageToConversation(conversationId, assistantMessage);
-
     try {
       let assistantResponse = ""; // This will accumulate the final response
+      let stats: GenerationStats | undefined;
+      let thinkingBuffer = "";
 
       if (!isGeminiModel) {
-        // Ollama logic
-        let thinkingBuffer = "";
-        let inThinkTag = false;
-
-        for await (const chunk of OllamaClient.chatStream({
-          model: modelName,
-          messages: messagesToSend,
-          options: {
-            num_ctx: contextLength || undefined,
-            temperature: temperature,
-          },
-        } as ChatRequest)) {
-          let content = chunk.message.content;
-
-          if (inThinkTag) {
-            if (content.includes("</think>")) {
-              const parts = content.split("</think>");
-              thinkingBuffer += parts[0];
-              setThinkingContent(thinkingBuffer);
-              inThinkTag = false;
-              content = parts[1] || "";
-            } else {
-              thinkingBuffer += content;
-              setThinkingContent(thinkingBuffer);
-              continue;
-            }
-          }
-
-          if (content.includes("<think>")) {
-            const parts = content.split("<think>");
-            assistantResponse += parts[0]; // Add pre-<think> content to final response
-            inThinkTag = true;
+        // Ollama logic; the backend splits reasoning onto its own channel
+        for await (const chunk of OllamaClient.chatStream(
+          {
+            model: modelName,
+            messages: messagesToSend,
+            conversation_id: conversationId,
+          } as ChatRequest,
+          (thinking) => {
+            thinkingBuffer += thinking;
             setIsThinking(true);
-            thinkingBuffer = parts[1] || "";
             setThinkingContent(thinkingBuffer);
-          } else {
-            assistantResponse += content; // Accumulate content for final response
+          },
+          (report) => {
+            const notice = contextNotice(report);
+            if (notice) showToast(notice, report.overflow ? "error" : "info");
+          },
+        )) {
+          if (chunk.done && chunk.stats) {
+            stats = chunk.stats;
           }
+          assistantResponse += chunk.message?.content ?? ""; // Accumulate content for final response
 
           // Update the streaming content for immediate display
           setStreamingAssistantContent(assistantResponse);
         }
-        // After streaming is complete, update the conversation with the final response
-        updateMessageInConversation(
-          conversationId,
-          assistantMessageIndex,
-          assistantResponse,
-        );
       } else {
-        // Gemini logic
-        const geminiConfigString = localStorage.getItem("geminiConfig");
-        if (!geminiConfigString) {
-          showToast(
-            "Gemini not configured. Please go to Cloud LLMs page.",
-            "error",
-          );
-          setLoading(false);
-          return;
-        }
+        // Gemini logic; an empty key makes the backend use its stored copy
         let geminiApiKey = "";
-        try {
-          const config = JSON.parse(geminiConfigString);
-          geminiApiKey = config.apiKey || "";
-        } catch (e) {
-          console.error("Failed to parse Gemini config from localStorage", e);
-          showToast(
-            "Invalid Gemini configuration. Please reconfigure.",
-            "error",
-          );
-          setLoading(false);
-          return;
-        }
-
-        if (!geminiApiKey) {
-          showToast("Please enter your Gemini API Key.", "error");
-          setLoading(false);
-          return;
+        const geminiConfigString = localStorage.getItem("geminiConfig");
+        if (geminiConfigString) {
+          try {
+            geminiApiKey = JSON.parse(geminiConfigString).apiKey || "";
+          } catch (e) {
+            console.error("Failed to parse Gemini config from localStorage", e);
+          }
         }
 
         const geminiMessages: GeminiContent[] = messagesToSend.map((msg) => ({
@@ -255,29 +220,31 @@ export default function ChatPage({
           parts: [{ text: msg.content }],
         }));
 
-        const geminiResponse = await GeminiClient.chat(
+        assistantResponse = await GeminiClient.chat(
           geminiApiKey,
           modelName, // Use modelName directly
           geminiMessages,
         );
-        assistantResponse = geminiResponse;
-
-        updateMessageInConversation(
-          conversationId,
-          assistantMessageIndex,
-          assistantResponse,
-        );
       }
+
+      // After streaming is complete, update the conversation with the final response
+      await updateMessageInConversation(
+        conversationId,
+        replyId,
+        assistantResponse,
+        stats,
+        thinkingBuffer,
+      );
     } catch (error) {
       console.error("Error during chat:", error);
-      showToast("Failed to send message. Please try again.", "error");
+      showToast(errorMessage(error), "error");
 
       // Update assistant message with error
-      updateMessageInConversation(
+      await updateMessageInConversation(
         conversationId,
-        assistantMessageIndex,
+        replyId,
         "Sorry, I encountered an error. Please try again.",
-      );
+      ).catch(() => {});
     } finally {
       setLoading(false);
       setIsThinking(false);
@@ -285,6 +252,109 @@ export default function ChatPage({
     }
   };
 
+  // Adds an empty assistant message after the active path and streams into it
+  const replyTo = async (conversation: Conversation) => {
+    const history = conversation.messages;
+    const updated = await addMessageToConversation(
+      conversation.id,
+      "assistant",
+      "",
+      modelName ?? undefined,
+    );
+    const reply = updated.messages[updated.messages.length - 1];
+    await streamReply(conversation.id, reply.id, history);
+  };
+
+  const handleSubmit = async () => {
+    if (!input.trim()) return;
+    if (!modelName) {
+      showToast("Please select a model first.", "error");
+      return;
+    }
+
+    const userInput = input.trim();
+    setInput("");
+
+    try {
+      // Create new conversation if none exists or if model changed
+      let conversationId = currentConversationId;
+      if (
+        !conversationId ||
+        (currentConversation && currentConversation.model !== modelName)
+      ) {
+        conversationId = (await createNewConversation(modelName)).id;
+      }
+
+      const conversation = await addMessageToConversation(
+        conversationId,
+        "user",
+        userInput,
+      );
+      await replyTo(conversation);
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  // Saves the edit as a new branch next to the original message and answers it
+  const handleSaveEdit = async (message: Message) => {
+    if (!currentConversationId || !editText.trim()) return;
+    setEditingMessageId(null);
+    try {
+      const conversation = await editMessage(
+        currentConversationId,
+        message.id,
+        editText.trim(),
+      );
+      await replyTo(conversation);
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  // Keeps the current reply as a branch and streams a fresh one next to it
+  const handleRegenerate = async (message: Message) => {
+    if (!currentConversationId) return;
+    try {
+      const conversation = await regenerateMessage(
+        currentConversationId,
+        message.id,
+        modelName ?? undefined,
+      );
+      const reply = conversation.messages[conversation.messages.length - 1];
+      await streamReply(
+        conversation.id,
+        reply.id,
+        conversation.messages.slice(0, -1),
+      );
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const handleSwitchBranch = async (message: Message, offset: number) => {
+    if (!currentConversationId) return;
+    try {
+      const branches = await listBranches(currentConversationId, message.id);
+      const target = branches[message.siblingIndex + offset];
+      if (target) {
+        await switchBranch(currentConversationId, target.messageId);
+      }
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const handleFork = async (message: Message) => {
+    if (!currentConversationId) return;
+    try {
+      await forkConversation(currentConversationId, message.id);
+      showToast("Forked into a new conversation.", "success");
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
   const handleCopy = async (content: string, index: number) => {
     try {
       await navigator.clipboard.writeText(content);
@@ -307,7 +377,9 @@ export default function ChatPage({
       showToast("Please select a model first.", "error");
       return;
     }
-    createNewConversation(modelName);
+    createNewConversation(modelName).catch((error) =>
+      showToast(errorMessage(error), "error"),
+    );
   };
 
   const messages = currentConversation?.messages || [];
@@ -366,8 +438,8 @@ export default function ChatPage({
         ) : (
           messages.map((message, index) => (
             <div
-              key={`${message.timestamp}-${index}`}
-              className={`flex ${message.role === "user" ? "justify-end" : "justify-start"}`}
+              key={message.id}
+              className={`flex flex-col ${message.role === "user" ? "items-end" : "items-start"}`}
             >
               <div
                 className={`max-w-[80%] rounded-lg px-4 py-2 relative group ${
@@ -378,9 +450,13 @@ export default function ChatPage({
               >
                 {message.role === "assistant" ? (
                   <div className="prose prose-sm max-w-none dark:prose-invert prose-headings:text-foreground prose-p:text-foreground prose-strong:text-foreground prose-em:text-foreground prose-ul:text-foreground prose-ol:text-foreground prose-li:text-foreground prose-blockquote:text-foreground prose-code:text-foreground prose-pre:text-foreground">
-                    {isThinking && index === messages.length - 1 && (
-                      <Thinking content={thinkingContent} />
-                    )}
+                    {loading && index === messages.length - 1
+                      ? isThinking && (
+                          <Thinking content={thinkingContent} active />
+                        )
+                      : message.thinking && (
+                          <Thinking content={message.thinking} />
+                        )}
                     <ReactMarkdown
                       remarkPlugins={[remarkGfm]}
                       components={{
@@ -475,6 +551,33 @@ export default function ChatPage({
                         : message.content}
                     </ReactMarkdown>
                   </div>
+                ) : editingMessageId === message.id ? (
+                  <div className="space-y-2 min-w-[20rem]">
+                    <Textarea
+                      value={editText}
+                      onInput={(e) =>
+                        setEditText((e.target as HTMLTextAreaElement).value)
+                      }
+                      className="bg-background text-foreground"
+                    />
+                    <div className="flex justify-end gap-2">
+                      <Button
+                        variant="ghost"
+                        size="sm"
+                        onClick={() => setEditingMessageId(null)}
+                      >
+                        Cancel
+                      </Button>
+                      <Button
+                        variant="secondary"
+                        size="sm"
+                        onClick={() => handleSaveEdit(message)}
+                        disabled={!editText.trim()}
+                      >
+                        Save & Submit
+                      </Button>
+                    </div>
+                  </div>
                 ) : (
                   <p className="whitespace-pre-wrap">{message.content}</p>
                 )}
@@ -491,11 +594,99 @@ export default function ChatPage({
                   )}
                 </Button>
               </div>
+              {!(loading && index === messages.length - 1) && (
+                <div className="flex items-center gap-1 mt-1 text-xs text-muted-foreground">
+                  {message.siblingCount > 1 && (
+                    <>
+                      <Button
+                        variant="ghost"
+                        size="sm"
+                        className="h-6 w-6 p-0"
+                        disabled={loading || message.siblingIndex === 0}
+                        onClick={() => handleSwitchBranch(message, -1)}
+                      >
+                        <ChevronLeft className="w-3 h-3" />
+                      </Button>
+                      <span>
+                        {message.siblingIndex + 1}/{message.siblingCount}
+                      </span>
+                      <Button
+                        variant="ghost"
+                        size="sm"
+                        className="h-6 w-6 p-0"
+                        disabled={
+                          loading ||
+                          message.siblingIndex === message.siblingCount - 1
+                        }
+                        onClick={() => handleSwitchBranch(message, 1)}
+                      >
+                        <ChevronRight className="w-3 h-3" />
+                      </Button>
+                    </>
+                  )}
+                  {message.role === "user" && (
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      className="h-6 w-6 p-0"
+                      title="Edit"
+                      disabled={loading}
+                      onClick={() => {
+                        setEditingMessageId(message.id);
+                        setEditText(message.content);
+                      }}
+                    >
+                      <Pencil className="w-3 h-3" />
+                    </Button>
+                  )}
+                  {message.role === "assistant" && (
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      className="h-6 w-6 p-0"
+                      title="Regenerate"
+                      disabled={loading}
+                      onClick={() => handleRegenerate(message)}
+                    >
+                      <RefreshCw className="w-3 h-3" />
+                    </Button>
+                  )}
+                  {message.stats?.tokens_per_second != null && (
+                    <span
+                      className="ml-1"
+                      title={`${message.stats.completion_tokens ?? "?"} tokens, ${Math.round(message.stats.total_ms ?? 0)}ms total`}
+                    >
+                      {message.stats.tokens_per_second.toFixed(1)} tok/s
+                      {message.stats.time_to_first_token_ms != null &&
+                        ` · ${Math.round(message.stats.time_to_first_token_ms)}ms to first token`}
+                    </span>
+                  )}
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    className="h-6 w-6 p-0"
+                    title="Fork from here"
+                    disabled={loading}
+                    onClick={() => handleFork(message)}
+                  >
+                    <GitBranch className="w-3 h-3" />
+                  </Button>
+                </div>
+              )}
             </div>
           ))
         )}
       </div>
 
+      {/* Library templates fill the message box */}
+      <div className="w-64 mb-2">
+        <PromptPicker
+          kind="user"
+          onApply={setInput}
+          onError={(message) => showToast(message, "error")}
+        />
+      </div>
+
       {/* Input */}
       <div className="flex gap-2">
         <Input
@@ -10,8 +10,11 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
-import { Trash2 } from "lucide-react";
+import { Trash2, Server } from "lucide-react";
 import { RiGeminiFill } from "react-icons/ri";
+import { OllamaClientClass } from "$/lib/client";
+import { errorMessage } from "$/lib/errors";
+import type { ServerStatus } from "$/lib/schemas/client.schema";
 
 interface GeminiConfig {
   apiKey: string;
@@ -19,11 +22,14 @@ interface GeminiConfig {
 }
 
 const GEMINI_CONFIG_KEY = "geminiConfig";
+const client = new OllamaClientClass();
 
 export default function CloudLLMsPage() {
   const [geminiApiKey, setGeminiApiKey] = useState<string>("");
   const [selectedGeminiModel, setSelectedGeminiModel] =
     useState<string>("gemini-pro");
+  const [serverStatus, setServerStatus] = useState<ServerStatus | null>(null);
+  const [serverPort, setServerPort] = useState<string>("");
   const { showToast, ToastComponent } = useToast();
 
   const availableGeminiModels = [
@@ -48,9 +54,16 @@ export default function CloudLLMsPage() {
         localStorage.removeItem(GEMINI_CONFIG_KEY);
       }
     }
+    client
+      .getServerStatus()
+      .then((status) => {
+        setServerStatus(status);
+        setServerPort(String(status.port));
+      })
+      .catch((e) => console.error("Failed to load server status", e));
   }, []);
 
-  const handleSaveGeminiConfig = () => {
+  const handleSaveGeminiConfig = async () => {
     if (!geminiApiKey) {
       showToast("Gemini API Key cannot be empty.", "error");
       return;
@@ -60,16 +73,64 @@ export default function CloudLLMsPage() {
       defaultModel: selectedGeminiModel,
     };
     localStorage.setItem(GEMINI_CONFIG_KEY, JSON.stringify(config));
+    try {
+      // The backend keeps its own copy for the local API server
+      await client.setProviderKey("gemini", geminiApiKey);
+    } catch (e) {
+      showToast(errorMessage(e), "error");
+      return;
+    }
     showToast("Gemini configuration saved successfully!", "success");
   };
 
-  const handleDeleteGeminiConfig = () => {
+  const handleDeleteGeminiConfig = async () => {
     localStorage.removeItem(GEMINI_CONFIG_KEY);
+    try {
+      await client.setProviderKey("gemini", null);
+    } catch (e) {
+      showToast(errorMessage(e), "error");
+    }
     setGeminiApiKey("");
     setSelectedGeminiModel("gemini-pro");
     showToast("Gemini configuration deleted.", "success");
   };
 
+  const updateServer = async (settings: {
+    enabled?: boolean;
+    port?: number;
+  }) => {
+    try {
+      const status = await client.setServerSettings(settings);
+      setServerStatus(status);
+      setServerPort(String(status.port));
+    } catch (e) {
+      showToast(errorMessage(e), "error");
+    }
+  };
+
+  const handleSaveServerPort = () => {
+    const port = Number(serverPort);
+    if (!Number.isInteger(port) || port < 1024 || port > 65535) {
+      showToast("Port must be between 1024 and 65535.", "error");
+      return;
+    }
+    updateServer({ port });
+  };
+
+  const handleRegenerateToken = async () => {
+    try {
+      setServerStatus(await client.regenerateServerToken());
+      showToast("Server token regenerated.", "success");
+    } catch (e) {
+      showToast(errorMessage(e), "error");
+    }
+  };
+
+  const handleCopy = async (text: string) => {
+    await navigator.clipboard.writeText(text);
+    showToast("Copied to clipboard.", "success");
+  };
+
   return (
     <div className="p-4 space-y-6">
       <h1 className="text-2xl font-bold">Cloud LLM Configurations</h1>
@@ -133,6 +194,94 @@ export default function CloudLLMsPage() {
         </CardContent>
       </Card>
 
+      <Card>
+        <CardHeader>
+          <CardTitle className={"flex gap-2 items-center"}>
+            <Server className="w-4 h-4" />
+            Local API Server
+          </CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <p className="text-sm text-muted-foreground">
+            Exposes an OpenAI-compatible API on localhost so editors and
+            scripts can use the same models and keys as Zama. Use{" "}
+            <code>gemini/&lt;model&gt;</code> to reach Gemini; other model
+            names go to Ollama.
+          </p>
+          <div>
+            <label
+              htmlFor="server-port"
+              className="block text-sm font-medium text-muted-foreground mb-1"
+            >
+              Port:
+            </label>
+            <div className="flex space-x-2">
+              <Input
+                id="server-port"
+                type="number"
+                value={serverPort}
+                onInput={(e) =>
+                  setServerPort((e.target as HTMLInputElement).value)
+                }
+                className="w-32"
+              />
+              <Button variant="outline" onClick={handleSaveServerPort}>
+                Apply
+              </Button>
+            </div>
+          </div>
+          {serverStatus?.running && serverStatus.base_url && (
+            <div className="space-y-2 text-sm">
+              <div className="flex items-center gap-2">
+                <span className="text-muted-foreground">Base URL:</span>
+                <code>{serverStatus.base_url}</code>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => handleCopy(serverStatus.base_url!)}
+                >
+                  Copy
+                </Button>
+              </div>
+              {serverStatus.token && (
+                <div className="flex items-center gap-2">
+                  <span className="text-muted-foreground">Token:</span>
+                  <code>{serverStatus.token.slice(0, 12)}…</code>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    onClick={() => handleCopy(serverStatus.token!)}
+                  >
+                    Copy
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    onClick={handleRegenerateToken}
+                  >
+                    Regenerate
+                  </Button>
+                </div>
+              )}
+            </div>
+          )}
+          <div className="flex space-x-2">
+            {serverStatus?.enabled ? (
+              <Button
+                variant="destructive"
+                onClick={() => updateServer({ enabled: false })}
+              >
+                Stop Server
+              </Button>
+            ) : (
+              <Button onClick={() => updateServer({ enabled: true })}>
+                Start Server
+              </Button>
+            )}
+          </div>
+        </CardContent>
+      </Card>
+
       {ToastComponent}
     </div>
   );
@@ -0,0 +1,188 @@
+import { useEffect, useState } from "preact/hooks";
+import { Button } from "@/components/ui/button";
+import { Textarea } from "@/components/ui/textarea";
+import { useToast } from "@/components/ui/toast";
+import { OllamaClientClass, compareChat } from "$/lib/client";
+import { errorMessage } from "$/lib/errors";
+import type { CompareResult } from "$/lib/schemas/client.schema";
+import { Columns3, Play } from "lucide-react";
+import ReactMarkdown from "react-markdown";
+import remarkGfm from "remark-gfm";
+
+const OllamaClient = new OllamaClientClass();
+
+interface ComparePageProps {
+  contextLength: number | null;
+  temperature: number;
+  systemPrompt: string;
+}
+
+function formatStats(result: CompareResult): string {
+  const parts = [`${(result.total_ms / 1000).toFixed(1)}s total`];
+  if (result.first_token_ms != null) {
+    parts.push(`${result.first_token_ms}ms to first token`);
+  }
+  if (result.tokens_per_second != null) {
+    parts.push(`${result.tokens_per_second.toFixed(1)} tok/s`);
+  }
+  if (result.prompt_tokens != null || result.completion_tokens != null) {
+    parts.push(
+      `${result.prompt_tokens ?? "?"} in / ${result.completion_tokens ?? "?"} out`,
+    );
+  }
+  return parts.join(" · ");
+}
+
+export default function ComparePage({
+  contextLength,
+  temperature,
+  systemPrompt,
+}: ComparePageProps) {
+  const [availableModels, setAvailableModels] = useState<string[]>([]);
+  const [selected, setSelected] = useState<string[]>([]);
+  const [prompt, setPrompt] = useState("");
+  const [running, setRunning] = useState(false);
+  const [outputs, setOutputs] = useState<Record<string, string>>({});
+  const [results, setResults] = useState<Record<string, CompareResult>>({});
+  const { showToast, ToastComponent } = useToast();
+
+  useEffect(() => {
+    const loadModels = async () => {
+      const models: string[] = [];
+      try {
+        const response = await OllamaClient.listModels();
+        models.push(...response.models.map((m) => m.name));
+      } catch (error) {
+        console.error("Failed to load Ollama models:", error);
+      }
+      try {
+        const config = JSON.parse(localStorage.getItem("geminiConfig") || "{}");
+        if (config.apiKey && config.defaultModel) {
+          models.push(`gemini/${config.defaultModel}`);
+        }
+      } catch (e) {
+        console.error("Failed to parse Gemini config from localStorage", e);
+      }
+      setAvailableModels(models);
+    };
+    loadModels();
+  }, []);
+
+  const toggleModel = (model: string) => {
+    setSelected((prev) =>
+      prev.includes(model) ? prev.filter((m) => m !== model) : [...prev, model],
+    );
+  };
+
+  const handleRun = async () => {
+    if (selected.length === 0 || !prompt.trim()) return;
+    const messages: Array<{
+      role: "system" | "user" | "assistant";
+      content: string;
+    }> = [];
+    if (systemPrompt) {
+      messages.push({ role: "system", content: systemPrompt });
+    }
+    messages.push({ role: "user", content: prompt.trim() });
+
+    setRunning(true);
+    setOutputs({});
+    setResults({});
+    try {
+      await compareChat(
+        selected,
+        messages,
+        { temperature, num_ctx: contextLength || undefined },
+        (chunk) =>
+          setOutputs((prev) => ({
+            ...prev,
+            [chunk.model]: (prev[chunk.model] || "") + chunk.content,
+          })),
+        (result) => setResults((prev) => ({ ...prev, [result.model]: result })),
+      );
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  return (
+    <div className="flex flex-col h-full gap-4">
+      <div className="flex items-center gap-2">
+        <Columns3 className="w-5 h-5" />
+        <h1 className="text-lg font-semibold">Compare Models</h1>
+      </div>
+
+      <div className="flex flex-wrap gap-2">
+        {availableModels.map((model) => (
+          <Button
+            key={model}
+            variant={selected.includes(model) ? "secondary" : "outline"}
+            size="sm"
+            onClick={() => toggleModel(model)}
+            disabled={running}
+          >
+            {model}
+          </Button>
+        ))}
+      </div>
+
+      <div className="flex gap-2">
+        <Textarea
+          value={prompt}
+          onInput={(e) => setPrompt((e.target as HTMLTextAreaElement).value)}
+          placeholder="Prompt to send to every selected model..."
+          className="flex-1"
+          disabled={running}
+        />
+        <Button
+          onClick={handleRun}
+          disabled={running || selected.length === 0 || !prompt.trim()}
+        >
+          <Play className="w-4 h-4" />
+          {running ? "Running..." : "Run"}
+        </Button>
+      </div>
+
+      <div
+        className="grid gap-4 flex-1 overflow-auto"
+        style={{
+          gridTemplateColumns: `repeat(${Math.max(selected.length, 1)}, minmax(0, 1fr))`,
+        }}
+      >
+        {selected.map((model) => {
+          const result = results[model];
+          return (
+            <div
+              key={model}
+              className="flex flex-col rounded-lg border border-border bg-card p-3 min-h-0"
+            >
+              <div className="font-mono text-sm font-semibold mb-1">
+                {model}
+              </div>
+              <div className="text-xs text-muted-foreground mb-2">
+                {result
+                  ? formatStats(result)
+                  : running
+                    ? "Generating..."
+                    : ""}
+              </div>
+              <div className="prose prose-sm max-w-none dark:prose-invert overflow-auto flex-1">
+                {result?.error ? (
+                  <p className="text-destructive">{result.error.message}</p>
+                ) : (
+                  <ReactMarkdown remarkPlugins={[remarkGfm]}>
+                    {outputs[model] || ""}
+                  </ReactMarkdown>
+                )}
+              </div>
+            </div>
+          );
+        })}
+      </div>
+
+      {ToastComponent}
+    </div>
+  );
+}
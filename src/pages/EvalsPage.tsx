@@ -0,0 +1,288 @@
+import { useEffect, useState } from "preact/hooks";
+import { Button } from "@/components/ui/button";
+import { Textarea } from "@/components/ui/textarea";
+import { useToast } from "@/components/ui/toast";
+import { OllamaClientClass } from "$/lib/client";
+import { EvalsClientClass } from "$/lib/evals-client";
+import { errorMessage } from "$/lib/errors";
+import type {
+  EvalDiff,
+  EvalProgress,
+  EvalReport,
+  EvalReportSummary,
+} from "$/lib/schemas/evals.schema";
+import { FlaskConical, Play, Trash2 } from "lucide-react";
+
+const OllamaClient = new OllamaClientClass();
+const EvalsClient = new EvalsClientClass();
+
+const EXAMPLE_SUITE = `name: capitals
+system: Answer in one short sentence.
+cases:
+  - name: france
+    input: What is the capital of France?
+    expect:
+      - contains: Paris
+  - name: json
+    input: 'Reply with JSON like {"capital": "..."} for Japan.'
+    expect:
+      - json_schema:
+          type: object
+          required: [capital]
+      - regex: Tokyo
+  - name: tone
+    input: Why is the sky blue?
+    expect:
+      - judge: Explains Rayleigh scattering correctly and briefly
+`;
+
+const percent = (score: number) => `${Math.round(score * 100)}%`;
+
+export default function EvalsPage() {
+  const [suite, setSuite] = useState(EXAMPLE_SUITE);
+  const [models, setModels] = useState<string[]>([]);
+  const [selected, setSelected] = useState<string[]>([]);
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<EvalProgress | null>(null);
+  const [report, setReport] = useState<EvalReport | null>(null);
+  const [diff, setDiff] = useState<EvalDiff | null>(null);
+  const [history, setHistory] = useState<EvalReportSummary[]>([]);
+  const { showToast, ToastComponent } = useToast();
+
+  const loadHistory = () =>
+    EvalsClient.list()
+      .then(setHistory)
+      .catch((error) => console.error("Failed to load eval reports:", error));
+
+  useEffect(() => {
+    OllamaClient.listModels()
+      .then((response) => setModels(response.models.map((m) => m.name)))
+      .catch((error) => console.error("Failed to load Ollama models:", error));
+    loadHistory();
+  }, []);
+
+  const showReport = async (next: EvalReport) => {
+    setReport(next);
+    setDiff(
+      next.baseline_id ? await EvalsClient.diff(next.id).catch(() => null) : null,
+    );
+  };
+
+  const handleRun = async () => {
+    setRunning(true);
+    setProgress(null);
+    try {
+      await showReport(
+        await EvalsClient.run(
+          suite,
+          selected.length > 0 ? selected : undefined,
+          setProgress,
+        ),
+      );
+      loadHistory();
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const openReport = async (id: string) => {
+    try {
+      await showReport(await EvalsClient.get(id));
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const deleteReport = async (id: string) => {
+    try {
+      await EvalsClient.delete(id);
+      if (report?.id === id) {
+        setReport(null);
+        setDiff(null);
+      }
+      loadHistory();
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  return (
+    <div className="flex h-full gap-4">
+      <div className="flex flex-col flex-1 gap-4 min-w-0">
+        <div className="flex items-center gap-2">
+          <FlaskConical className="w-5 h-5" />
+          <h1 className="text-lg font-semibold">Evaluations</h1>
+        </div>
+
+        <div className="flex flex-wrap gap-2">
+          {models.map((model) => (
+            <Button
+              key={model}
+              variant={selected.includes(model) ? "secondary" : "outline"}
+              size="sm"
+              disabled={running}
+              onClick={() =>
+                setSelected((prev) =>
+                  prev.includes(model)
+                    ? prev.filter((m) => m !== model)
+                    : [...prev, model],
+                )
+              }
+            >
+              {model}
+            </Button>
+          ))}
+        </div>
+
+        <Textarea
+          value={suite}
+          onInput={(e) => setSuite((e.target as HTMLTextAreaElement).value)}
+          className="font-mono text-xs min-h-48"
+          disabled={running}
+        />
+
+        <div className="flex items-center gap-4">
+          <Button onClick={handleRun} disabled={running || !suite.trim()}>
+            <Play className="w-4 h-4" />
+            {running ? "Running..." : "Run suite"}
+          </Button>
+          <span className="text-xs text-muted-foreground">
+            {running && progress
+              ? `${progress.completed} / ${progress.total} · ${progress.model} · ${progress.case}`
+              : "Without a selection, the models listed in the suite are used."}
+          </span>
+        </div>
+
+        {report && (
+          <div className="flex-1 overflow-auto space-y-4">
+            <table className="w-full text-sm">
+              <thead className="text-left text-muted-foreground">
+                <tr>
+                  <th>Model</th>
+                  <th>Score</th>
+                  <th>Passed</th>
+                  <th>Errors</th>
+                  <th>Change</th>
+                </tr>
+              </thead>
+              <tbody>
+                {report.models.map((m) => {
+                  const delta = diff?.models.find(
+                    (d) => d.model === m.model,
+                  )?.delta;
+                  return (
+                    <tr key={m.model}>
+                      <td className="font-mono">{m.model}</td>
+                      <td>{percent(m.score)}</td>
+                      <td>
+                        {m.passed} / {m.passed + m.failed}
+                      </td>
+                      <td>{m.errors}</td>
+                      <td>
+                        {delta == null
+                          ? "—"
+                          : `${delta >= 0 ? "+" : ""}${Math.round(delta * 100)} pts`}
+                      </td>
+                    </tr>
+                  );
+                })}
+              </tbody>
+            </table>
+
+            {diff && diff.regressions + diff.fixes > 0 && (
+              <div className="text-sm">
+                {diff.regressions > 0 && (
+                  <span className="text-destructive">
+                    {diff.regressions} regression
+                    {diff.regressions === 1 ? "" : "s"}{" "}
+                  </span>
+                )}
+                {diff.fixes > 0 && <span>{diff.fixes} fixed </span>}
+                since the previous run
+                {!diff.same_suite && " (the suite has changed)"}
+              </div>
+            )}
+
+            {report.results.map((result) => {
+              const change = diff?.changes.find(
+                (c) => c.model === result.model && c.case === result.case,
+              );
+              return (
+                <div
+                  key={`${result.model}/${result.case}`}
+                  className="rounded-lg border border-border bg-card p-3 text-sm space-y-1"
+                >
+                  <div className="flex justify-between">
+                    <span>
+                      <span className="font-mono">{result.model}</span> ·{" "}
+                      {result.case}
+                    </span>
+                    <span
+                      className={result.passed ? "" : "text-destructive"}
+                    >
+                      {result.passed ? "pass" : "fail"} ·{" "}
+                      {percent(result.score)}
+                      {change && ` · ${change.kind.replace("_", " ")}`}
+                    </span>
+                  </div>
+                  {result.error && (
+                    <div className="text-destructive">{result.error}</div>
+                  )}
+                  {result.assertions.map((a, i) => (
+                    <div
+                      key={i}
+                      className={`text-xs ${a.passed ? "text-muted-foreground" : "text-destructive"}`}
+                    >
+                      {a.passed ? "✓" : "✗"} {a.kind}: {a.expected}
+                      {a.detail && ` — ${a.detail}`}
+                    </div>
+                  ))}
+                  {result.output && (
+                    <pre className="text-xs whitespace-pre-wrap text-muted-foreground">
+                      {result.output}
+                    </pre>
+                  )}
+                </div>
+              );
+            })}
+          </div>
+        )}
+      </div>
+
+      <aside className="w-64 space-y-2 overflow-auto">
+        <h2 className="text-sm font-semibold">Past runs</h2>
+        {history.map((h) => (
+          <div
+            key={h.id}
+            className={`flex items-center justify-between rounded border border-border p-2 text-xs cursor-pointer ${report?.id === h.id ? "bg-secondary" : ""}`}
+            onClick={() => openReport(h.id)}
+          >
+            <div>
+              <div className="font-semibold">{h.suite}</div>
+              <div className="text-muted-foreground">
+                {new Date(h.created_at).toLocaleString()}
+              </div>
+              <div className="text-muted-foreground">
+                {h.models.map((m) => `${m.model} ${percent(m.score)}`).join(", ")}
+              </div>
+            </div>
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={(e) => {
+                e.stopPropagation();
+                deleteReport(h.id);
+              }}
+            >
+              <Trash2 className="w-3 h-3" />
+            </Button>
+          </div>
+        ))}
+      </aside>
+
+      {ToastComponent}
+    </div>
+  );
+}
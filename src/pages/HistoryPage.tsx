@@ -1,5 +1,8 @@
-import { useState } from "preact/hooks";
+import { useEffect, useState } from "preact/hooks";
 import { useChatHistory } from "@/contexts/ChatHistoryContext";
+import { HistoryClientClass } from "$/lib/history-client";
+import { errorMessage } from "$/lib/errors";
+import type { TitleSettings } from "$/lib/schemas/history.schema";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { useToast } from "@/components/ui/toast";
@@ -10,16 +13,20 @@ import {
   Check,
   X,
   MessageSquare,
+  Sparkles,
 } from "lucide-react";
 import ReactMarkdown from "react-markdown";
 import remarkGfm from "remark-gfm";
 
+const History = new HistoryClientClass();
+
 export default function HistoryPage() {
   const {
     conversations,
     switchToConversation,
     deleteConversation,
     updateConversationTitle,
+    generateTitle,
     clearAllConversations,
   } = useChatHistory();
 
@@ -31,6 +38,36 @@ export default function HistoryPage() {
   const [expandedConversation, setExpandedConversation] = useState<
     string | null
   >(null);
+  const [titleSettings, setTitleSettings] = useState<TitleSettings | null>(
+    null,
+  );
+  const [titleModel, setTitleModel] = useState("");
+
+  useEffect(() => {
+    History.getTitleSettings()
+      .then((settings) => {
+        setTitleSettings(settings);
+        setTitleModel(settings.model ?? "");
+      })
+      .catch((error) => console.error("Failed to load title settings:", error));
+  }, []);
+
+  const saveTitleSettings = async (settings: TitleSettings) => {
+    try {
+      setTitleSettings(await History.setTitleSettings(settings));
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const handleGenerateTitle = async (conversationId: string) => {
+    try {
+      await generateTitle(conversationId);
+      showToast("Generating title...", "info");
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
 
   const handleEditTitle = (conversationId: string, currentTitle: string) => {
     setEditingConversationId(conversationId);
@@ -85,6 +122,35 @@ export default function HistoryPage() {
               {conversations.length !== 1 ? "s" : ""}
             </p>
           </div>
+          {titleSettings && (
+            <div className="flex items-center gap-2 text-sm">
+              <label className="flex items-center gap-1 text-muted-foreground">
+                <input
+                  type="checkbox"
+                  checked={titleSettings.enabled}
+                  onChange={(e) =>
+                    saveTitleSettings({
+                      ...titleSettings,
+                      enabled: e.currentTarget.checked,
+                    })
+                  }
+                />
+                Auto-title with
+              </label>
+              <Input
+                value={titleModel}
+                placeholder="conversation's model"
+                className="h-8 w-48"
+                onChange={(e) => setTitleModel(e.currentTarget.value)}
+                onBlur={() =>
+                  saveTitleSettings({
+                    ...titleSettings,
+                    model: titleModel.trim() || null,
+                  })
+                }
+              />
+            </div>
+          )}
           {conversations.length > 0 && (
             <Button
               onClick={() => {
@@ -159,6 +225,11 @@ export default function HistoryPage() {
                           <span>•</span>
                           <span>{formatDate(conversation.updatedAt)}</span>
                         </div>
+                        {conversation.summary && (
+                          <p className="text-sm text-muted-foreground mt-2">
+                            {conversation.summary}
+                          </p>
+                        )}
                       </div>
                     )}
                   </div>
@@ -183,6 +254,14 @@ export default function HistoryPage() {
                     >
                       <Edit3 size={14} />
                     </Button>
+                    <Button
+                      size="sm"
+                      variant="ghost"
+                      title="Generate title and summary"
+                      onClick={() => handleGenerateTitle(conversation.id)}
+                    >
+                      <Sparkles size={14} />
+                    </Button>
                     <Button
                       size="sm"
                       variant="ghost"
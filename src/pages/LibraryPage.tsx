@@ -1,8 +1,12 @@
 /** @jsxImportSource preact */
 import { useEffect, useState } from "preact/hooks";
-import type { OllamaDBModel } from "$/lib/schemas/ollamadb.schema";
-import type { HfModel } from "$/lib/schemas/hf.schema";
+import type {
+  CatalogPage,
+  OllamaDBModel,
+} from "$/lib/schemas/ollamadb.schema";
+import type { HfGgufListing, HfModel } from "$/lib/schemas/hf.schema";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { Input } from "@/components/ui/input";
 import { Toaster } from "@/components/ui/sonner";
 import { toast } from "sonner";
@@ -21,6 +25,7 @@ import { Check } from "lucide-react";
 import { OllamaClientClass } from "$/lib/client";
 import { HfClientClass } from "$/lib/hf-client";
 import { SiHuggingface } from "react-icons/si";
+import MemoryFitBadge from "@/components/MemoryFitBadge";
 
 type UnifiedModel = {
   id: string;
@@ -51,6 +56,9 @@ export default function LibraryPage() {
     new Set(),
   );
   const [provider, setProvider] = useState<"ollama" | "huggingface">("ollama");
+  const [catalogVersion, setCatalogVersion] = useState(0);
+  const [quantListing, setQuantListing] = useState<HfGgufListing | null>(null);
+  const [loadingQuants, setLoadingQuants] = useState<string | null>(null);
 
   const getBaseModelName = (modelName: string) => {
     const parts = modelName.split(":");
@@ -65,9 +73,9 @@ export default function LibraryPage() {
         let fetchedModels: UnifiedModel[] = [];
 
         if (provider === "ollama") {
-          const res = await invoke<string>("get_ollama_models");
-          const json = JSON.parse(res);
-          fetchedModels = json.models.map((model: OllamaDBModel) => ({
+          const res =
+            await invoke<CatalogPage<OllamaDBModel>>("get_ollama_models");
+          fetchedModels = res.models.map((model: OllamaDBModel) => ({
             id: model.model_identifier,
             name: model.model_name,
             description: model.description,
@@ -87,10 +95,10 @@ export default function LibraryPage() {
             tags: model.tags,
             url: `https://huggingface.co/${model.modelId}`,
             provider: "huggingface",
-            author: model.author,
-            lastModified: model.lastModified,
-            downloads: model.downloads,
-            likes: model.likes,
+            author: model.author ?? undefined,
+            lastModified: model.lastModified ?? undefined,
+            downloads: model.downloads ?? undefined,
+            likes: model.likes ?? undefined,
           }));
         }
 
@@ -118,11 +126,38 @@ export default function LibraryPage() {
     }, 300); // 300ms debounce
 
     return () => clearTimeout(debounce);
-  }, [provider, query]);
+  }, [provider, query, catalogVersion]);
 
-  const handlePullModel = async (model: UnifiedModel) => {
-    const modelName =
-      provider === "huggingface" ? `hf.co/${model.name}` : model.name;
+  // Stale catalogs are served from cache and refreshed in the background
+  useEffect(() => {
+    const unlisten = listen("catalog-updated", () =>
+      setCatalogVersion((v) => v + 1),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Hugging Face repos hold several quantizations, so pick one before pulling
+  const handleShowQuants = async (model: UnifiedModel) => {
+    if (quantListing?.repo === model.name) {
+      setQuantListing(null);
+      return;
+    }
+    setLoadingQuants(model.name);
+    try {
+      setQuantListing(await HfClient.listGgufFiles(model.name));
+    } catch (err) {
+      toast.error(`Failed to list files for ${model.name}.`, {
+        description: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setLoadingQuants(null);
+    }
+  };
+
+  const handlePullModel = async (model: UnifiedModel, pullName?: string) => {
+    const modelName = pullName ?? model.name;
     setPullingModel(model.name);
     const toastId = toast.loading(`Installing ${model.name}...`);
     try {
@@ -233,9 +268,14 @@ export default function LibraryPage() {
                           ? "disabled"
                           : "hover:cursor-pointer"
                       } hover:bg-accent rounded p-1.5}`}
-                      onClick={() => handlePullModel(model)}
+                      onClick={() =>
+                        model.provider === "huggingface"
+                          ? handleShowQuants(model)
+                          : handlePullModel(model)
+                      }
                       disabled={
                         pullingModel === model.name ||
+                        loadingQuants === model.name ||
                         installedModels.has(getBaseModelName(model.name))
                       }
                     >
@@ -260,6 +300,53 @@ export default function LibraryPage() {
                     Modified: {model.lastModified}
                   </p>
                 )}
+                {quantListing?.repo === model.name && (
+                  <ul className="mt-2 border-t pt-2 text-sm">
+                    {quantListing.quantizations.length === 0 && (
+                      <li className="text-muted-foreground">
+                        No GGUF files in this repository.
+                      </li>
+                    )}
+                    {quantListing.quantizations.map((q) => (
+                      <li
+                        key={q.quant}
+                        className="flex justify-between items-center py-1"
+                      >
+                        <span className="flex items-center gap-2">
+                          {q.quant} ·{" "}
+                          {(q.total_size / (1000 * 1000 * 1000)).toFixed(1)} GB
+                          <MemoryFitBadge
+                            request={{
+                              file_size: q.total_size,
+                              quantization_level: q.bits_per_weight
+                                ? q.quant
+                                : undefined,
+                            }}
+                          />
+                        </span>
+                        <button
+                          type={"button"}
+                          className={`${
+                            q.pull_name && pullingModel !== model.name
+                              ? "hover:cursor-pointer"
+                              : "disabled"
+                          } hover:bg-accent rounded p-1.5`}
+                          title={
+                            q.pull_name
+                              ? `Pull ${q.pull_name}`
+                              : "Unknown quantization, can't be pulled by name"
+                          }
+                          onClick={() =>
+                            q.pull_name && handlePullModel(model, q.pull_name)
+                          }
+                          disabled={!q.pull_name || pullingModel === model.name}
+                        >
+                          <RxDownload />
+                        </button>
+                      </li>
+                    ))}
+                  </ul>
+                )}
                 <div className="mt-2 flex flex-wrap gap-1">
                   {model.provider === "huggingface" &&
                     Array.isArray(model.tags) &&
@@ -0,0 +1,111 @@
+import { useEffect, useState } from "preact/hooks";
+import { Button } from "@/components/ui/button";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { useToast } from "@/components/ui/toast";
+import { OllamaClientClass } from "$/lib/client";
+import { errorMessage } from "$/lib/errors";
+import type { LogEntry } from "$/lib/schemas/client.schema";
+import { FileArchive, RefreshCcw } from "lucide-react";
+
+const OllamaClient = new OllamaClientClass();
+
+const LEVELS = ["error", "warn", "info", "debug", "trace"];
+const LEVEL_COLORS: Record<string, string> = {
+  ERROR: "text-red-400",
+  WARN: "text-yellow-400",
+};
+
+export default function LogsPage() {
+  const [logs, setLogs] = useState<LogEntry[]>([]);
+  const [minLevel, setMinLevel] = useState("info");
+  const [loading, setLoading] = useState(false);
+  const [exporting, setExporting] = useState(false);
+  const { showToast, ToastComponent } = useToast();
+
+  const fetchLogs = async () => {
+    setLoading(true);
+    try {
+      setLogs(await OllamaClient.getRecentLogs(500, minLevel));
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleExport = async () => {
+    setExporting(true);
+    try {
+      const path = await OllamaClient.exportDiagnostics();
+      showToast(`Diagnostics saved to ${path}`, "success");
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  useEffect(() => {
+    fetchLogs();
+  }, [minLevel]);
+
+  return (
+    <div className="p-4 bg-background text-foreground">
+      <div className="flex items-center justify-between mb-4 gap-2">
+        <h2 className="text-xl font-semibold">Logs</h2>
+        <div className="flex gap-2">
+          <Select value={minLevel} onValueChange={setMinLevel}>
+            <SelectTrigger className="w-[120px]">
+              <SelectValue placeholder="Level" />
+            </SelectTrigger>
+            <SelectContent>
+              {LEVELS.map((level) => (
+                <SelectItem key={level} value={level}>
+                  {level}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <Button variant="outline" onClick={fetchLogs} disabled={loading}>
+            <RefreshCcw className="w-4 h-4 mr-2" />
+            Refresh
+          </Button>
+          <Button variant="outline" onClick={handleExport} disabled={exporting}>
+            <FileArchive className="w-4 h-4 mr-2" />
+            Export diagnostics
+          </Button>
+        </div>
+      </div>
+
+      {logs.length === 0 ? (
+        <p className="text-muted-foreground">No log entries.</p>
+      ) : (
+        <ul className="font-mono text-xs space-y-1">
+          {logs.map((entry, i) => (
+            <li key={i} className="flex gap-2">
+              <span className="text-muted-foreground shrink-0">
+                {entry.timestamp}
+              </span>
+              <span
+                className={`shrink-0 w-12 ${LEVEL_COLORS[entry.level ?? ""] ?? ""}`}
+              >
+                {entry.level}
+              </span>
+              <span className="text-muted-foreground shrink-0">
+                {entry.target}
+              </span>
+              <span className="break-all">{entry.message}</span>
+            </li>
+          ))}
+        </ul>
+      )}
+      {ToastComponent}
+    </div>
+  );
+}
@@ -1,15 +1,30 @@
 import { useEffect, useState } from "preact/hooks";
+import { listen } from "@tauri-apps/api/event";
 import { OllamaClientClass } from "$/lib/client";
-import type { ListModelsResponse } from "$/lib/schemas/client.schema";
+import type {
+  GgufInspection,
+  ListModelsResponse,
+} from "$/lib/schemas/client.schema";
 import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Textarea } from "@/components/ui/textarea";
+import MemoryFitBadge from "@/components/MemoryFitBadge";
 import { useToast } from "@/components/ui/toast";
-import { Trash2, RefreshCcw } from "lucide-react";
+import { Trash2, RefreshCcw, FileUp, Info } from "lucide-react";
 
 const OllamaClient = new OllamaClientClass();
 
 export default function ModelsPage() {
   const [models, setModels] = useState<ListModelsResponse["models"]>([]);
   const [loading, setLoading] = useState(false);
+  const [importPath, setImportPath] = useState("");
+  const [importName, setImportName] = useState("");
+  const [importTemplate, setImportTemplate] = useState("");
+  const [importStage, setImportStage] = useState<string | null>(null);
+  const [inspection, setInspection] = useState<{
+    model: string;
+    gguf: GgufInspection;
+  } | null>(null);
   const { showToast, ToastComponent } = useToast();
 
   const fetchModels = async () => {
@@ -34,10 +49,59 @@ export default function ModelsPage() {
     }
   };
 
+  const handleInspect = async (name: string) => {
+    if (inspection?.model === name) {
+      setInspection(null);
+      return;
+    }
+    try {
+      const gguf = await OllamaClient.inspectGguf({ model: name });
+      setInspection({ model: name, gguf });
+    } catch (err) {
+      showToast(
+        err instanceof Error ? err.message : `Failed to inspect ${name}`,
+        "error",
+      );
+    }
+  };
+
+  const handleImport = async (e: Event) => {
+    e.preventDefault();
+    setImportStage("reading");
+    try {
+      const result = await OllamaClient.importGguf({
+        path: importPath.trim(),
+        name: importName.trim(),
+        template: importTemplate.trim() || undefined,
+      });
+      showToast(`Imported ${result.model}`, "success");
+      setImportPath("");
+      setImportName("");
+      setImportTemplate("");
+      fetchModels();
+    } catch (err) {
+      showToast(
+        err instanceof Error ? err.message : `Failed to import ${importName}`,
+        "error",
+      );
+    } finally {
+      setImportStage(null);
+    }
+  };
+
   useEffect(() => {
     fetchModels();
   }, []);
 
+  useEffect(() => {
+    const unlisten = listen<{ stage: string }>("gguf-import-progress", (e) =>
+      setImportStage(e.payload.stage),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   return (
     <div className="p-4 bg-background text-foreground">
       <div className="flex items-center justify-between mb-4">
@@ -60,29 +124,94 @@ export default function ModelsPage() {
         </Button>
       </div>
 
+      <form
+        onSubmit={handleImport}
+        className="bg-card rounded-lg p-4 mb-4 space-y-2"
+      >
+        <div className="font-medium">Import GGUF</div>
+        <div className="flex gap-2">
+          <Input
+            placeholder="/absolute/path/to/model.gguf"
+            value={importPath}
+            onInput={(e) => setImportPath((e.target as HTMLInputElement).value)}
+          />
+          <Input
+            placeholder="name:tag"
+            value={importName}
+            onInput={(e) => setImportName((e.target as HTMLInputElement).value)}
+          />
+        </div>
+        <Textarea
+          placeholder="Ollama template (optional, defaults to the file's own)"
+          value={importTemplate}
+          onInput={(e) =>
+            setImportTemplate((e.target as HTMLTextAreaElement).value)
+          }
+        />
+        <Button
+          type="submit"
+          variant="outline"
+          disabled={!importPath.trim() || !importName.trim() || !!importStage}
+        >
+          <FileUp className="w-4 h-4 mr-2" />
+          {importStage ? `Importing (${importStage})...` : "Import"}
+        </Button>
+      </form>
+
       {models.length === 0 ? (
         <p className="text-muted-foreground">No models installed.</p>
       ) : (
         <ul className="space-y-3">
           {models.map((model) => (
-            <li
-              key={model.name}
-              className="bg-card rounded-lg p-4 flex justify-between items-center"
-            >
-              <div>
-                <div className="font-medium">{model.name}</div>
-                <div className="text-sm text-muted-foreground">
-                  Size: {Math.round(model.size / (1000 * 1000 * 1000))} GB |
-                  Last Modified: {model.modified_at}
+            <li key={model.name} className="bg-card rounded-lg p-4">
+              <div className="flex justify-between items-center">
+                <div>
+                  <div className="font-medium flex items-center gap-2">
+                    {model.name}
+                    <MemoryFitBadge request={{ model: model.name }} />
+                  </div>
+                  <div className="text-sm text-muted-foreground">
+                    Size: {Math.round(model.size / (1000 * 1000 * 1000))} GB |
+                    Last Modified: {model.modified_at}
+                  </div>
+                </div>
+                <div className="flex gap-2">
+                  <Button
+                    variant="outline"
+                    onClick={() => handleInspect(model.name)}
+                  >
+                    <Info className="w-4 h-4 mr-2" />
+                    Inspect
+                  </Button>
+                  <Button
+                    variant="destructive"
+                    onClick={() => handleDelete(model.name)}
+                  >
+                    <Trash2 className="w-4 h-4 mr-2" />
+                    Delete
+                  </Button>
                 </div>
               </div>
-              <Button
-                variant="destructive"
-                onClick={() => handleDelete(model.name)}
-              >
-                <Trash2 className="w-4 h-4 mr-2" />
-                Delete
-              </Button>
+              {inspection?.model === model.name && (
+                <dl className="mt-3 grid grid-cols-2 gap-x-4 gap-y-1 text-sm">
+                  <dt className="text-muted-foreground">Architecture</dt>
+                  <dd>{inspection.gguf.architecture ?? "unknown"}</dd>
+                  <dt className="text-muted-foreground">Quantization</dt>
+                  <dd>{inspection.gguf.file_type ?? "unknown"}</dd>
+                  <dt className="text-muted-foreground">Parameters</dt>
+                  <dd>{inspection.gguf.parameter_count.toLocaleString()}</dd>
+                  <dt className="text-muted-foreground">Context length</dt>
+                  <dd>{inspection.gguf.context_length ?? "unknown"}</dd>
+                  <dt className="text-muted-foreground">Layers</dt>
+                  <dd>{inspection.gguf.layer_count ?? "unknown"}</dd>
+                  <dt className="text-muted-foreground">Tensor types</dt>
+                  <dd>
+                    {inspection.gguf.tensor_types
+                      .map((t) => `${t.ggml_type} (${t.tensors})`)
+                      .join(", ")}
+                  </dd>
+                </dl>
+              )}
             </li>
           ))}
         </ul>
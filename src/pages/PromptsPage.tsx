@@ -0,0 +1,329 @@
+import { useEffect, useState } from "preact/hooks";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Textarea } from "@/components/ui/textarea";
+import { useToast } from "@/components/ui/toast";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { Plus, Trash2, Download, Upload, NotebookPen } from "lucide-react";
+import { PromptsClientClass } from "$/lib/prompts-client";
+import { errorMessage } from "$/lib/errors";
+import type {
+  Prompt,
+  PromptInput,
+  PromptKind,
+} from "$/lib/schemas/prompts.schema";
+
+const Prompts = new PromptsClientClass();
+
+const emptyDraft = (): PromptInput => ({
+  name: "",
+  kind: "system",
+  description: "",
+  tags: [],
+  content: "",
+  note: "",
+});
+
+function toDraft(prompt: Prompt): PromptInput {
+  return {
+    id: prompt.id,
+    name: prompt.name,
+    kind: prompt.kind,
+    description: prompt.description ?? "",
+    tags: prompt.tags,
+    content: prompt.content,
+    note: "",
+  };
+}
+
+function download(filename: string, content: string, type: string) {
+  const url = URL.createObjectURL(new Blob([content], { type }));
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = filename;
+  link.click();
+  URL.revokeObjectURL(url);
+}
+
+export default function PromptsPage() {
+  const [prompts, setPrompts] = useState<Prompt[]>([]);
+  const [tagFilter, setTagFilter] = useState<string>("");
+  const [draft, setDraft] = useState<PromptInput>(emptyDraft());
+  const [tagsText, setTagsText] = useState("");
+  const { showToast, ToastComponent } = useToast();
+
+  const load = async () => {
+    try {
+      setPrompts(await Prompts.list());
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const selected = prompts.find((p) => p.id === draft.id) ?? null;
+  const allTags = [...new Set(prompts.flatMap((p) => p.tags))].sort();
+  const visible = tagFilter
+    ? prompts.filter((p) => p.tags.includes(tagFilter))
+    : prompts;
+
+  const edit = (prompt: Prompt | null) => {
+    const next = prompt ? toDraft(prompt) : emptyDraft();
+    setDraft(next);
+    setTagsText(next.tags.join(", "));
+  };
+
+  const handleSave = async () => {
+    try {
+      const saved = await Prompts.save({
+        ...draft,
+        tags: tagsText.split(","),
+      });
+      await load();
+      edit(saved);
+      showToast(`Saved "${saved.name}" (v${saved.version})`, "success");
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const handleDelete = async () => {
+    if (!draft.id) return;
+    try {
+      await Prompts.delete(draft.id);
+      edit(null);
+      await load();
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const handleExport = async (format: "json" | "yaml") => {
+    try {
+      const content = await Prompts.export(format);
+      download(
+        `zama-prompts.${format}`,
+        content,
+        format === "json" ? "application/json" : "application/yaml",
+      );
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  const handleImport = async (file: File | undefined) => {
+    if (!file) return;
+    try {
+      const result = await Prompts.import(await file.text());
+      await load();
+      showToast(
+        `Imported ${result.created} new, ${result.updated} updated, ${result.unchanged} unchanged`,
+        "success",
+      );
+    } catch (error) {
+      showToast(errorMessage(error), "error");
+    }
+  };
+
+  return (
+    <div className="flex gap-4 h-full">
+      {/* Library */}
+      <div className="w-72 flex flex-col gap-2">
+        <div className="flex items-center justify-between">
+          <h1 className="text-xl font-bold">Prompts</h1>
+          <Button size="sm" onClick={() => edit(null)}>
+            <Plus size={14} className="mr-1" />
+            New
+          </Button>
+        </div>
+        {allTags.length > 0 && (
+          <div className="flex flex-wrap gap-1">
+            {allTags.map((tag) => (
+              <Button
+                key={tag}
+                size="sm"
+                variant={tagFilter === tag ? "secondary" : "ghost"}
+                className="h-6 px-2 text-xs"
+                onClick={() => setTagFilter(tagFilter === tag ? "" : tag)}
+              >
+                #{tag}
+              </Button>
+            ))}
+          </div>
+        )}
+        <div className="flex-1 overflow-y-auto space-y-1">
+          {visible.length === 0 ? (
+            <div className="text-center py-8 text-muted-foreground">
+              <NotebookPen size={40} className="mx-auto mb-2 opacity-50" />
+              <p className="text-sm">No prompts yet</p>
+            </div>
+          ) : (
+            visible.map((prompt) => (
+              <button
+                type="button"
+                key={prompt.id}
+                onClick={() => edit(prompt)}
+                className={`w-full text-left rounded-md px-3 py-2 text-sm hover:bg-muted ${
+                  prompt.id === draft.id ? "bg-muted" : ""
+                }`}
+              >
+                <div className="font-medium truncate">{prompt.name}</div>
+                <div className="text-xs text-muted-foreground">
+                  {prompt.kind} · v{prompt.version}
+                  {prompt.variables.length > 0 &&
+                    ` · ${prompt.variables.length} variable${prompt.variables.length === 1 ? "" : "s"}`}
+                </div>
+              </button>
+            ))
+          )}
+        </div>
+        <div className="flex gap-2">
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => handleExport("yaml")}
+          >
+            <Download size={14} className="mr-1" />
+            YAML
+          </Button>
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => handleExport("json")}
+          >
+            <Download size={14} className="mr-1" />
+            JSON
+          </Button>
+          <label className="inline-flex items-center text-sm cursor-pointer rounded-md border border-input px-3 h-8 hover:bg-muted">
+            <Upload size={14} className="mr-1" />
+            Import
+            <input
+              type="file"
+              accept=".json,.yaml,.yml"
+              className="hidden"
+              onChange={(e) => {
+                handleImport(e.currentTarget.files?.[0]);
+                e.currentTarget.value = "";
+              }}
+            />
+          </label>
+        </div>
+      </div>
+
+      {/* Editor */}
+      <div className="flex-1 flex flex-col gap-3">
+        <div className="flex gap-2">
+          <Input
+            value={draft.name}
+            placeholder="Name"
+            onInput={(e) =>
+              setDraft({ ...draft, name: (e.target as HTMLInputElement).value })
+            }
+          />
+          <Select
+            value={draft.kind}
+            onValueChange={(value: string) =>
+              setDraft({ ...draft, kind: value as PromptKind })
+            }
+          >
+            <SelectTrigger className="w-40">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="system">System prompt</SelectItem>
+              <SelectItem value="user">User template</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
+        <Input
+          value={draft.description ?? ""}
+          placeholder="Description"
+          onInput={(e) =>
+            setDraft({
+              ...draft,
+              description: (e.target as HTMLInputElement).value,
+            })
+          }
+        />
+        <Input
+          value={tagsText}
+          placeholder="Tags, comma separated"
+          onInput={(e) => setTagsText((e.target as HTMLInputElement).value)}
+        />
+        <Textarea
+          value={draft.content}
+          placeholder="Prompt text. Use {{variable}} for values filled in when it's used."
+          className="flex-1 min-h-48 font-mono text-sm"
+          onInput={(e) =>
+            setDraft({
+              ...draft,
+              content: (e.target as HTMLTextAreaElement).value,
+            })
+          }
+        />
+        {selected && selected.versions.length > 1 && (
+          <div className="flex items-center gap-2 text-sm text-muted-foreground">
+            <span>History:</span>
+            <Select
+              value=""
+              onValueChange={(value: string) => {
+                const version = selected.versions.find(
+                  (v) => String(v.version) === value,
+                );
+                if (version) setDraft({ ...draft, content: version.content });
+              }}
+            >
+              <SelectTrigger className="w-64" size="sm">
+                <SelectValue placeholder="Load an earlier version" />
+              </SelectTrigger>
+              <SelectContent>
+                {[...selected.versions].reverse().map((version) => (
+                  <SelectItem
+                    key={version.version}
+                    value={String(version.version)}
+                  >
+                    v{version.version} ·{" "}
+                    {new Date(version.createdAt).toLocaleDateString()}
+                    {version.note ? ` · ${version.note}` : ""}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+        )}
+        <div className="flex items-center gap-2">
+          {draft.id && (
+            <Input
+              value={draft.note ?? ""}
+              placeholder="What changed (optional)"
+              className="flex-1"
+              onInput={(e) =>
+                setDraft({
+                  ...draft,
+                  note: (e.target as HTMLInputElement).value,
+                })
+              }
+            />
+          )}
+          <Button onClick={handleSave}>Save</Button>
+          {draft.id && (
+            <Button variant="destructive" onClick={handleDelete}>
+              <Trash2 size={14} />
+            </Button>
+          )}
+        </div>
+      </div>
+
+      {ToastComponent}
+    </div>
+  );
+}